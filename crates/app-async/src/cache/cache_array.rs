@@ -42,15 +42,34 @@ type CacheBuffer = DashMap<String, CacheItem>;
 #[derive(Clone, Debug)]
 pub struct ArrayCache {
     is_maintained: Arc<AtomicBool>,
-    buffer: Arc<CacheBuffer>
+    buffer: Arc<CacheBuffer>,
+    hits: Counter,
+    misses: Counter
 }
 
 impl Cacher<ArrayCache> {
     pub fn from_static() -> &'static Self {
         static INSTANCE: LazyLock<Cacher<ArrayCache>> = LazyLock::new(|| {
+            let buffer = Arc::new(DashMap::with_capacity(*DEFAULT_CACHE_CAPACITY));
+            let metrics = Metrics::from_static();
+            let labels = [("cache", "array")];
+
+            metrics.add_collector({
+                let buffer = buffer.clone();
+                move |metrics| {
+                    metrics
+                        .gauge("cache_items", "Number of cache items", &labels)
+                        .set(buffer.len() as f64);
+                }
+            });
+
             Cacher::with(ArrayCache {
                 is_maintained: Default::default(),
-                buffer: Arc::new(DashMap::with_capacity(*DEFAULT_CACHE_CAPACITY))
+                buffer,
+                hits: metrics
+                    .counter("cache_hits_total", "Number of cache hits", &labels),
+                misses: metrics
+                    .counter("cache_misses_total", "Number of cache misses", &labels)
             })
         });
 
@@ -87,13 +106,17 @@ impl Cache for ArrayCache {
 
         match self.buffer.get(&key) {
             Some(v) if v.expired > now() => {
+                self.hits.inc();
                 v.data
                     .clone()
                     .downcast::<T>()
                     .map_err(|_| "Invalid downcast type.")?
                     .into_ok()
             },
-            _ => Ok(None)
+            _ => {
+                self.misses.inc();
+                Ok(None)
+            }
         }
     }
 
@@ -4,7 +4,9 @@ use {
     futures::lock::Mutex,
     sqlx::{
         Database,
-        pool::{Pool, PoolOptions}
+        pool::{Pool, PoolOptions},
+        postgres::PgConnectOptions,
+        sqlite::SqliteConnectOptions
     },
     std::{
         any::Any,
@@ -22,7 +24,7 @@ static POOLS: LazyLock<Mutex<PoolsMap>> = LazyLock::new(Default::default);
 
 static DEFAULT_CONFIG: LazyLock<Arc<DbConfig>> = LazyLock::new(|| {
     let mut config = DbConfig::default();
-    config.load_env();
+    let _ = config.load_env();
     config.into()
 });
 
@@ -57,10 +59,68 @@ pub async fn db_pool<D: Database>(config: Option<&Arc<DbConfig>>) -> Ok<Arc<Pool
         .map_err(|_| ERR_INVALID_DOWNCAST_TO_POOL)?;
 
     pools.insert(config.clone(), pool);
+    db_pool_metrics(config, &pool_ref);
 
     Ok(pool_ref)
 }
 
+/// Registers collector of pool usage, gauges are zero after the pool is dropped
+fn db_pool_metrics<D: Database>(config: &DbConfig, pool: &Arc<Pool<D>>) {
+    let name = db_name(&config.url);
+    let pool = Arc::downgrade(pool);
+
+    Metrics::from_static().add_collector(move |metrics| {
+        let (size, idle, max) = pool
+            .upgrade()
+            .map(|pool| {
+                (
+                    pool.size(),
+                    pool.num_idle() as u32,
+                    pool.options().get_max_connections()
+                )
+            })
+            .unwrap_or_default();
+        let gauge = |state: &str| {
+            metrics.gauge(
+                "db_pool_connections",
+                "Number of database pool connections",
+                &[("pool", &name), ("state", state)]
+            )
+        };
+
+        gauge("idle").set(idle as f64);
+        gauge("used").set(size.saturating_sub(idle) as f64);
+        gauge("max").set(max as f64);
+    });
+}
+
+/// Name of database by connect options of url, i.e. `dbname` of postgres or
+/// file name of sqlite, credentials of url are never included
+fn db_name(url: &str) -> String {
+    if url.starts_with("sqlite:") {
+        return url
+            .parse::<SqliteConnectOptions>()
+            .ok()
+            .and_then(|options| {
+                options
+                    .get_filename()
+                    .file_name()
+                    .map(|name| name.to_string_lossy().into())
+            })
+            .unwrap_or_default();
+    }
+
+    url.parse::<PgConnectOptions>()
+        .map(|options| {
+            // postgres connects to database of user by default
+            options
+                .get_database()
+                .unwrap_or(options.get_username())
+                .to_string()
+        })
+        .unwrap_or_default()
+}
+
 pub async fn db_pool_reset(config: Option<&Arc<DbConfig>>) {
     if let Some(config) = config {
         POOLS.lock().await.remove(config);
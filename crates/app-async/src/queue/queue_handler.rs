@@ -7,7 +7,7 @@ use {
     core::{str::FromStr, time::Duration},
     futures::FutureExt,
     sqlx::{Acquire, Executor, Pool, Postgres, types::Uuid},
-    std::sync::Arc,
+    std::{sync::Arc, time::Instant},
     tokio::{spawn, task::JoinHandle, time::sleep}
 };
 
@@ -65,25 +65,37 @@ impl QueueHandler {
                 let conn = db_pool.acquire().await?;
 
                 if let Some(task) = QueueTask::start_process(&id, conn).await? {
-                    let handler = match handlers.get(task.name.as_str()) {
-                        Some(handler) => handler,
-                        None => {
-                            let error = format!("Undefined task name '{}'", &task.name);
-                            task.finish_process(Some(&error), conn).await?;
-                            log::error!("{id}: {error}");
-                            return ok();
-                        }
-                    };
-
-                    let error = match handler.handle(&task).await {
-                        Ok(..) => None,
-                        Err(e) => {
-                            log::error!("{id}: {e}");
-                            Some(format!("{e}"))
-                        }
-                    };
-
-                    task.finish_process(error.as_deref(), conn).await?;
+                    let fields =
+                        [("task_id", id.to_string()), ("task", task.name.clone())];
+                    LogContext::scope(fields, async move {
+                        let handler = match handlers.get(task.name.as_str()) {
+                            Some(handler) => handler,
+                            None => {
+                                let error =
+                                    format!("Undefined task name '{}'", &task.name);
+                                task.finish_process(Some(&error), conn).await?;
+                                log::error!("{id}: {error}");
+                                Self::metrics(&task.name, "undefined", None);
+                                return ok();
+                            }
+                        };
+
+                        let start = Instant::now();
+                        let error = match handler.handle(&task).await {
+                            Ok(..) => None,
+                            Err(e) => {
+                                log::error!("{id}: {e}");
+                                Some(format!("{e}"))
+                            }
+                        };
+                        let status = if error.is_some() { "error" } else { "ok" };
+                        Self::metrics(&task.name, status, Some(start));
+
+                        task.finish_process(error.as_deref(), conn).await?;
+
+                        ok()
+                    })
+                    .await?;
                 }
 
                 ok()
@@ -91,4 +103,28 @@ impl QueueHandler {
             .boxed()
         })
     }
+
+    /// Counts task outcome and observes duration of handling
+    fn metrics(task: &str, status: &str, start: Option<Instant>) {
+        let metrics = Metrics::from_static();
+
+        metrics
+            .counter(
+                "queue_tasks_total",
+                "Number of processed queue tasks",
+                &[("task", task), ("status", status)]
+            )
+            .inc();
+
+        if let Some(start) = start {
+            metrics
+                .histogram(
+                    "queue_task_duration_seconds",
+                    "Duration of queue task handling",
+                    &[("task", task)],
+                    &[]
+                )
+                .observe_since(start);
+        }
+    }
 }
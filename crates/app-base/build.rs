@@ -66,6 +66,8 @@ fn main() {
         .define("INI_MAX_LINE", "1000")
         .define("INI_ALLOW_BOM", "0")
         .define("INI_ALLOW_NO_VALUE", "1")
+        .define("INI_ALLOW_MULTILINE", "1")
+        .define("INI_HANDLER_LINENO", "1")
         .define("INI_STOP_ON_FIRST_ERROR", "1")
         .shared_flag(true)
         //.static_flag(true)
@@ -83,6 +85,7 @@ fn main() {
         .blocklist_type("__uint64_t")
         .blocklist_type("FILE")
         .use_core()
+        .clang_arg("-DINI_HANDLER_LINENO=1")
         .header("vendor/inih/ini.h")
         .allowlist_item("ini_.*")
         .blocklist_function("ini_parse_file")
@@ -8,13 +8,20 @@ use {core::ffi::c_char, core::ffi::c_int};
 use {
     super::AppConfig,
     crate::{app::AppConfigExt, prelude::*},
-    alloc::{boxed::Box, format, string::ToString, sync::Arc, vec::Vec},
+    alloc::{
+        borrow::Cow,
+        boxed::Box,
+        format,
+        string::{String, ToString},
+        sync::Arc,
+        vec::Vec
+    },
     core::{
         mem::forget,
         ops::{Deref, DerefMut},
-        ptr::{addr_eq, fn_addr_eq}
-    },
-    log::set_max_level
+        ptr::{addr_eq, fn_addr_eq},
+        time::Duration
+    }
 };
 
 #[repr(C)]
@@ -29,6 +36,19 @@ pub enum AppEvent {
     APP_END
 }
 
+impl AppEvent {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::APP_PRE_INIT => "PRE_INIT",
+            Self::APP_INIT => "INIT",
+            Self::APP_BOOT => "BOOT",
+            Self::APP_SETUP => "SETUP",
+            Self::APP_RUN => "RUN",
+            Self::APP_END => "END"
+        }
+    }
+}
+
 #[derive(Default)]
 pub struct App<C>
 where
@@ -39,6 +59,10 @@ where
     config: Arc<AppConfig<C>>,
     modules: IndexSet<AppModule<C>>,
     commands: IndexMap<&'static str, AppModule<C>>,
+    async_commands: IndexMap<&'static str, AppModuleAsync<C>>,
+    runtime: Option<Arc<AppRuntime>>,
+    module_names: IndexMap<AppModule<C>, &'static str>,
+    timings: Arc<AppTimings>,
     pub clear_global: bool
 }
 
@@ -81,8 +105,14 @@ where
     fn drop(&mut self) {
         let _ = self.trigger_event(AppEvent::APP_END);
 
+        if self.args.get_flag("timings") == Ok(true) {
+            eprint!("{}", self.timings);
+        }
+
         core::mem::take(&mut self.di);
         core::mem::take(&mut self.commands);
+        core::mem::take(&mut self.async_commands);
+        self.runtime.take();
         core::mem::take(&mut self.modules);
 
         let global_di = unsafe { Di::from_static_mut() };
@@ -101,7 +131,7 @@ where
 
         Env::is_debug().then(|| log::debug!("App finished"));
 
-        unsafe { Logger::from_static_mut().log_close() };
+        Logger::from_static().log_close();
     }
 }
 
@@ -109,6 +139,9 @@ impl<C> App<C>
 where
     C: AppConfigExt
 {
+    /// Command which runs several commands, i.e. `app run serve,worker`
+    pub const RUN_COMMAND: &str = "run";
+
     pub fn new(modules: impl IntoIterator<Item = AppModule<C>>) -> Self {
         let mut app = Self {
             di: Default::default(),
@@ -118,15 +151,22 @@ where
                 ("env-file", None, None),
                 ("debug:b", None, None),
                 ("version:b", None, None),
+                ("timings:b", None, None),
                 ("help:b", "-h".into(), None)
             ])
             .unwrap(),
             config: Arc::new(AppConfig::<C>::default()),
             modules: Default::default(),
             commands: Default::default(),
+            async_commands: Default::default(),
+            runtime: None,
+            module_names: Default::default(),
+            timings: Default::default(),
             clear_global: true
         };
 
+        app.di.add_ref(app.timings.clone());
+
         for module in modules {
             app.register_module(module);
         }
@@ -180,8 +220,16 @@ where
         #[cfg(not(feature = "std"))] argc: c_int,
         #[cfg(not(feature = "std"))] argv: *const *const c_char
     ) -> Ok<&mut Self> {
+        let start = AppTimings::now();
         dotenv(false);
+        self.add_timing("DotEnv", "LOAD", start);
+
         let log = log_init();
+        log::debug!("Loaded env files: {:?}", DotEnv::files());
+        #[cfg(feature = "std")]
+        let argv: Vec<String> = std::env::args().collect();
+        #[cfg(not(feature = "std"))]
+        let argv = unsafe { Args::collect_argc(argc, argv)? };
         let args = &mut self.args;
 
         #[cfg(feature = "std")]
@@ -194,12 +242,7 @@ where
         // Skips undefined arguments for preloading.
         //
         args.set_undefined(ArgUndef::Skip);
-        #[cfg(feature = "std")]
-        args.parse_args(std::env::args().collect())?;
-        #[cfg(not(feature = "std"))]
-        unsafe {
-            args.parse_argc(argc, argv)?
-        };
+        args.parse_args(argv.clone())?;
         // Throws error if undefined arguments are detected for next load.
         args.set_undefined(ArgUndef::Error);
 
@@ -209,8 +252,8 @@ where
 
         if args.get("debug").unwrap() == Some("1") {
             setenv("APP_DEBUG", "1");
-            if log::max_level() < log::LevelFilter::Debug {
-                set_max_level(log::LevelFilter::Debug);
+            if log.level < log::LevelFilter::Debug {
+                log.set_level(log::LevelFilter::Debug);
             }
         }
 
@@ -232,6 +275,15 @@ where
 
         // Correct command name only after registration commands
         self.correct_command_name()?;
+        if self.is_run_command()? {
+            // Preloads commands of `app run` for modules initialization
+            let args = &mut self.args;
+            args.add_options([("run-commands", "2".into(), None)])
+                .unwrap();
+            args.set_undefined(ArgUndef::Skip);
+            args.parse_args(argv.clone())?;
+            args.set_undefined(ArgUndef::Error);
+        }
         let args = &mut self.args;
         self.config.try_mut().unwrap().init_args(args);
 
@@ -246,12 +298,7 @@ where
         if Env::is_test() {
             args.set_undefined(ArgUndef::Skip);
         }
-        #[cfg(feature = "std")]
-        args.parse_args(std::env::args().collect())?;
-        #[cfg(not(feature = "std"))]
-        unsafe {
-            args.parse_argc(argc, argv)?
-        };
+        args.parse_args(argv)?;
         // Correct command name again after parse args
         self.correct_command_name()?;
         let args = &mut self.args;
@@ -267,9 +314,15 @@ where
             )
         });
 
+        let start = AppTimings::now();
         self.config.try_mut().unwrap().load(Some(args))?;
+        self.add_timing("AppConfig", "LOAD", start);
 
+        Time::init(&self.config.base)?;
         log.configure(&self.config.base.log)?;
+        CrashReport::init(self);
+        filters::Phone::set_default_country(self.config.base.country());
+        I18n::init(&self.config.base, &self.config.dirs)?;
 
         Env::is_debug().then(|| log::debug!("Loaded {:#?}", &self.config));
 
@@ -279,10 +332,110 @@ where
         Ok(self)
     }
 
+    /// Runs command or several commands concurrently on the app runtime
+    ///
+    /// Several commands are separated by comma: `app serve,worker`,
+    /// `app run serve,worker` or `app.run = serve,worker` in config.
     pub fn run(&mut self) -> Void {
-        let command = self.command()?;
-        let module = self.get_module_by_command(command)?;
-        self.trigger_module_event(module, AppEvent::APP_RUN)
+        let commands = self.run_commands()?;
+
+        if let [command] = commands.as_slice() {
+            let module = self.get_module_by_command(command)?;
+            return self.trigger_module_event(module, AppEvent::APP_RUN);
+        }
+
+        // Help and version are shown by module instead of running
+        if self.args.get_flag("help")? {
+            Err(format!(
+                "Help is shown for one command, i.e. `{} --help`",
+                commands[0]
+            ))?;
+        }
+        if self.args.get_flag("version")? {
+            let module = self.get_module_by_command(&commands[0])?;
+            return self.trigger_module_event(module, AppEvent::APP_RUN);
+        }
+
+        let mut futures = Vec::new();
+        for command in commands {
+            let Some(run_async) = self.async_commands.get(command.as_str()).copied()
+            else {
+                return Err(format!("Invalid command: '{command}'").into());
+            };
+
+            Env::is_debug()
+                .then(|| log::debug!("Raise event: APP_RUN (async: {command})"));
+
+            let Some(future) = run_async(self)? else {
+                return Err(format!(
+                    "Command '{command}' can not run with other commands"
+                )
+                .into());
+            };
+            futures.push((command, future));
+        }
+
+        let future: AppFuture = Box::pin(AppRunAll::new(futures));
+        let start = AppTimings::now();
+        let res = match self.runtime.clone() {
+            Some(runtime) => runtime(future),
+            None => app_block_on(future)
+        };
+        self.add_timing("AppRunAll", AppEvent::APP_RUN.name(), start);
+
+        res
+    }
+
+    /// Commands to run, see `App::run`
+    pub fn run_commands(&self) -> Ok<Vec<String>> {
+        let mut commands = self.command()?;
+
+        if self.is_run_command()? {
+            if let Some(run) = self.args.get("run-commands").unwrap_or_default() {
+                commands = run;
+            } else if let Some(run) = self.config.run.as_deref() {
+                commands = run;
+            }
+        }
+
+        let commands: Vec<String> = commands
+            .split(',')
+            .map(str::trim)
+            .filter(|c| c.is_empty() == false)
+            .map(Into::into)
+            .collect();
+
+        if commands.is_empty() {
+            Err("Argument 'command' not specified")?;
+        }
+
+        commands.into_ok()
+    }
+
+    /// Command is running, alone or with other commands
+    pub fn is_command(&self, command: &str) -> bool {
+        self.run_commands()
+            .is_ok_and(|commands| commands.iter().any(|c| c == command))
+    }
+
+    /// Command is `run` and no module has such command
+    fn is_run_command(&self) -> Ok<bool> {
+        (self.command()? == Self::RUN_COMMAND
+            && self.commands.contains_key(Self::RUN_COMMAND) == false)
+            .into_ok()
+    }
+
+    /// Sets runtime of concurrent commands, i.e. tokio runtime shared by them
+    pub fn set_runtime(
+        &mut self,
+        runtime: impl Fn(AppFuture) -> Void + Send + Sync + 'static
+    ) {
+        self.runtime = Some(Arc::new(runtime));
+    }
+
+    #[inline]
+    pub fn has_runtime(&self) -> bool {
+        self.runtime.is_some()
     }
 
     pub fn command(&self) -> Ok<&str> {
@@ -306,9 +459,20 @@ where
     }
 
     pub fn unregister_command(&mut self, command: &str) -> Option<AppModule<C>> {
+        self.async_commands.swap_remove(command);
         self.commands.swap_remove(command)
     }
 
+    /// Registers future of command for running with other commands
+    pub fn register_command_async(
+        &mut self,
+        command: &'static str,
+        module: AppModuleAsync<C>
+    ) -> &mut Self {
+        self.async_commands.insert(command, module);
+        self
+    }
+
     pub fn register_module(&mut self, module: AppModule<C>) -> &mut Self {
         self.modules.insert(module);
         self
@@ -330,11 +494,38 @@ where
         None
     }
 
+    /// Name of module for reports, address of module if name is not set
+    pub fn module_name(&self, module: AppModule<C>) -> Cow<'static, str> {
+        match self.module_names.get(&module) {
+            Some(name) => Cow::Borrowed(*name),
+            None => Cow::Owned(format!("{module:p}"))
+        }
+    }
+
+    pub fn set_module_name(&mut self, module: AppModule<C>, name: &'static str) {
+        self.module_names.insert(module, name);
+    }
+
+    /// Durations of module events and config loading
+    #[inline]
+    pub fn timings(&self) -> &AppTimings {
+        &self.timings
+    }
+
+    fn add_timing(
+        &self,
+        name: impl Into<Cow<'static, str>>,
+        event: &'static str,
+        start: Duration
+    ) {
+        self.timings.add(name, event, start);
+    }
+
     fn trigger_event(&mut self, event: AppEvent) -> Void {
         Env::is_debug().then(|| log::debug!("Raise event: {event:#?}"));
 
         for module in self.modules.clone() {
-            module(self, event)?;
+            self.call_module(module, event)?;
         }
 
         ok()
@@ -344,7 +535,15 @@ where
         Env::is_debug()
             .then(|| log::debug!("Raise event: {event:#?} (module: {module:p})"));
 
-        module(self, event)
+        self.call_module(module, event)
+    }
+
+    fn call_module(&mut self, module: AppModule<C>, event: AppEvent) -> Void {
+        let start = AppTimings::now();
+        let res = module(self, event);
+        self.add_timing(self.module_name(module), event.name(), start);
+
+        res
     }
 
     pub fn get_module_by_command(&self, command: &str) -> Ok<AppModule<C>> {
@@ -381,9 +580,19 @@ where
     ) {
         eprintln!("PANIC: {info}");
         log::error!("{info}");
-        unsafe {
-            Di::from_static_mut().clear();
-            Logger::from_static_mut().log_close();
+
+        #[cfg(feature = "std")]
+        let message = info.payload_as_str().unwrap_or("Box<dyn Any>").to_string();
+        #[cfg(not(feature = "std"))]
+        let message = info.message().to_string();
+        let location = info.location().map(|l| l.to_string());
+
+        match CrashReport::write(&message, location) {
+            Ok(path) => eprintln!("Crash report: {path}"),
+            Err(e) => eprintln!("Could not write crash report: {e}")
         }
+
+        unsafe { Di::from_static_mut().clear() };
+        log::logger().flush();
     }
 }
@@ -26,6 +26,7 @@ pub trait AppConfigExt:
     + 'static
     + Debug
     + for<'a> Extend<(&'a str, Option<&'a str>)>
+    + for<'a> TryExtend<(&'a str, Option<&'a str>)>
     + for<'a> Iter<'a, (&'static str, String)>
     + LoadArgs
     + LoadEnv
@@ -41,6 +42,8 @@ pub struct AppConfig<C: AppConfigExt> {
     pub name: Box<str>,
     pub version: Box<str>,
     pub env_file: Option<Box<str>>,
+    /// Commands running together by `app run`, i.e. `serve,worker`
+    pub run: Option<Box<str>>,
     pub base: Arc<BaseConfig>,
     pub dirs: Arc<Dirs>,
     pub external: Arc<C>
@@ -65,6 +68,7 @@ where
             version: concat!("v", env!("APP_VERSION"), " (", env!("BUILD_TIME"), ")")
                 .into(),
             env_file: None,
+            run: None,
             base: Default::default(),
             dirs: Default::default(),
             external: Default::default()
@@ -135,8 +139,13 @@ where
                 ("app.features", &Self::FEATURES),
                 ("app.profile", &env!("BUILD_PROFILE")),
                 ("app.default_command", &C::COMMAND),
+                (
+                    "app.run",
+                    &self.run.as_ref().map(|v| v.as_ref()).unwrap_or("")
+                ),
                 // env
                 ("env.env", &Env::env() as &dyn Display),
+                ("env.chain", &Env::chain().join(",")),
                 ("env.is_prod", &Env::is_prod()),
                 ("env.is_dev", &Env::is_dev()),
                 ("env.is_test", &Env::is_test()),
@@ -170,26 +179,28 @@ where
         }
     }
 
-    fn load_args(&mut self, args: &Args) {
-        self.extend(
+    fn load_args(&mut self, args: &Args) -> Result<(), ExtendErrors> {
+        let mut errors = ExtendErrors::default();
+        errors.merge(ExtendErrors::from_args(
+            self,
+            args,
             [
-                ("debug", args.get("debug")),
-                ("env_file", args.get("env-file")),
-                ("command", args.get("command"))
+                ("debug", "debug"),
+                ("env_file", "env-file"),
+                ("command", "command")
             ]
-            .iter()
-            .map(convert::tuple_result_option_str)
-        );
+        ));
 
         let list = [
-            self.base.try_mut().unwrap() as &mut dyn LoadArgs,
-            self.dirs.try_mut().unwrap(),
-            self.external.try_mut().unwrap()
+            ("base", self.base.try_mut().unwrap() as &mut dyn LoadArgs),
+            ("dirs", self.dirs.try_mut().unwrap()),
+            ("", self.external.try_mut().unwrap())
         ];
 
-        for item in list {
-            item.load_args(args);
+        for (key, item) in list {
+            errors.merge(item.load_args(args).map_err(|e| e.key(key)));
         }
+        errors.into_result()
     }
 }
 
@@ -213,16 +224,20 @@ impl<C> LoadEnv for AppConfig<C>
 where
     C: AppConfigExt
 {
-    fn load_env(&mut self) {
+    fn load_env(&mut self) -> Result<(), ExtendErrors> {
+        let mut errors = ExtendErrors::default();
+        errors.merge(ExtendErrors::from_env(self, [("run", "APP_RUN")]));
+
         let list = [
-            self.base.try_mut().unwrap() as &mut dyn LoadEnv,
-            self.dirs.try_mut().unwrap(),
-            self.external.try_mut().unwrap()
+            ("base", self.base.try_mut().unwrap() as &mut dyn LoadEnv),
+            ("dirs", self.dirs.try_mut().unwrap()),
+            ("", self.external.try_mut().unwrap())
         ];
 
-        for item in list {
-            item.load_env();
+        for (key, item) in list {
+            errors.merge(item.load_env().map_err(|e| e.key(key)));
         }
+        errors.into_result()
     }
 }
 
@@ -246,12 +261,17 @@ where
         (**self).as_mut().try_mut()
     }
 
+    /// Loads config files, environment and command line arguments
+    ///
+    /// All invalid values are returned together as `ExtendErrors` with keys
+    /// and sources of values.
     pub fn load(&mut self, args: Option<&Args>) -> Ok<&mut Self> {
+        // Errors of dirs are returned by loading of `self.dirs` below
         let mut dirs = Dirs::default();
-        dirs.load_env();
+        let _ = dirs.load_env();
 
         if let Some(args) = args {
-            dirs.load_args(args);
+            let _ = dirs.load_args(args);
         }
 
         dirs.init();
@@ -272,30 +292,56 @@ where
 
         let user_config_file =
             format!("{}/{}", &dirs.user_config, Self::CONFIG_FILE_NAME);
-        match Ini::from_file(&user_config_file) {
+        let user_ini = match Ini::from_file(&user_config_file) {
             Ok(user_ini) => {
                 Env::is_debug().then(|| log::debug!("Loading {user_config_file}"));
-                ini.extend(
-                    user_ini
-                        .into_iter()
-                        .map(|(n, v)| (n.into(), v.map(|v| v.into())))
-                );
+                user_ini
             },
             Err(e) => {
                 match e.downcast_ref::<IniError>() {
-                    Some(IniError::FileNotFound(..)) => (),
+                    Some(IniError::FileNotFound(..)) => Ini::default(),
                     _ => Err(e)?
                 }
             },
         };
+        ini.extend(
+            (&user_ini)
+                .into_iter()
+                .map(|(n, v)| (n.into(), v.map(|v| v.into())))
+        );
 
-        self.extend(&ini);
-        self.external.try_mut().unwrap().extend(&ini);
+        let mut errors = ExtendErrors::default();
+        let (config_source, user_config_source) = (
+            format!("file {config_file}"),
+            format!("file {user_config_file}")
+        );
+        let items = || {
+            (&ini).into_iter().map(|(name, value)| {
+                let source = match user_ini.contains_key(name) {
+                    true => &user_config_source,
+                    false => &config_source
+                };
+                (name, value, source)
+            })
+        };
 
-        self.load_env();
+        errors.merge(ExtendErrors::extend_from(self, items()));
+        if let Some(Some(run)) = ini.get("app.run") {
+            self.run = Some(run.clone());
+        }
+        errors.merge(ExtendErrors::extend_from(
+            self.external.try_mut().unwrap(),
+            items()
+        ));
+
+        errors.merge(self.load_env());
 
         if let Some(args) = args {
-            self.load_args(args);
+            errors.merge(self.load_args(args));
+        }
+
+        if errors.is_empty() == false {
+            Err(errors)?
         }
 
         self.dirs.try_mut().unwrap().init();
@@ -4,6 +4,7 @@ use {
 };
 
 pub type AppModule<C> = fn(&mut App<C>, AppEvent) -> Void;
+pub type AppModuleAsync<C> = fn(&mut App<C>) -> Ok<Option<AppFuture>>;
 
 pub trait AppModuleExt: Default + Send + Sync + 'static {
     const COMMAND: &str = "";
@@ -17,8 +18,11 @@ pub trait AppModuleExt: Default + Send + Sync + 'static {
                 app.add(Self::default());
             }
 
+            app.set_module_name(Self::handle, type_name_simple!(Self));
+
             if Self::COMMAND.is_empty() == false {
                 app.register_command(Self::COMMAND, Self::handle);
+                app.register_command_async(Self::COMMAND, Self::handle_async);
             }
         }
 
@@ -74,6 +78,13 @@ pub trait AppModuleExt: Default + Send + Sync + 'static {
         }
     }
 
+    /// Calls `run_async()` of module
+    fn handle_async(app: &mut App<Self::Config>) -> Ok<Option<AppFuture>> {
+        let module = unsafe { &mut *(app.get_mut::<Self>()? as *mut Self) };
+
+        module.run_async(app)
+    }
+
     #[allow(unused_variables)]
     fn init(&mut self, app: &mut App<Self::Config>) -> Void {
         ok()
@@ -94,18 +105,20 @@ pub trait AppModuleExt: Default + Send + Sync + 'static {
         ok()
     }
 
+    /// Future of command for running concurrently with other commands on
+    /// the app runtime. Commands without it can run only alone.
+    #[allow(unused_variables)]
+    fn run_async(&mut self, app: &mut App<Self::Config>) -> Ok<Option<AppFuture>> {
+        Ok(None)
+    }
+
     #[allow(unused_variables)]
     fn end(&mut self, app: &mut App<Self::Config>) -> Void {
         ok()
     }
 
     fn version(&self, app: &mut App<Self::Config>) -> Void {
-        println!(
-            "{name} {version}",
-            name = app.config().name,
-            version = app.config().version
-        );
-        ok()
+        print_version(app)
     }
 
     fn help(&self, app: &mut App<Self::Config>) -> Void {
@@ -127,6 +140,7 @@ Options:
     --env-file file - loads env vars from file
     --debug         - enable debuging
     --version       - show current version
+    --timings       - show durations of boot steps on exit
 "#,
             Self::COMMAND,
             desc = Self::DESCRIPTION,
@@ -139,3 +153,53 @@ Options:
         ok()
     }
 }
+
+/// Lifecycle of module declared by `#[app_module(...)]`, which implements
+/// `AppModuleExt` and calls these hooks after loading of its options
+///
+/// Hooks are named apart from methods of `AppModuleExt`, so both traits are
+/// in scope without ambiguity.
+pub trait AppModuleHooks: AppModuleExt {
+    #[allow(unused_variables)]
+    fn on_init(&mut self, app: &mut App<Self::Config>) -> Void {
+        ok()
+    }
+
+    #[allow(unused_variables)]
+    fn on_setup(&mut self, app: &mut App<Self::Config>) -> Void {
+        ok()
+    }
+
+    #[allow(unused_variables)]
+    fn on_boot(&mut self, app: &mut App<Self::Config>) -> Void {
+        ok()
+    }
+
+    #[allow(unused_variables)]
+    fn on_run(&mut self, app: &mut App<Self::Config>) -> Void {
+        ok()
+    }
+
+    #[allow(unused_variables)]
+    fn on_run_async(&mut self, app: &mut App<Self::Config>) -> Ok<Option<AppFuture>> {
+        Ok(None)
+    }
+
+    #[allow(unused_variables)]
+    fn on_end(&mut self, app: &mut App<Self::Config>) -> Void {
+        ok()
+    }
+
+    fn on_version(&self, app: &mut App<Self::Config>) -> Void {
+        print_version(app)
+    }
+}
+
+fn print_version<C: AppConfigExt>(app: &App<C>) -> Void {
+    println!(
+        "{name} {version}",
+        name = app.config().name,
+        version = app.config().version
+    );
+    ok()
+}
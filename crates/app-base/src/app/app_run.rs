@@ -0,0 +1,109 @@
+use {
+    crate::prelude::*,
+    alloc::{boxed::Box, format, string::String, vec::Vec},
+    core::{
+        future::Future,
+        pin::{Pin, pin},
+        task::{Context, Poll}
+    }
+};
+
+/// Future of command running concurrently with other commands
+pub type AppFuture = Pin<Box<dyn Future<Output = Void> + 'static>>;
+
+/// Runs future of commands until they are finished, i.e. on tokio runtime
+pub type AppRuntime = dyn Fn(AppFuture) -> Void + Send + Sync;
+
+/// Future of several commands
+///
+/// It is finished when the first command is finished or fails, i.e. a server
+/// is stopped. Other commands are shut down together with it, their futures
+/// are dropped.
+pub struct AppRunAll {
+    futures: Vec<(String, AppFuture)>
+}
+
+impl AppRunAll {
+    pub fn new(futures: impl IntoIterator<Item = (String, AppFuture)>) -> Self {
+        Self { futures: futures.into_iter().collect() }
+    }
+}
+
+impl Future for AppRunAll {
+    type Output = Void;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+        let mut finished = None;
+
+        // every command is polled, so all of them start before one finishes
+        for (i, (command, future)) in this.futures.iter_mut().enumerate() {
+            match future.as_mut().poll(cx) {
+                Poll::Ready(Ok(..)) => {
+                    finished.get_or_insert(i);
+                },
+                Poll::Ready(Err(e)) => {
+                    log::error!("Command '{command}' failed, shutting down: {e}");
+                    return Poll::Ready(Err(format!("{command}: {e}").into()));
+                },
+                Poll::Pending => ()
+            }
+        }
+
+        match finished {
+            Some(i) => {
+                let command = &this.futures[i].0;
+                Env::is_debug()
+                    .then(|| log::debug!("Command '{command}' finished, shutting down"));
+                Poll::Ready(ok())
+            },
+            None if this.futures.is_empty() => Poll::Ready(ok()),
+            None => Poll::Pending
+        }
+    }
+}
+
+/// Runs future on the current thread, it is the default runtime of app
+#[cfg(feature = "std")]
+pub fn app_block_on<F: Future>(future: F) -> F::Output {
+    use std::{
+        sync::Arc,
+        task::Wake,
+        thread::{Thread, current, park}
+    };
+
+    struct ThreadWaker(Thread);
+
+    impl Wake for ThreadWaker {
+        fn wake(self: Arc<Self>) {
+            self.0.unpark();
+        }
+    }
+
+    let waker = Arc::new(ThreadWaker(current())).into();
+    let mut cx = Context::from_waker(&waker);
+    let mut future = pin!(future);
+
+    loop {
+        match future.as_mut().poll(&mut cx) {
+            Poll::Ready(res) => return res,
+            Poll::Pending => park()
+        }
+    }
+}
+
+/// Runs future on the current thread, it is the default runtime of app
+#[cfg(not(feature = "std"))]
+pub fn app_block_on<F: Future>(future: F) -> F::Output {
+    let mut cx = Context::from_waker(core::task::Waker::noop());
+    let mut future = pin!(future);
+
+    loop {
+        match future.as_mut().poll(&mut cx) {
+            Poll::Ready(res) => return res,
+            Poll::Pending => unsafe {
+                libc::sched_yield();
+            }
+        }
+    }
+}
@@ -0,0 +1,102 @@
+use {
+    crate::prelude::*,
+    alloc::{borrow::Cow, vec::Vec},
+    core::{
+        fmt::{Debug, Display},
+        mem::zeroed,
+        time::Duration
+    }
+};
+
+/// Duration of one lifecycle step
+#[derive(Debug, Clone)]
+pub struct AppTiming {
+    /// Module name, i.e. `WebModule`, or `AppConfig` for config loading
+    pub name: Cow<'static, str>,
+    /// Event name, i.e. `BOOT`, or `LOAD` for config loading
+    pub event: &'static str,
+    pub duration: Duration
+}
+
+/// Durations of module events and config loading in order of execution
+///
+/// App keeps one shared instance in `Di` and adds timings to it in place, so
+/// modules get the current timings by `app.get::<AppTimings>()`.
+#[derive(Default)]
+pub struct AppTimings(SpinLock<Vec<AppTiming>>);
+
+impl Debug for AppTimings {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl AppTimings {
+    /// Current time of monotonic clock
+    pub fn now() -> Duration {
+        let mut time: libc::timespec = unsafe { zeroed() };
+        unsafe { libc::clock_gettime(libc::CLOCK_MONOTONIC, &mut time) };
+
+        Duration::new(time.tv_sec as u64, time.tv_nsec as u32)
+    }
+
+    /// Adds duration since *start*
+    pub fn add(
+        &self,
+        name: impl Into<Cow<'static, str>>,
+        event: &'static str,
+        start: Duration
+    ) -> &Self {
+        let timing = AppTiming {
+            name: name.into(),
+            event,
+            duration: Self::now().saturating_sub(start)
+        };
+        self.0.with(|list| list.push(timing));
+        self
+    }
+
+    /// Copy of the current timings
+    #[inline]
+    pub fn iter(&self) -> impl Iterator<Item = AppTiming> {
+        self.0.with(|list| list.clone()).into_iter()
+    }
+
+    /// Total duration of event, i.e. `BOOT`, of all modules
+    pub fn event(&self, event: &str) -> Duration {
+        self.iter()
+            .filter(|t| t.event == event)
+            .map(|t| t.duration)
+            .sum()
+    }
+
+    pub fn total(&self) -> Duration {
+        self.iter().map(|t| t.duration).sum()
+    }
+
+    /// Timings from longest to shortest
+    pub fn sorted(&self) -> Vec<AppTiming> {
+        let mut list: Vec<_> = self.iter().collect();
+        list.sort_by(|a, b| b.duration.cmp(&a.duration));
+        list
+    }
+}
+
+impl Display for AppTimings {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let ms = |d: Duration| d.as_secs_f64() * 1000.0;
+
+        writeln!(f, "Timings (total {:.3} ms):", ms(self.total()))?;
+        for timing in self.sorted() {
+            writeln!(
+                f,
+                "    {:>10.3} ms  {:<8}  {}",
+                ms(timing.duration),
+                timing.event,
+                timing.name
+            )?;
+        }
+
+        Ok(())
+    }
+}
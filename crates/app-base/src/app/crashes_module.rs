@@ -0,0 +1,90 @@
+use {
+    crate::prelude::*,
+    alloc::{format, string::String},
+    core::{ffi::c_void, marker::PhantomData, ptr::null}
+};
+
+#[unsafe(no_mangle)]
+extern "C" fn module_crashes(app: *mut app_c::App, event: AppEvent) -> *const c_void {
+    match CrashesModule::handle(unsafe { &mut *app }, event) {
+        Ok(..) => null(),
+        Err(e) => panic!("{e}")
+    }
+}
+
+#[derive(Default)]
+pub struct CrashesModule<C: AppConfigExt>(PhantomData<C>);
+
+impl<C> AppModuleExt for CrashesModule<C>
+where
+    C: AppConfigExt
+{
+    const COMMAND: &str = "crashes";
+    const DESCRIPTION: &str = "lists and shows crash reports";
+
+    type Config = C;
+
+    fn init(&mut self, app: &mut App<Self::Config>) -> Void {
+        if Self::COMMAND == app.command()? {
+            app.args_mut()
+                .add_options([("name", "2".into(), None)])
+                .unwrap();
+        }
+
+        ok()
+    }
+
+    fn run(&mut self, app: &mut App<Self::Config>) -> Void {
+        let dir = CrashReport::dir(&app.config().dirs);
+        let list = CrashReport::list(&dir)?;
+
+        match app.args().get("name").unwrap() {
+            None => {
+                for name in list {
+                    println!("{name}");
+                }
+            },
+            Some(name) => {
+                let file = if name == "last" {
+                    list.first().ok_or("There are no crash reports")?
+                } else {
+                    list.iter()
+                        .find(|f| f.as_str() == name || f.contains(name))
+                        .ok_or_else(|| format!("Crash report not found: {name}"))?
+                };
+
+                let content = Dirs::read_file(&format!("{dir}/{file}"))?;
+                print!("{}", String::from_utf8_lossy(&content));
+            }
+        }
+
+        ok()
+    }
+
+    fn help(&self, app: &mut App<Self::Config>) -> Void {
+        let config = app.config();
+
+        println!(
+            r#"
+Usage: {bin} {cmd} [name] [options]
+
+This command {desc}.
+
+Reports are written on panic to {dir}
+
+Arguments:
+    name - if defined, then it shows the report with this name or part of name,
+           `last` shows the newest report
+
+Options:
+    -h, --help  - show usage help
+"#,
+            bin = config.dirs.exe_file(),
+            cmd = Self::COMMAND,
+            desc = Self::DESCRIPTION,
+            dir = CrashReport::dir(&config.dirs)
+        );
+
+        ok()
+    }
+}
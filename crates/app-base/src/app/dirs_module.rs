@@ -0,0 +1,100 @@
+use {
+    crate::prelude::*,
+    alloc::format,
+    core::{ffi::c_void, marker::PhantomData, ptr::null}
+};
+
+#[unsafe(no_mangle)]
+extern "C" fn module_dirs(app: *mut app_c::App, event: AppEvent) -> *const c_void {
+    match DirsModule::handle(unsafe { &mut *app }, event) {
+        Ok(..) => null(),
+        Err(e) => panic!("{e}")
+    }
+}
+
+#[derive(Default)]
+pub struct DirsModule<C: AppConfigExt>(PhantomData<C>);
+
+impl<C> AppModuleExt for DirsModule<C>
+where
+    C: AppConfigExt
+{
+    const COMMAND: &str = "dirs";
+    const DESCRIPTION: &str = "displays, creates and checks directories";
+
+    type Config = C;
+
+    fn run(&mut self, app: &mut App<Self::Config>) -> Void {
+        let dirs = &app.config().dirs;
+        let mut failed = 0;
+
+        println!("mode: {}", dirs.mode());
+
+        for (name, path) in dirs.list() {
+            let writable = Dirs::WRITABLE.contains(&name);
+            let exists = Dirs::access(path, libc::F_OK);
+
+            let status = if exists == false && writable {
+                match Dirs::mkdir(path) {
+                    Ok(..)
+                        if Dirs::access(path, libc::R_OK | libc::W_OK | libc::X_OK) =>
+                    {
+                        "created"
+                    },
+                    Ok(..) => "created, not writable",
+                    Err(..) => "could not create"
+                }
+            } else if exists == false {
+                "missing"
+            } else if Dirs::access(path, libc::R_OK | libc::X_OK) == false {
+                "not readable"
+            } else if writable && Dirs::access(path, libc::W_OK) == false {
+                "not writable"
+            } else {
+                "ok"
+            };
+
+            if matches!(status, "ok" | "created" | "missing") == false {
+                failed += 1;
+            }
+
+            println!("{name:<12} {status:<22} {path}");
+        }
+
+        if failed > 0 {
+            Err(format!("Directories with invalid permissions: {failed}"))?;
+        }
+
+        ok()
+    }
+
+    fn help(&self, app: &mut App<Self::Config>) -> Void {
+        let config = app.config();
+
+        println!(
+            r#"
+Usage: {bin} {cmd} [options]
+
+This command {desc}.
+
+Directories which are written at runtime ({writable}) are created
+if missing. The command fails if any directory has invalid permissions.
+
+Install mode is set by DIRS_MODE env var or --dirs-mode option:
+    system   - FHS layout, config in /etc (default)
+    user     - XDG base directories of current user
+    portable - all directories are inside the directory of executable
+
+Options:
+    -h, --help              - show usage help
+    --dirs-mode mode        - install mode: system, user or portable
+"#,
+            bin = config.dirs.exe_file(),
+            cmd = Self::COMMAND,
+            desc = Self::DESCRIPTION,
+            writable = Dirs::WRITABLE.join(", ")
+        );
+
+        ok()
+    }
+}
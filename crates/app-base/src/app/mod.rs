@@ -4,9 +4,14 @@ mod app_config;
 mod app_simple_config;
 mod app_simple_module;
 mod app_config_module;
+mod crashes_module;
+mod dirs_module;
 mod app_module;
+mod app_run;
+mod app_timings;
 
 pub use {
-    app::*, app_config::*, app_config_module::*, app_module::*, app_simple_config::*,
-    app_simple_module::*
+    app::*, app_config::*, app_config_module::*, app_module::*, app_run::*,
+    app_simple_config::*, app_simple_module::*, app_timings::*, crashes_module::*,
+    dirs_module::*
 };
@@ -15,7 +15,7 @@ use {
 pub trait LoadArgs {
     fn init_args(&mut self, args: &mut Args);
 
-    fn load_args(&mut self, args: &Args);
+    fn load_args(&mut self, args: &Args) -> Result<(), ExtendErrors>;
 }
 
 type ArgsOptions = IndexMap<&'static str, Option<&'static str>>;
@@ -101,8 +101,12 @@ impl Args {
             }
 
             self.options.insert(n, o);
+            // keeps value set before, i.e. by `App::with_args`
             let n = n.split(':').next().unwrap();
-            self.arguments.insert(n.into(), v.map(|v| v.into()));
+            let value = self.arguments.entry(n.into()).or_default();
+            if value.is_none() {
+                *value = v.map(|v| v.into());
+            }
         }
 
         Ok(self)
@@ -113,6 +117,14 @@ impl Args {
         argc: c_int,
         argv: *const *const c_char
     ) -> Ok<&mut Self> {
+        self.parse_args(unsafe { Self::collect_argc(argc, argv)? })
+    }
+
+    /// Command line arguments of `main(argc, argv)`
+    pub unsafe fn collect_argc(
+        argc: c_int,
+        argv: *const *const c_char
+    ) -> Ok<Vec<String>> {
         let mut args = Vec::with_capacity(argc as usize);
 
         for arg in unsafe { slice::from_raw_parts(argv, argc as usize) } {
@@ -120,7 +132,7 @@ impl Args {
             args.push(arg);
         }
 
-        self.parse_args(args)
+        args.into_ok()
     }
 
     pub fn parse_args(&mut self, args: Vec<String>) -> Ok<&mut Self> {
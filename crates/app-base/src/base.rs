@@ -1,4 +1,5 @@
 use {
+    crate::serde::{JsonPatch, PatchJson, json_diff},
     ahash::AHasher,
     alloc::{
         boxed::Box,
@@ -211,6 +212,37 @@ where
     {
         serde_json::from_slice(value)
     }
+
+    /// Copy with applied JSON Merge Patch (RFC 7386)
+    fn json_merge_patched(
+        &self,
+        patch: serde_json::Value
+    ) -> Result<Self, serde_json::Error>
+    where
+        Self: Serialize + DeserializeOwned
+    {
+        let mut value = self.to_json()?;
+        value.merge_patch(patch);
+        Self::from_json(value)
+    }
+
+    /// Copy with applied JSON Patch (RFC 6902)
+    fn json_patched(&self, patch: &JsonPatch) -> Ok<Self>
+    where
+        Self: Serialize + DeserializeOwned
+    {
+        let mut value = self.to_json()?;
+        value.apply_patch(patch)?;
+        Self::from_json(value)?.into_ok()
+    }
+
+    /// JSON Patch (RFC 6902) which changes `self` to *other*
+    fn json_diff(&self, other: &Self) -> Result<JsonPatch, serde_json::Error>
+    where
+        Self: Serialize
+    {
+        json_diff(&self.to_json()?, &other.to_json()?).into_ok()
+    }
 }
 
 pub trait TryMut {
@@ -1,13 +1,10 @@
 use {
     crate::prelude::*,
     alloc::{
-        boxed::Box,
         format,
         string::{String, ToString},
-        sync::Arc,
-        vec::Vec
+        sync::Arc
     },
-    core::fmt::Display,
     serde::{Deserialize, Serialize}
 };
 
@@ -39,38 +36,18 @@ impl Default for BaseConfig {
 
 impl Iter<'_, (&'static str, String)> for BaseConfig {
     fn iter(&self) -> impl Iterator<Item = (&'static str, String)> {
+        use convert::{config_map, config_option, config_value};
+
         [
-            ("base.language", &self.language as &dyn Display),
+            ("base.language", config_value(&self.language)),
             (
                 "base.locales",
-                Box::leak(Box::new(
-                    self.locales
-                        .iter()
-                        .map(|(n, m)| format!("{n}={}", m.as_ref().unwrap_or(&"".into())))
-                        .collect::<Vec<_>>()
-                        .join("\n")
-                ))
-            ),
-            ("base.timezone", &self.timezone),
-            ("base.log.level", &self.log.level),
-            ("base.log.color", &self.log.color),
-            (
-                "base.log.filter",
-                Box::leak(Box::new(
-                    self.log
-                        .filter
-                        .as_ref()
-                        .map(|v| v.join(","))
-                        .unwrap_or_default()
-                ))
+                config_map(&self.locales, |v| config_option(v.as_ref(), config_value))
             ),
-            (
-                "base.log.file",
-                Box::leak(Box::new(self.log.file.as_deref().unwrap_or_default()))
-            )
+            ("base.timezone", config_value(&self.timezone))
         ]
         .into_iter()
-        .map(|(k, v)| (k, v.to_string()))
+        .chain(Iter::<'_, convert::ConfigItem>::iter(&self.log))
     }
 }
 
@@ -86,39 +63,40 @@ impl LoadArgs for BaseConfig {
         self.log.init_args(args);
     }
 
-    fn load_args(&mut self, args: &Args) {
-        #[rustfmt::skip]
-        self.extend(
+    fn load_args(&mut self, args: &Args) -> Result<(), ExtendErrors> {
+        let mut errors = ExtendErrors::default();
+        errors.merge(ExtendErrors::from_args(
+            self,
+            args,
             [
-                ("language", args.get("base-language")),
-                ("timezone", args.get("base-timezone")),
-                ("locales", args.get("base-locales")),
+                ("language", "base-language"),
+                ("timezone", "base-timezone"),
+                ("locales", "base-locales")
             ]
-            .iter().map(convert::tuple_result_option_str)
-        );
-
-        self.log.load_args(args);
+        ));
+        errors.merge(self.log.load_args(args).map_err(|e| e.key("log")));
+        errors.into_result()
     }
 }
 
 impl LoadEnv for BaseConfig {
-    fn load_env(&mut self) {
-        #[rustfmt::skip]
-        self.extend(
+    fn load_env(&mut self) -> Result<(), ExtendErrors> {
+        let mut errors = ExtendErrors::default();
+        errors.merge(ExtendErrors::from_env(
+            self,
             [
-                ("language", getenv("LANG")),
-                ("timezone", getenv("TZ")),
-                ("locales", getenv("LOCALES"))
+                ("language", "LANG"),
+                ("timezone", "TZ"),
+                ("locales", "LOCALES")
             ]
-            .iter()
-            .map(convert::tuple_option_str)
-        );
+        ));
 
         if self.language.len() > 2 {
             self.language = self.language[0..2].into();
         }
         self.language.make_ascii_lowercase();
-        self.log.load_env();
+        errors.merge(self.log.load_env().map_err(|e| e.key("log")));
+        errors.into_result()
     }
 }
 
@@ -127,3 +105,16 @@ impl LoadDirs for BaseConfig {
         self.log.load_dirs(dirs);
     }
 }
+
+impl BaseConfig {
+    /// Country of the current language by locales, i.e. `RU` for `ru = ru_RU`
+    ///
+    /// Language code isn't a country code (`uk` is Ukrainian, not GB), so
+    /// there is no country without region of locale.
+    pub fn country(&self) -> Option<&'static filters::PhoneCountry> {
+        self.locales
+            .get(&self.language)
+            .and_then(|locale| locale.as_deref())
+            .and_then(filters::PhoneCountry::from_locale)
+    }
+}
@@ -5,7 +5,8 @@ pub type ini_handler = ::core::option::Option<
         user: *mut ::core::ffi::c_void,
         section: *const ::core::ffi::c_char,
         name: *const ::core::ffi::c_char,
-        value: *const ::core::ffi::c_char
+        value: *const ::core::ffi::c_char,
+        lineno: ::core::ffi::c_int
     ) -> ::core::ffi::c_int
 >;
 pub type ini_reader = ::core::option::Option<
@@ -1,4 +1,14 @@
-use core::ops::Deref;
+use {
+    alloc::{
+        format,
+        string::{String, ToString},
+        vec::Vec
+    },
+    core::{fmt::Display, ops::Deref}
+};
+
+/// Option name and shown value of config, items of `Iter` of configs
+pub type ConfigItem = (&'static str, String);
 
 #[inline]
 pub fn tuple_option_str<'a, T>(
@@ -29,3 +39,49 @@ where
 {
     (item.0, item.1.as_ref().ok().unwrap_or(&None).as_deref())
 }
+
+/// Shown value of config option, used by `AppConfig` derive
+#[inline]
+pub fn config_value<T: Display + ?Sized>(value: &T) -> String {
+    value.to_string()
+}
+
+/// Value of option or empty string
+#[inline]
+pub fn config_option<T: ?Sized>(value: Option<&T>, f: impl Fn(&T) -> String) -> String {
+    value.map(f).unwrap_or_default()
+}
+
+/// Values separated by comma
+pub fn config_list<'a, T: 'a + ?Sized>(
+    values: impl IntoIterator<Item = &'a T>,
+    f: impl Fn(&T) -> String
+) -> String {
+    values.into_iter().map(f).collect::<Vec<_>>().join(",")
+}
+
+/// `name=value` lines, shown by `app config` as `option.name=value`
+pub fn config_map<'a, K: Display + 'a, V: 'a>(
+    values: impl IntoIterator<Item = (&'a K, &'a V)>,
+    f: impl Fn(&V) -> String
+) -> String {
+    values
+        .into_iter()
+        .map(|(name, value)| format!("{name}={}", f(value)))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Hides value of secret option
+#[inline]
+pub fn config_secret(value: String) -> String {
+    if value.is_empty() { value } else { "******".into() }
+}
+
+/// Prepends *dir* to relative *path*
+pub fn config_path(path: &mut String, dir: &str) {
+    if dir.is_empty() == false && path.starts_with('/') == false {
+        path.insert(0, '/');
+        path.insert_str(0, dir.trim_end_matches('/'));
+    }
+}
@@ -1,13 +1,19 @@
 use {
     crate::prelude::*,
     alloc::{
-        boxed::Box,
         ffi::CString,
         format,
         string::{String, ToString},
         vec::Vec
     },
-    core::{cell::RefCell, fmt::Display, mem::ManuallyDrop, ptr::null_mut, str::FromStr},
+    core::{
+        cell::RefCell,
+        ffi::c_int,
+        fmt::Display,
+        mem::{ManuallyDrop, take},
+        ptr::null_mut,
+        str::FromStr
+    },
     libc::{getcwd, readlink},
     serde::{Deserialize, Serialize}
 };
@@ -18,9 +24,37 @@ pub trait LoadDirs {
     fn load_dirs<'a>(&'a mut self, dirs: &'a Dirs);
 }
 
+/// Install mode which defines default directories
+#[derive(
+    Debug, Default, Clone, Copy, PartialEq, Eq, ExtendFromIter, Serialize, Deserialize,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum DirsMode {
+    /// FHS layout: `{prefix}/bin`, `/etc/{suffix}`, `{var}/lib/{suffix}`, ...
+    #[default]
+    System,
+    /// Per-user install by XDG base directories: `$XDG_CONFIG_HOME/{suffix}`,
+    /// `$XDG_DATA_HOME/{suffix}`, `$XDG_STATE_HOME/{suffix}`, ...
+    User,
+    /// All directories are inside the directory of executable
+    Portable
+}
+
+impl Display for DirsMode {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::System => write!(f, "system"),
+            Self::User => write!(f, "user"),
+            Self::Portable => write!(f, "portable")
+        }
+    }
+}
+
 #[derive(Debug, Clone, ExtendFromIter, Serialize, Deserialize)]
 pub struct Dirs {
     exe: String,
+    #[extend_skip]
+    mode: DirsMode,
     pub prefix: String,
     pub suffix: String,
     pub home: String,
@@ -43,45 +77,216 @@ pub struct Dirs {
 
 impl Default for Dirs {
     fn default() -> Self {
-        Self {
-            exe: Self::exe_path().unwrap(),
-            prefix: option_env!("PREFIX").unwrap_or(".").into(),
-            suffix: option_env!("SUFFIX").unwrap_or("").into(),
-            var: option_env!("VARDIR").unwrap_or("{prefix}/var").into(),
-            data: option_env!("DATADIR")
-                .unwrap_or("{prefix}/share/{suffix}")
-                .into(),
-            config: option_env!("CONFDIR").unwrap_or("/etc/{suffix}").into(),
-            home: "~".into(),
-            user_config: option_env!("USERCONFDIR")
-                .unwrap_or("{home}/.config/{suffix}")
-                .into(),
-            bin: option_env!("BINDIR").unwrap_or("{prefix}/bin").into(),
-            sbin: option_env!("SBINDIR").unwrap_or("{prefix}/sbin").into(),
-            lib: option_env!("LIBDIR").unwrap_or("{prefix}/lib").into(),
-            include: option_env!("INCDIR")
-                .unwrap_or("{prefix}/include/{suffix}")
-                .into(),
-            man: option_env!("MANDIR")
-                .unwrap_or("{prefix}/share/man/{suffix}")
-                .into(),
-            doc: option_env!("DOCDIR")
-                .unwrap_or("{prefix}/share/doc/{suffix}")
-                .into(),
-            state: option_env!("STATEDIR")
-                .unwrap_or("{var}/lib/{suffix}")
-                .into(),
-            cache: option_env!("CACHEDIR")
-                .unwrap_or("{var}/cache/{suffix}")
-                .into(),
-            run: option_env!("RUNDIR").unwrap_or("{var}/run/{suffix}").into(),
-            log: option_env!("LOGDIR").unwrap_or("{var}/log/{suffix}").into(),
-            tmp: option_env!("TEMPDIR").unwrap_or("/tmp/{suffix}").into()
+        let mut dirs = Self::with_mode(DirsMode::default());
+        // Invalid mode of build keeps the default one
+        if let Some(mode) = option_env!("DIRS_MODE")
+            && let Err(e) = dirs.load_mode(mode, "build DIRS_MODE")
+        {
+            log::error!("{e}");
         }
+
+        dirs
     }
 }
 
 impl Dirs {
+    /// Directories which are written by application at runtime
+    pub const WRITABLE: [&str; 7] =
+        ["var", "run", "log", "tmp", "cache", "state", "user_config"];
+
+    /// Default directories of install mode
+    pub fn with_mode(mode: DirsMode) -> Self {
+        let exe = Self::exe_path().unwrap();
+        let suffix: String = option_env!("SUFFIX").unwrap_or("").into();
+
+        match mode {
+            DirsMode::System => {
+                Self {
+                    exe,
+                    mode,
+                    prefix: option_env!("PREFIX").unwrap_or(".").into(),
+                    suffix,
+                    var: option_env!("VARDIR").unwrap_or("{prefix}/var").into(),
+                    data: option_env!("DATADIR")
+                        .unwrap_or("{prefix}/share/{suffix}")
+                        .into(),
+                    config: option_env!("CONFDIR").unwrap_or("/etc/{suffix}").into(),
+                    home: "~".into(),
+                    user_config: option_env!("USERCONFDIR")
+                        .unwrap_or("{home}/.config/{suffix}")
+                        .into(),
+                    bin: option_env!("BINDIR").unwrap_or("{prefix}/bin").into(),
+                    sbin: option_env!("SBINDIR").unwrap_or("{prefix}/sbin").into(),
+                    lib: option_env!("LIBDIR").unwrap_or("{prefix}/lib").into(),
+                    include: option_env!("INCDIR")
+                        .unwrap_or("{prefix}/include/{suffix}")
+                        .into(),
+                    man: option_env!("MANDIR")
+                        .unwrap_or("{prefix}/share/man/{suffix}")
+                        .into(),
+                    doc: option_env!("DOCDIR")
+                        .unwrap_or("{prefix}/share/doc/{suffix}")
+                        .into(),
+                    state: option_env!("STATEDIR")
+                        .unwrap_or("{var}/lib/{suffix}")
+                        .into(),
+                    cache: option_env!("CACHEDIR")
+                        .unwrap_or("{var}/cache/{suffix}")
+                        .into(),
+                    run: option_env!("RUNDIR").unwrap_or("{var}/run/{suffix}").into(),
+                    log: option_env!("LOGDIR").unwrap_or("{var}/log/{suffix}").into(),
+                    tmp: option_env!("TEMPDIR").unwrap_or("/tmp/{suffix}").into()
+                }
+            },
+            DirsMode::User => {
+                Self {
+                    exe,
+                    mode,
+                    prefix: "{home}/.local".into(),
+                    suffix,
+                    home: "~".into(),
+                    config: format!(
+                        "{}/{{suffix}}",
+                        Self::xdg("XDG_CONFIG_HOME", "{home}/.config")
+                    ),
+                    user_config: "{config}".into(),
+                    bin: "{prefix}/bin".into(),
+                    sbin: "{prefix}/bin".into(),
+                    lib: "{prefix}/lib".into(),
+                    include: "{prefix}/include/{suffix}".into(),
+                    data: format!(
+                        "{}/{{suffix}}",
+                        Self::xdg("XDG_DATA_HOME", "{home}/.local/share")
+                    ),
+                    man: "{prefix}/share/man/{suffix}".into(),
+                    doc: "{prefix}/share/doc/{suffix}".into(),
+                    var: Self::xdg("XDG_STATE_HOME", "{home}/.local/state"),
+                    state: "{var}/{suffix}".into(),
+                    log: "{state}/log".into(),
+                    cache: format!(
+                        "{}/{{suffix}}",
+                        Self::xdg("XDG_CACHE_HOME", "{home}/.cache")
+                    ),
+                    // the spec has no fallback, so a dir inside user cache is used
+                    run: match Self::xdg("XDG_RUNTIME_DIR", "") {
+                        dir if dir.is_empty() => "{cache}/run".into(),
+                        dir => format!("{dir}/{{suffix}}")
+                    },
+                    // private dir of user, shared `/tmp` is open to symlink attacks
+                    tmp: "{run}/tmp".into()
+                }
+            },
+            DirsMode::Portable => {
+                Self {
+                    prefix: Self::dirname(&exe).into(),
+                    exe,
+                    mode,
+                    suffix,
+                    home: "~".into(),
+                    config: "{prefix}/config".into(),
+                    user_config: "{config}".into(),
+                    bin: "{prefix}".into(),
+                    sbin: "{prefix}".into(),
+                    lib: "{prefix}/lib".into(),
+                    include: "{prefix}/include".into(),
+                    data: "{prefix}/share".into(),
+                    man: "{prefix}/share/man".into(),
+                    doc: "{prefix}/share/doc".into(),
+                    var: "{prefix}/var".into(),
+                    state: "{var}/lib".into(),
+                    cache: "{var}/cache".into(),
+                    run: "{var}/run".into(),
+                    log: "{var}/log".into(),
+                    tmp: "{var}/tmp".into()
+                }
+            },
+        }
+    }
+
+    #[inline]
+    pub fn mode(&self) -> DirsMode {
+        self.mode
+    }
+
+    /// Changes install mode
+    ///
+    /// Only directories which still have defaults of the current mode are
+    /// replaced, so values set by env or args before are kept.
+    pub fn set_mode(&mut self, mode: DirsMode) -> &mut Self {
+        let mut old = Self::with_mode(self.mode);
+        let mut new = Self::with_mode(mode);
+
+        for ((dir, old), new) in self
+            .list_mut()
+            .into_iter()
+            .zip(old.list_mut())
+            .zip(new.list_mut())
+        {
+            if dir == old {
+                *dir = take(new);
+            }
+        }
+        self.mode = mode;
+
+        self
+    }
+
+    /// Changes install mode parsed from *value* of *source*
+    fn load_mode(&mut self, value: &str, source: &str) -> Result<(), ExtendErrors> {
+        let mut mode = self.mode;
+        let result = ExtendErrors::extend_from(&mut mode, [("", Some(value), source)]);
+        self.set_mode(mode);
+        result.map_err(|e| e.key("mode"))
+    }
+
+    /// Named directories
+    pub fn list(&self) -> [(&'static str, &str); 17] {
+        [
+            ("prefix", &self.prefix),
+            ("home", &self.home),
+            ("config", &self.config),
+            ("user_config", &self.user_config),
+            ("bin", &self.bin),
+            ("sbin", &self.sbin),
+            ("lib", &self.lib),
+            ("include", &self.include),
+            ("data", &self.data),
+            ("man", &self.man),
+            ("doc", &self.doc),
+            ("var", &self.var),
+            ("state", &self.state),
+            ("cache", &self.cache),
+            ("run", &self.run),
+            ("log", &self.log),
+            ("tmp", &self.tmp)
+        ]
+    }
+
+    fn list_mut(&mut self) -> [&mut String; 18] {
+        [
+            &mut self.prefix, &mut self.suffix, &mut self.home, &mut self.config,
+            &mut self.user_config, &mut self.bin, &mut self.sbin, &mut self.lib,
+            &mut self.include, &mut self.data, &mut self.man, &mut self.doc,
+            &mut self.var, &mut self.state, &mut self.cache, &mut self.run,
+            &mut self.log, &mut self.tmp
+        ]
+    }
+
+    /// XDG base directory, relative paths are invalid by the spec and ignored
+    fn xdg(name: &str, default: &str) -> String {
+        getenv(name)
+            .filter(|dir| dir.starts_with('/'))
+            .map(|dir| dir.trim_end_matches('/').into())
+            .unwrap_or_else(|| default.into())
+    }
+
+    /// Checks access to *path* by `libc::access` *mode*, i.e. `libc::W_OK`
+    pub fn access(path: &str, mode: c_int) -> bool {
+        CString::new(path)
+            .map(|path| unsafe { libc::access(path.as_ptr(), mode) } == 0)
+            .unwrap_or(false)
+    }
+
     pub fn init(&mut self) -> &mut Self {
         let list: IndexMap<_, _> = IndexMap::from_iter([
             ("{prefix}", RefCell::new(&mut self.prefix)),
@@ -247,12 +452,49 @@ impl Dirs {
         }
         ok()
     }
+
+    /// Raw content of file
+    #[cfg(feature = "std")]
+    pub fn read_file(path: &str) -> Ok<Vec<u8>> {
+        match std::fs::read(path) {
+            Ok(content) => content.into_ok(),
+            Err(e) => Err(format!("Could not read file - {e}: {path}"))?
+        }
+    }
+
+    /// Raw content of file
+    #[cfg(not(feature = "std"))]
+    pub fn read_file(path: &str) -> Ok<Vec<u8>> {
+        let c_path = CString::new(path)?;
+        let mut content = Vec::new();
+
+        unsafe {
+            let file = libc::fopen(c_path.as_ptr(), c"rb".as_ptr());
+            if file.is_null() {
+                Err(format!("Could not read file: {path}"))?;
+            }
+
+            let mut buf = [0u8; 4096];
+            loop {
+                let len = libc::fread(buf.as_mut_ptr().cast(), 1, buf.len(), file);
+                content.extend_from_slice(&buf[..len]);
+                if len < buf.len() {
+                    break;
+                }
+            }
+
+            libc::fclose(file);
+        }
+
+        content.into_ok()
+    }
 }
 
 impl Iter<'_, (&'static str, String)> for Dirs {
     fn iter(&self) -> impl Iterator<Item = (&'static str, String)> {
         [
-            ("dirs.exe", Box::leak(Box::new(self.exe())) as &dyn Display),
+            ("dirs.exe", &self.exe as &dyn Display),
+            ("dirs.mode", &self.mode),
             ("dirs.bin", &self.bin),
             ("dirs.sbin", &self.sbin),
             ("dirs.lib", &self.lib),
@@ -280,6 +522,7 @@ impl Iter<'_, (&'static str, String)> for Dirs {
 impl LoadArgs for Dirs {
     fn init_args(&mut self, args: &mut Args) {
         args.add_options([
+            ("dirs-mode", None, None),
             ("dirs-home", None, None),
             ("dirs-config", None, None),
             ("dirs-user-config", None, None),
@@ -297,29 +540,40 @@ impl LoadArgs for Dirs {
         .unwrap();
     }
 
-    fn load_args(&mut self, args: &Args) {
-        self.extend(
+    fn load_args(&mut self, args: &Args) -> Result<(), ExtendErrors> {
+        let mut errors = ExtendErrors::default();
+        if let Some(mode) = args.get("dirs-mode").unwrap() {
+            errors.merge(self.load_mode(mode, "arg --dirs-mode"));
+        }
+
+        errors.merge(ExtendErrors::from_args(
+            self,
+            args,
             [
-                ("home", args.get("dirs-home")),
-                ("config", args.get("dirs-config")),
-                ("user_config", args.get("dirs-user-config")),
-                ("bin", args.get("dirs-bin")),
-                ("sbin", args.get("dirs-sbin")),
-                ("lib", args.get("dirs-lib")),
-                ("log", args.get("dirs-log")),
-                ("var", args.get("dirs-var")),
-                ("run", args.get("dirs-run")),
-                ("data", args.get("dirs-data")),
-                ("cache", args.get("dirs-cache")),
-                ("state", args.get("dirs-state")),
-                ("tmp", args.get("dirs-tmp"))
+                ("home", "dirs-home"),
+                ("config", "dirs-config"),
+                ("user_config", "dirs-user-config"),
+                ("bin", "dirs-bin"),
+                ("sbin", "dirs-sbin"),
+                ("lib", "dirs-lib"),
+                ("log", "dirs-log"),
+                ("var", "dirs-var"),
+                ("run", "dirs-run"),
+                ("data", "dirs-data"),
+                ("cache", "dirs-cache"),
+                ("state", "dirs-state"),
+                ("tmp", "dirs-tmp")
             ]
-            .iter()
-            .map(convert::tuple_result_option_str)
-        );
+        ));
+        errors.into_result()
     }
 }
 
 impl LoadEnv for Dirs {
-    fn load_env(&mut self) {}
+    fn load_env(&mut self) -> Result<(), ExtendErrors> {
+        match getenv("DIRS_MODE") {
+            Some(mode) => self.load_mode(&mode, "env DIRS_MODE"),
+            None => Ok(())
+        }
+    }
 }
@@ -0,0 +1,112 @@
+#[cfg(not(feature = "std"))]
+#[allow(unused_imports)]
+use libc_print::std_name::*;
+use {
+    crate::prelude::*,
+    alloc::{boxed::Box, format, string::String, vec::Vec},
+    core::{ffi::c_int, mem::take}
+};
+
+/// Layered .env files
+///
+/// Files are loaded from `DOTENV_DIR` (current dir by default) in order:
+/// `.env`, `.env.local`, `.env.{env}`, `.env.{env}.local`, where `{env}` is
+/// the current environment preceded by its ancestors. Later files take
+/// precedence over earlier ones. Variables of the real process environment
+/// are never overwritten.
+#[derive(Debug, Default, Clone, FromStatic)]
+pub struct DotEnv {
+    files: Vec<Box<str>>,
+    vars: IndexSet<Box<str>>
+}
+
+impl DotEnv {
+    pub const DEFAULT_DIR: &str = ".";
+
+    /// Loads .env files from *dir*
+    ///
+    /// If *overwrite* is set, variables of previously loaded files are
+    /// replaced. Returns paths of loaded files.
+    pub fn load(dir: &str, overwrite: bool) -> Ok<&'static [Box<str>]> {
+        let this = unsafe { Self::from_static_mut() };
+        // Variables which came from .env files, not from the process environment
+        let mut vars = if overwrite {
+            take(&mut this.vars)
+        } else {
+            IndexSet::default()
+        };
+
+        this.files.clear();
+
+        // Env layer depends on APP_ENV, so it is resolved after base layer
+        for layer in [Self::base_layer as fn() -> Vec<String>, Self::env_layer] {
+            for name in layer() {
+                let path = format!("{}/{name}", dir.trim_end_matches('/'));
+                let ini = match Ini::from_file(&path) {
+                    Ok(ini) => ini,
+                    Err(e) => {
+                        match e.downcast_ref::<IniError>() {
+                            // skips not existing files
+                            Some(IniError::FileNotFound(..)) => continue,
+                            _ => Err(e)?
+                        }
+                    }
+                };
+
+                for (name, value) in ini.iter() {
+                    if let Some(value) = value
+                        && (vars.contains(name) || getenv(name).is_none())
+                    {
+                        setenv(name, value);
+                        vars.insert(name.clone());
+                    }
+                }
+
+                this.files.push(path.into());
+            }
+
+            // .env and .env.local may change APP_ENV and APP_ENVS
+            unsafe { Env::reset() };
+        }
+
+        this.vars.extend(vars);
+
+        Self::files().into_ok()
+    }
+
+    /// Paths of .env files loaded last time
+    #[inline]
+    pub fn files() -> &'static [Box<str>] {
+        &Self::from_static().files
+    }
+
+    fn base_layer() -> Vec<String> {
+        Vec::from([".env".into(), ".env.local".into()])
+    }
+
+    fn env_layer() -> Vec<String> {
+        Env::chain()
+            .iter()
+            .rev()
+            .flat_map(|env| [format!(".env.{env}"), format!(".env.{env}.local")])
+            .collect()
+    }
+}
+
+/// Loads .env file variables
+///
+/// Loads `.env`, `.env.local`, `.env.{APP_ENV}` and `.env.{APP_ENV}.local`
+/// from `DOTENV_DIR` or current dir.
+///
+/// Returns zero if initialization is successfull.
+/// Otherwise returns int less zero.
+#[unsafe(no_mangle)]
+pub extern "C" fn dotenv(overwrite: bool) -> c_int {
+    let dir = getenv("DOTENV_DIR").unwrap_or(DotEnv::DEFAULT_DIR.into());
+
+    match DotEnv::load(&dir, overwrite) {
+        Ok(files) if files.is_empty() => -1,
+        Ok(..) => 0,
+        Err(e) => panic!("dotenv error: {e}")
+    }
+}
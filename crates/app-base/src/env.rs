@@ -1,9 +1,9 @@
-use {crate::prelude::*, alloc::string::String};
-#[cfg(not(feature = "std"))]
 use {
-    alloc::boxed::Box, alloc::ffi::CString, alloc::string::ToString, core::ffi::CStr,
-    core::str::FromStr
+    crate::prelude::*,
+    alloc::{boxed::Box, string::String, vec::Vec}
 };
+#[cfg(not(feature = "std"))]
+use {alloc::ffi::CString, alloc::string::ToString, core::ffi::CStr, core::str::FromStr};
 
 pub fn getenv(name: &str) -> Option<String> {
     #[cfg(feature = "std")]
@@ -42,9 +42,15 @@ pub fn setenv(name: &str, value: &str) {
 }
 
 pub trait LoadEnv {
-    fn load_env(&mut self);
+    fn load_env(&mut self) -> Result<(), ExtendErrors>;
 }
 
+/// Current application environment
+///
+/// Known environments are set by `APP_ENVS` as comma separated list of
+/// `name` or `name:parent`, e.g. `dev,prod,test:dev,staging:prod`.
+/// Environment inherits all properties of its parent, so `staging` is also
+/// `prod`.
 #[derive(Debug, Clone, FromStatic)]
 pub struct Env {
     is_test: bool,
@@ -52,7 +58,9 @@ pub struct Env {
     is_dev: bool,
     is_debug: bool,
     is_release: bool,
-    env: Box<str>
+    env: Box<str>,
+    envs: Box<[(Box<str>, Option<Box<str>>)]>,
+    chain: Box<[Box<str>]>
 }
 
 impl Default for Env {
@@ -71,18 +79,68 @@ impl Default for Env {
             .map(|v| ["1", "true", "on"].contains(&v.to_lowercase().as_str()))
             .unwrap_or(false);
 
+        let envs = Self::parse_envs(
+            &getenv("APP_ENVS")
+                .filter(|v| v.is_empty() == false)
+                .unwrap_or(option_env!("APP_ENVS").unwrap_or(Self::DEFAULT_ENVS).into())
+        );
+        let chain = Self::resolve_chain(&env, &envs);
+        let inherits = |name: &str| chain.iter().any(|e| e.as_ref() == name);
+
         Self {
-            is_test: &env == "test",
-            is_prod: &env == "prod",
-            is_dev: &env != "prod",
+            is_test: inherits("test"),
+            is_prod: inherits("prod"),
+            is_dev: inherits("prod") == false,
             is_debug,
             is_release: cfg!(debug_assertions) == false,
-            env: env.into_boxed_str()
+            env: env.into_boxed_str(),
+            envs,
+            chain
         }
     }
 }
 
 impl Env {
+    pub const DEFAULT_ENVS: &str = "dev,prod,test";
+
+    fn parse_envs(value: &str) -> Box<[(Box<str>, Option<Box<str>>)]> {
+        value
+            .split_terminator(',')
+            .map(str::trim)
+            .filter(|v| v.is_empty() == false)
+            .map(|v| {
+                match v.split_once(':') {
+                    Some((name, parent)) => {
+                        (name.trim().into(), Some(parent.trim().into()))
+                    },
+                    None => (v.into(), None)
+                }
+            })
+            .collect()
+    }
+
+    /// Returns the environment followed by its ancestors
+    fn resolve_chain(
+        env: &str,
+        envs: &[(Box<str>, Option<Box<str>>)]
+    ) -> Box<[Box<str>]> {
+        let mut chain: Vec<Box<str>> = Vec::from([env.into()]);
+
+        while let Some(parent) = envs
+            .iter()
+            .find(|(name, ..)| name == chain.last().unwrap())
+            .and_then(|(.., parent)| parent.clone())
+        {
+            // Breaks cyclic inheritance
+            if chain.contains(&parent) {
+                break;
+            }
+            chain.push(parent);
+        }
+
+        chain.into_boxed_slice()
+    }
+
     pub unsafe fn reset() {
         unsafe { Self::from_static_mut().clone_from(&Self::default()) };
     }
@@ -92,6 +150,32 @@ impl Env {
         &Self::from_static().env
     }
 
+    /// Names of all known environments
+    pub fn envs() -> impl Iterator<Item = &'static str> {
+        Self::from_static()
+            .envs
+            .iter()
+            .map(|(name, ..)| name.as_ref())
+    }
+
+    /// Current environment followed by its ancestors, the nearest first
+    #[inline]
+    pub fn chain() -> &'static [Box<str>] {
+        &Self::from_static().chain
+    }
+
+    /// Checks if current environment is exactly *name*
+    #[inline]
+    pub fn is(name: &str) -> bool {
+        Self::env() == name
+    }
+
+    /// Checks if current environment is *name* or inherits it
+    #[inline]
+    pub fn inherits(name: &str) -> bool {
+        Self::chain().iter().any(|e| e.as_ref() == name)
+    }
+
     #[inline]
     pub fn is_test() -> bool {
         Self::from_static().is_test
@@ -0,0 +1,246 @@
+use {
+    crate::prelude::{Args, getenv},
+    alloc::{
+        format,
+        string::{String, ToString},
+        vec::Vec
+    },
+    core::{
+        error::Error,
+        fmt::{self, Display},
+        ops::Deref
+    }
+};
+
+/// Invalid value of config option
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ExtendError {
+    /// Dotted path of option, i.e. `actix.port`
+    pub key: String,
+    pub value: String,
+    /// Expected type of value
+    pub expected: String,
+    /// Where the value came from: config file, environment variable or
+    /// command line argument
+    pub source: String
+}
+
+impl Display for ExtendError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Invalid value '{}' of {}, expected {}",
+            self.value, self.key, self.expected
+        )?;
+        if self.source.is_empty() == false {
+            write!(f, " ({})", self.source)?;
+        }
+        Ok(())
+    }
+}
+
+/// All errors of config extension
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ExtendErrors(pub Vec<ExtendError>);
+
+impl Error for ExtendErrors {}
+
+impl Display for ExtendErrors {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, error) in self.0.iter().enumerate() {
+            if i > 0 {
+                f.write_str("\n")?;
+            }
+            write!(f, "{error}")?;
+        }
+        Ok(())
+    }
+}
+
+impl Deref for ExtendErrors {
+    type Target = [ExtendError];
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl ExtendErrors {
+    /// Error of value of the current key
+    pub fn expected(expected: impl Display) -> Self {
+        Self(alloc::vec![ExtendError {
+            expected: expected.to_string(),
+            ..Default::default()
+        }])
+    }
+
+    /// Prepends *key* to keys of errors, empty *key* keeps them
+    pub fn key(mut self, key: &str) -> Self {
+        if key.is_empty() {
+            return self;
+        }
+        for error in &mut self.0 {
+            error.key = match error.key.is_empty() {
+                true => key.into(),
+                false => [key, ".", &error.key].concat()
+            };
+        }
+        self
+    }
+
+    /// Sets value of errors which have no value yet
+    pub fn value(mut self, value: &str) -> Self {
+        self.0
+            .iter_mut()
+            .filter(|e| e.value.is_empty())
+            .for_each(|e| e.value = value.into());
+        self
+    }
+
+    /// Sets source of errors which have no source yet
+    pub fn source(mut self, source: impl Display) -> Self {
+        let source = source.to_string();
+        self.0
+            .iter_mut()
+            .filter(|e| e.source.is_empty())
+            .for_each(|e| e.source.clone_from(&source));
+        self
+    }
+
+    /// Appends errors of *result*
+    pub fn merge(&mut self, result: Result<(), Self>) -> &mut Self {
+        if let Err(e) = result {
+            self.0.extend(e.0);
+        }
+        self
+    }
+
+    #[inline]
+    pub fn into_result(self) -> Result<(), Self> {
+        if self.0.is_empty() { Ok(()) } else { Err(self) }
+    }
+
+    /// Logs errors as warnings, for `Extend` which can't return them
+    pub fn warn(&self) {
+        for error in &self.0 {
+            log::warn!("{error}");
+        }
+    }
+
+    /// Splits *iter* into valid values and all errors
+    pub fn partition<T>(
+        iter: impl IntoIterator<Item = Result<T, Self>>
+    ) -> (Vec<T>, Self) {
+        let mut errors = Self::default();
+        let values = iter
+            .into_iter()
+            .filter_map(|item| item.map_err(|e| errors.0.extend(e.0)).ok())
+            .collect();
+        (values, errors)
+    }
+
+    /// Collects values or all errors of *iter*
+    pub fn collect<T>(
+        iter: impl IntoIterator<Item = Result<T, Self>>
+    ) -> Result<Vec<T>, Self> {
+        let (values, errors) = Self::partition(iter);
+        errors.into_result().map(|_| values)
+    }
+
+    /// Splits `a = 1, b = x,y` into pairs, a segment without `=` continues
+    /// value of the previous pair, so values may contain commas
+    pub fn pairs<'a>(value: &'a str) -> Vec<Result<(&'a str, &'a str), Self>> {
+        let mut pairs = Vec::new();
+        // key, start of segment, start and end of value of the last pair
+        let mut last: Option<(&str, usize, usize, usize)> = None;
+        let pair = |(key, start, from, to): (&'a str, usize, usize, usize)| {
+            match key.is_empty() {
+                true => Err(Self::expected("key = value").value(value[start..to].trim())),
+                false => Ok((key, value[from..to].trim()))
+            }
+        };
+
+        let mut start = 0;
+        for segment in value.split(',') {
+            let end = start + segment.len();
+            let blank = segment.trim().is_empty();
+            match (segment.split_once('='), &mut last) {
+                (Some((key, _)), _) => {
+                    pairs.extend(last.take().map(pair));
+                    last = Some((key.trim(), start, start + key.len() + 1, end));
+                },
+                (None, Some((.., to))) if blank == false => *to = end,
+                (None, None) if blank == false => {
+                    pairs.push(Err(Self::expected("key = value").value(segment.trim())));
+                },
+                _ => {}
+            }
+            start = end + 1;
+        }
+        pairs.extend(last.map(pair));
+        pairs
+    }
+
+    /// Extends *target* by items of `(name, value, source)` one by one, so
+    /// each error gets source of its value, i.e. name of environment variable
+    pub fn extend_from<'a, T, V, S>(
+        target: &mut T,
+        items: impl IntoIterator<Item = (&'a str, Option<V>, S)>
+    ) -> Result<(), Self>
+    where
+        T: for<'b> TryExtend<(&'b str, Option<&'b str>)> + ?Sized,
+        V: Deref<Target = str>,
+        S: Display
+    {
+        let mut errors = Self::default();
+        for (name, value, source) in items {
+            if let Err(e) = target.try_extend([(name, value.as_deref())]) {
+                errors.0.extend(e.source(source).0);
+            }
+        }
+        errors.into_result()
+    }
+
+    /// Extends *target* by environment variables of `(name, variable)`
+    pub fn from_env<'a, T>(
+        target: &mut T,
+        items: impl IntoIterator<Item = (&'a str, &'a str)>
+    ) -> Result<(), Self>
+    where
+        T: for<'b> TryExtend<(&'b str, Option<&'b str>)> + ?Sized
+    {
+        Self::extend_from(
+            target,
+            items
+                .into_iter()
+                .map(|(name, env)| (name, getenv(env), format!("env {env}")))
+        )
+    }
+
+    /// Extends *target* by command line arguments of `(name, option)`
+    pub fn from_args<'a, T>(
+        target: &mut T,
+        args: &Args,
+        items: impl IntoIterator<Item = (&'a str, &'a str)>
+    ) -> Result<(), Self>
+    where
+        T: for<'b> TryExtend<(&'b str, Option<&'b str>)> + ?Sized
+    {
+        Self::extend_from(
+            target,
+            items.into_iter().map(|(name, arg)| {
+                (name, args.get(arg).ok().flatten(), format!("arg --{arg}"))
+            })
+        )
+    }
+}
+
+/// Fallible `Extend`, which is generated by `ExtendFromIter` too
+pub trait TryExtend<A> {
+    /// Extends by all valid items and returns errors of invalid ones
+    fn try_extend<I: IntoIterator<Item = A>>(
+        &mut self,
+        iter: I
+    ) -> Result<(), ExtendErrors>;
+}
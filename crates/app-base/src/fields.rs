@@ -0,0 +1,78 @@
+use {
+    crate::extend::{ExtendErrors, TryExtend},
+    alloc::{format, vec::Vec},
+    core::any::Any
+};
+
+/// Field of struct described by `#[derive(StructFields)]`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FieldInfo {
+    pub name: &'static str,
+    pub ty: &'static str,
+    /// Name of field in serde, i.e. by `#[serde(rename = "...")]` or
+    /// `#[serde(rename_all = "...")]` of struct
+    pub serde_name: &'static str,
+    /// Doc comment of field
+    pub doc: &'static str,
+    /// Attributes of field without doc comments, i.e. `serde(skip)`
+    pub attrs: &'static [&'static str]
+}
+
+impl FieldInfo {
+    /// Whether field has attribute with *path*, i.e. `serde` or `config`
+    pub fn has_attr(&self, path: &str) -> bool {
+        self.attrs.iter().any(|a| {
+            a.strip_prefix(path)
+                .is_some_and(|a| a.is_empty() || a.starts_with([' ', '(', '=']))
+        })
+    }
+}
+
+/// Runtime reflection of struct fields, implemented by
+/// `#[derive(StructFields)]`
+///
+/// Fields are found by name or serde name, so generic admin forms and
+/// exports can use names of serialized data.
+pub trait StructFields {
+    const FIELDS: &'static [FieldInfo];
+
+    /// Field by name or serde name
+    fn field(name: &str) -> Option<&'static FieldInfo> {
+        Self::FIELDS
+            .iter()
+            .find(|f| f.name == name || f.serde_name == name)
+    }
+
+    /// Value of field, it's `None` for fields with non-static lifetimes
+    fn get_field(&self, name: &str) -> Option<&dyn Any>;
+
+    /// Sets field parsed from *value* like config values of `ExtendFromIter`,
+    /// nested keys as `limits.max` are set by name of field
+    fn set_field_str<'s>(
+        &mut self,
+        name: &'s str,
+        value: &'s str
+    ) -> Result<(), ExtendErrors>
+    where
+        Self: TryExtend<(&'s str, Option<&'s str>)>
+    {
+        let field = match name.split_once('.') {
+            Some((head, _)) => Self::FIELDS.iter().find(|f| f.name == head),
+            None => Self::field(name)
+        };
+        let Some(field) = field else {
+            let names = Self::FIELDS.iter().map(|f| f.name).collect::<Vec<_>>();
+            Err(
+                ExtendErrors::expected(format!("one of fields: {}", names.join(", ")))
+                    .value(value)
+                    .key(name)
+            )?
+        };
+
+        let key = match name.contains('.') {
+            true => name,
+            false => field.name
+        };
+        self.try_extend([(key, Some(value))])
+    }
+}
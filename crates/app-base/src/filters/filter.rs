@@ -0,0 +1,83 @@
+use alloc::{boxed::Box, string::String, vec::Vec};
+
+/// Struct with field filters, see `#[derive(Filter)]`
+///
+/// Filters run by `filter()` or during deserialization by `serde::Filtered`
+/// and `serde::filtered`.
+pub trait Filter {
+    /// Applies filters to fields
+    fn filter(&mut self);
+}
+
+impl<T: Filter> Filter for Option<T> {
+    fn filter(&mut self) {
+        if let Some(value) = self {
+            value.filter();
+        }
+    }
+}
+
+impl<T: Filter> Filter for Vec<T> {
+    fn filter(&mut self) {
+        self.iter_mut().for_each(Filter::filter);
+    }
+}
+
+/// Field value which can be filtered by string function
+pub trait FilterValue {
+    fn filter_value(&mut self, f: &dyn Fn(&str) -> String);
+
+    fn is_empty_value(&self) -> bool;
+}
+
+impl FilterValue for String {
+    fn filter_value(&mut self, f: &dyn Fn(&str) -> String) {
+        *self = f(self);
+    }
+
+    #[inline]
+    fn is_empty_value(&self) -> bool {
+        self.is_empty()
+    }
+}
+
+impl FilterValue for Box<str> {
+    fn filter_value(&mut self, f: &dyn Fn(&str) -> String) {
+        *self = f(self).into();
+    }
+
+    #[inline]
+    fn is_empty_value(&self) -> bool {
+        self.is_empty()
+    }
+}
+
+/// Empty value after filters is `None`
+impl<T: FilterValue> FilterValue for Option<T> {
+    fn filter_value(&mut self, f: &dyn Fn(&str) -> String) {
+        if let Some(value) = self {
+            value.filter_value(f);
+            if value.is_empty_value() {
+                *self = None;
+            }
+        }
+    }
+
+    #[inline]
+    fn is_empty_value(&self) -> bool {
+        self.as_ref().is_none_or(T::is_empty_value)
+    }
+}
+
+/// Empty values after filters are removed
+impl<T: FilterValue> FilterValue for Vec<T> {
+    fn filter_value(&mut self, f: &dyn Fn(&str) -> String) {
+        self.iter_mut().for_each(|value| value.filter_value(f));
+        self.retain(|value| value.is_empty_value() == false);
+    }
+
+    #[inline]
+    fn is_empty_value(&self) -> bool {
+        self.is_empty()
+    }
+}
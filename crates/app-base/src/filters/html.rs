@@ -0,0 +1,543 @@
+use {
+    crate::prelude::*,
+    alloc::{borrow::Cow, string::String, vec::Vec}
+};
+
+/// Whitelist-based HTML sanitizer, i.e. for rich text of users
+///
+/// Keeps only allowed tags and attributes, checks schemes of URLs, escapes
+/// text and closes unclosed tags. Content of disallowed tags is kept as text
+/// except of tags like `script` which are removed with content.
+///
+/// The default whitelist is available globally by
+/// `HtmlSanitizer::from_static()` and used by `sanitize_html()`.
+#[derive(Debug, Clone, FromStatic)]
+pub struct HtmlSanitizer {
+    /// Allowed tags with their allowed attributes
+    pub tags: IndexMap<Cow<'static, str>, IndexSet<Cow<'static, str>>>,
+    /// Attributes allowed for all allowed tags
+    pub attributes: IndexSet<Cow<'static, str>>,
+    /// Allowed schemes of URL attributes, relative URLs are always allowed
+    pub url_schemes: IndexSet<Cow<'static, str>>,
+    /// Tags removed together with content
+    pub clean_content_tags: IndexSet<Cow<'static, str>>,
+    /// Adds `rel="nofollow"` to links
+    pub nofollow: bool
+}
+
+impl Default for HtmlSanitizer {
+    fn default() -> Self {
+        let mut this = Self::new();
+
+        for (tag, attributes) in Self::DEFAULT_TAGS {
+            this.with_tag_attributes(tag, attributes.iter().copied());
+        }
+        this.with_url_schemes(Self::DEFAULT_URL_SCHEMES)
+            .with_nofollow(true);
+
+        this
+    }
+}
+
+impl HtmlSanitizer {
+    pub const DEFAULT_TAGS: [(&str, &[&str]); 16] = [
+        ("a", &["href", "title"]),
+        ("b", &[]),
+        ("strong", &[]),
+        ("i", &[]),
+        ("em", &[]),
+        ("u", &[]),
+        ("s", &[]),
+        ("p", &[]),
+        ("br", &[]),
+        ("ul", &[]),
+        ("ol", &[]),
+        ("li", &[]),
+        ("blockquote", &["cite"]),
+        ("code", &[]),
+        ("pre", &[]),
+        ("span", &[])
+    ];
+    pub const DEFAULT_URL_SCHEMES: [&str; 3] = ["http", "https", "mailto"];
+    pub const CLEAN_CONTENT_TAGS: [&str; 11] = [
+        "script", "style", "iframe", "object", "embed", "noscript", "template",
+        "textarea", "title", "svg", "math"
+    ];
+    pub const VOID_TAGS: [&str; 13] = [
+        "area", "base", "br", "col", "embed", "hr", "img", "input", "link", "meta",
+        "source", "track", "wbr"
+    ];
+    pub const URL_ATTRIBUTES: [&str; 8] = [
+        "href", "src", "cite", "action", "formaction", "poster", "background",
+        "xlink:href"
+    ];
+
+    /// Sanitizer without allowed tags, it keeps only text
+    pub fn new() -> Self {
+        Self {
+            tags: Default::default(),
+            attributes: Default::default(),
+            url_schemes: Default::default(),
+            clean_content_tags: Self::CLEAN_CONTENT_TAGS
+                .into_iter()
+                .map(Into::into)
+                .collect(),
+            nofollow: false
+        }
+    }
+
+    pub fn with_tags(
+        &mut self,
+        tags: impl IntoIterator<Item = impl Into<Cow<'static, str>>>
+    ) -> &mut Self {
+        for tag in tags {
+            self.tags.entry(tag.into()).or_default();
+        }
+        self
+    }
+
+    pub fn with_tag_attributes(
+        &mut self,
+        tag: impl Into<Cow<'static, str>>,
+        attributes: impl IntoIterator<Item = impl Into<Cow<'static, str>>>
+    ) -> &mut Self {
+        self.tags
+            .entry(tag.into())
+            .or_default()
+            .extend(attributes.into_iter().map(Into::into));
+        self
+    }
+
+    pub fn without_tags<'a>(
+        &mut self,
+        tags: impl IntoIterator<Item = &'a str>
+    ) -> &mut Self {
+        for tag in tags {
+            self.tags.shift_remove(tag);
+        }
+        self
+    }
+
+    pub fn with_attributes(
+        &mut self,
+        attributes: impl IntoIterator<Item = impl Into<Cow<'static, str>>>
+    ) -> &mut Self {
+        self.attributes
+            .extend(attributes.into_iter().map(Into::into));
+        self
+    }
+
+    pub fn with_url_schemes(
+        &mut self,
+        schemes: impl IntoIterator<Item = impl Into<Cow<'static, str>>>
+    ) -> &mut Self {
+        self.url_schemes.extend(schemes.into_iter().map(Into::into));
+        self
+    }
+
+    pub fn with_nofollow(&mut self, nofollow: bool) -> &mut Self {
+        self.nofollow = nofollow;
+        self
+    }
+
+    /// Relative URL or URL with allowed scheme
+    ///
+    /// Whitespaces and control chars are ignored like browsers do:
+    /// `java\tscript:`.
+    pub fn is_allowed_url(&self, url: &str) -> bool {
+        let url: String = url
+            .chars()
+            .filter(|c| c.is_ascii_whitespace() == false && c.is_control() == false)
+            .collect();
+
+        match url.find([':', '/', '?', '#']) {
+            Some(pos) if url[pos..].starts_with(':') => {
+                self.url_schemes
+                    .contains(url[..pos].to_ascii_lowercase().as_str())
+            },
+            _ => true
+        }
+    }
+
+    pub fn sanitize(&self, html: &str) -> String {
+        let lower = html.to_ascii_lowercase();
+        let mut out = String::with_capacity(html.len());
+        let mut open: Vec<&str> = Vec::new();
+        let mut pos = 0;
+        let mut text_start = 0;
+
+        while let Some(found) = html[pos..].find('<') {
+            let start = pos + found;
+            let Some(tag) = HtmlTag::parse(html, &lower, start) else {
+                // not a tag, i.e. `a < b`
+                pos = start + 1;
+                continue;
+            };
+
+            html_escape_text(&mut out, &html[text_start..start]);
+            pos = tag.end;
+
+            if tag.closing {
+                if self.tags.contains_key(tag.name)
+                    && let Some(idx) = open.iter().rposition(|t| *t == tag.name)
+                {
+                    for name in open.drain(idx..).rev() {
+                        out.push_str("</");
+                        out.push_str(name);
+                        out.push('>');
+                    }
+                }
+            } else if self.clean_content_tags.contains(tag.name) {
+                // browsers ignore `/>` of such tags
+                pos = HtmlTag::find_close(&lower, tag.name, pos);
+            } else if let Some(attributes) = self.tags.get(tag.name) {
+                self.write_tag(&mut out, &tag, attributes);
+
+                if Self::VOID_TAGS.contains(&tag.name) == false {
+                    if tag.self_closing {
+                        out.push_str("</");
+                        out.push_str(tag.name);
+                        out.push('>');
+                    } else {
+                        open.push(tag.name);
+                    }
+                }
+            }
+
+            text_start = pos;
+        }
+
+        html_escape_text(&mut out, &html[text_start..]);
+
+        for name in open.into_iter().rev() {
+            out.push_str("</");
+            out.push_str(name);
+            out.push('>');
+        }
+
+        out
+    }
+
+    fn write_tag(
+        &self,
+        out: &mut String,
+        tag: &HtmlTag,
+        allowed: &IndexSet<Cow<'static, str>>
+    ) {
+        let nofollow = self.nofollow && tag.name == "a";
+        let mut written: Vec<&str> = Vec::new();
+
+        out.push('<');
+        out.push_str(tag.name);
+
+        for (name, value) in &tag.attributes {
+            if (allowed.contains(*name) || self.attributes.contains(*name)) == false
+                || name.starts_with("on")
+                || (nofollow && *name == "rel")
+                || written.contains(name)
+            {
+                continue;
+            }
+
+            let value = value.map(html_unescape);
+            if Self::URL_ATTRIBUTES.contains(name)
+                && self.is_allowed_url(value.as_deref().unwrap_or("")) == false
+            {
+                continue;
+            }
+
+            out.push(' ');
+            out.push_str(name);
+            if let Some(value) = value {
+                out.push_str("=\"");
+                out.push_str(&html_escape(&value));
+                out.push('"');
+            }
+            written.push(*name);
+        }
+
+        if nofollow {
+            out.push_str(" rel=\"nofollow\"");
+        }
+
+        out.push('>');
+    }
+}
+
+/// Parsed tag, comment or declaration
+struct HtmlTag<'a> {
+    /// Lowercase name, empty for comments and declarations
+    name: &'a str,
+    /// Lowercase names with raw values
+    attributes: Vec<(&'a str, Option<&'a str>)>,
+    closing: bool,
+    self_closing: bool,
+    /// Position after the tag
+    end: usize
+}
+
+impl<'a> HtmlTag<'a> {
+    /// Parses tag at `<`, returns `None` if it is text
+    fn parse(html: &'a str, lower: &'a str, start: usize) -> Option<Self> {
+        let bytes = html.as_bytes();
+        let mut pos = start + 1;
+        let mut tag = Self {
+            name: "",
+            attributes: Vec::new(),
+            closing: false,
+            self_closing: false,
+            end: html.len()
+        };
+
+        // comment or declaration, they are always removed
+        if html[pos..].starts_with("!--") {
+            if let Some(end) = html[pos + 3..].find("-->") {
+                tag.end = pos + 3 + end + 3;
+            }
+            return Some(tag);
+        }
+        if matches!(bytes.get(pos), Some(b'!' | b'?')) {
+            tag.end = pos + html[pos..].find('>')? + 1;
+            return Some(tag);
+        }
+
+        if bytes.get(pos) == Some(&b'/') {
+            tag.closing = true;
+            pos += 1;
+        }
+
+        if bytes.get(pos).is_some_and(u8::is_ascii_alphabetic) == false {
+            return None;
+        }
+        let name_start = pos;
+        while bytes
+            .get(pos)
+            .is_some_and(|b| b.is_ascii_alphanumeric() || matches!(b, b'-' | b':'))
+        {
+            pos += 1;
+        }
+        tag.name = &lower[name_start..pos];
+
+        loop {
+            while bytes
+                .get(pos)
+                .is_some_and(|b| b.is_ascii_whitespace() || *b == b'/')
+            {
+                tag.self_closing = bytes[pos] == b'/';
+                pos += 1;
+            }
+
+            match bytes.get(pos)? {
+                b'>' => {
+                    tag.end = pos + 1;
+                    return Some(tag);
+                },
+                _ => tag.self_closing = false
+            }
+
+            let name_start = pos;
+            while bytes.get(pos).is_some_and(|b| {
+                b.is_ascii_whitespace() == false
+                    && matches!(b, b'=' | b'>' | b'/') == false
+            }) {
+                pos += 1;
+            }
+            let name = &lower[name_start..pos];
+
+            while bytes.get(pos).is_some_and(u8::is_ascii_whitespace) {
+                pos += 1;
+            }
+            if bytes.get(pos) != Some(&b'=') {
+                tag.attributes.push((name, None));
+                continue;
+            }
+            pos += 1;
+            while bytes.get(pos).is_some_and(u8::is_ascii_whitespace) {
+                pos += 1;
+            }
+
+            let value = match bytes.get(pos)? {
+                quote @ (b'"' | b'\'') => {
+                    let value_start = pos + 1;
+                    pos = value_start + html[value_start..].find(*quote as char)?;
+                    let value = &html[value_start..pos];
+                    pos += 1;
+                    value
+                },
+                _ => {
+                    let value_start = pos;
+                    while bytes
+                        .get(pos)
+                        .is_some_and(|b| b.is_ascii_whitespace() == false && *b != b'>')
+                    {
+                        pos += 1;
+                    }
+                    &html[value_start..pos]
+                }
+            };
+            tag.attributes.push((name, Some(value)));
+        }
+    }
+
+    /// Position after closing tag *name* or end of html
+    fn find_close(lower: &str, name: &str, mut pos: usize) -> usize {
+        while let Some(found) = lower[pos..].find("</") {
+            pos += found + 2;
+            if lower[pos..].starts_with(name)
+                && lower[pos + name.len()..]
+                    .starts_with(|c: char| c.is_ascii_alphanumeric() == false)
+            {
+                return lower[pos..]
+                    .find('>')
+                    .map(|p| pos + p + 1)
+                    .unwrap_or(lower.len());
+            }
+        }
+
+        lower.len()
+    }
+}
+
+/// Sanitizes HTML by the global sanitizer
+#[inline]
+pub fn sanitize_html(html: &str) -> String {
+    HtmlSanitizer::from_static().sanitize(html)
+}
+
+/// Removes tags, comments and content of tags like `script`, keeps text as is
+pub fn strip_html(html: &str) -> String {
+    let lower = html.to_ascii_lowercase();
+    let mut out = String::with_capacity(html.len());
+    let mut pos = 0;
+    let mut text_start = 0;
+
+    while let Some(found) = html[pos..].find('<') {
+        let start = pos + found;
+        let Some(tag) = HtmlTag::parse(html, &lower, start) else {
+            pos = start + 1;
+            continue;
+        };
+
+        out.push_str(&html[text_start..start]);
+        pos = tag.end;
+
+        if tag.closing == false && HtmlSanitizer::CLEAN_CONTENT_TAGS.contains(&tag.name) {
+            pos = HtmlTag::find_close(&lower, tag.name, pos);
+        }
+
+        text_start = pos;
+    }
+
+    out.push_str(&html[text_start..]);
+
+    out
+}
+
+/// Escapes `& < > " '`
+pub fn html_escape(value: &str) -> String {
+    let mut out = String::with_capacity(value.len());
+
+    for c in value.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&#39;"),
+            c => out.push(c)
+        }
+    }
+
+    out
+}
+
+/// Decodes numeric and basic named entities, unknown entities are kept
+pub fn html_unescape(value: &str) -> String {
+    let mut out = String::with_capacity(value.len());
+    let mut rest = value;
+
+    while let Some(pos) = rest.find('&') {
+        out.push_str(&rest[..pos]);
+        rest = &rest[pos..];
+
+        match html_entity(rest) {
+            Some((len, Some(c))) => {
+                out.push(c);
+                rest = &rest[len..];
+            },
+            _ => {
+                out.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+    out.push_str(rest);
+
+    out
+}
+
+/// Escapes text keeping valid entities
+fn html_escape_text(out: &mut String, text: &str) {
+    let mut rest = text;
+
+    while let Some(pos) = rest.find(['&', '<', '>']) {
+        out.push_str(&rest[..pos]);
+        rest = &rest[pos..];
+
+        match rest.as_bytes()[0] {
+            b'<' => out.push_str("&lt;"),
+            b'>' => out.push_str("&gt;"),
+            _ => {
+                if let Some((len, _)) = html_entity(rest) {
+                    out.push_str(&rest[..len]);
+                    rest = &rest[len..];
+                    continue;
+                }
+                out.push_str("&amp;");
+            }
+        }
+        rest = &rest[1..];
+    }
+    out.push_str(rest);
+}
+
+/// Length of entity at the start of *s* and its char if it is known
+fn html_entity(s: &str) -> Option<(usize, Option<char>)> {
+    let end = s.bytes().take(40).position(|b| b == b';')?;
+    let name = &s[1..end];
+
+    let c = if let Some(code) = name.strip_prefix('#') {
+        let code = match code.strip_prefix(['x', 'X']) {
+            Some(hex)
+                if (1..=6).contains(&hex.len())
+                    && hex.chars().all(|c| c.is_ascii_hexdigit()) =>
+            {
+                u32::from_str_radix(hex, 16).ok()?
+            },
+            None if (1..=7).contains(&code.len())
+                && code.chars().all(|c| c.is_ascii_digit()) =>
+            {
+                code.parse().ok()?
+            },
+            _ => return None
+        };
+        Some(char::from_u32(code).filter(|c| *c != '\0')?)
+    } else if name.len() <= 32
+        && name.starts_with(|c: char| c.is_ascii_alphabetic())
+        && name.chars().all(|c| c.is_ascii_alphanumeric())
+    {
+        match name {
+            "amp" => Some('&'),
+            "lt" => Some('<'),
+            "gt" => Some('>'),
+            "quot" => Some('"'),
+            "apos" => Some('\''),
+            "nbsp" => Some('\u{a0}'),
+            _ => None
+        }
+    } else {
+        return None;
+    };
+
+    Some((end + 1, c))
+}
@@ -1,4 +1,6 @@
+mod filter;
+mod html;
 mod phone;
 mod string;
 
-pub use {phone::*, string::*};
+pub use {filter::*, html::*, phone::*, string::*};
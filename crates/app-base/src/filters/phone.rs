@@ -1,4 +1,12 @@
-use alloc::string::String;
+use {
+    alloc::string::String,
+    core::{
+        error::Error,
+        fmt::Display,
+        str::FromStr,
+        sync::atomic::{AtomicUsize, Ordering}
+    }
+};
 
 pub fn filter_phone(phone: &str) -> String {
     phone
@@ -13,3 +21,402 @@ pub fn filter_phone(phone: &str) -> String {
         })
         .collect()
 }
+
+/// E.164 number if it is valid, otherwise `filter_phone()`, so invalid number
+/// is kept for validation
+pub fn filter_phone_e164(phone: &str) -> String {
+    normalize_phone(phone).unwrap_or_else(|_| filter_phone(phone))
+}
+
+/// Normalizes phone number to E.164, i.e. `8 (916) 123-45-67` to `+79161234567`
+///
+/// Numbers of countries missing in `PHONE_COUNTRIES` are only checked to
+/// have an assigned country code of `PHONE_CALLING_CODES` and 8 to 15 digits.
+pub fn normalize_phone(phone: &str) -> Result<String, PhoneError> {
+    match Phone::parse(phone) {
+        Ok(phone) => Ok(phone.e164()),
+        Err(PhoneError::UnknownCountry) => {
+            let digits = filter_phone(phone.trim());
+            let digits = match digits.strip_prefix('+') {
+                Some(digits) => digits,
+                None => digits.strip_prefix("00").unwrap_or(&digits)
+            };
+            if is_calling_code(digits) == false {
+                return Err(PhoneError::UnknownCountry);
+            }
+            match digits.len() {
+                ..8 => Err(PhoneError::TooShort),
+                8..=Phone::MAX_LEN => Ok(alloc::format!("+{digits}")),
+                _ => Err(PhoneError::TooLong)
+            }
+        },
+        Err(e) => Err(e)
+    }
+}
+
+/// Ranges of country calling codes assigned by ITU-T E.164, including
+/// international networks like `+882`
+#[rustfmt::skip]
+pub static PHONE_CALLING_CODES: [(u16, u16); 48] = [
+    (1, 1), (7, 7), (20, 20), (27, 27), (30, 34), (36, 36), (39, 41), (43, 49),
+    (51, 58), (60, 66), (81, 82), (84, 84), (86, 86), (90, 95), (98, 98),
+    (211, 213), (216, 216), (218, 218), (220, 258), (260, 269), (290, 291),
+    (297, 299), (350, 359), (370, 383), (385, 387), (389, 389), (420, 421),
+    (423, 423), (500, 509), (590, 599), (670, 670), (672, 683), (685, 692),
+    (800, 800), (808, 808), (850, 850), (852, 853), (855, 856), (870, 870),
+    (878, 878), (880, 883), (886, 886), (888, 888), (960, 968), (970, 977),
+    (979, 979), (992, 996), (998, 998)
+];
+
+/// Whether *digits* of international number start with an assigned country
+/// code, codes are prefix-free and never start with `0`
+fn is_calling_code(digits: &str) -> bool {
+    if digits.starts_with('0') {
+        return false;
+    }
+
+    (1..=3.min(digits.len())).any(|len| {
+        digits[..len].parse::<u16>().is_ok_and(|code| {
+            PHONE_CALLING_CODES
+                .iter()
+                .any(|(from, to)| (*from..=*to).contains(&code))
+        })
+    })
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PhoneError {
+    Empty,
+    InvalidChars,
+    NoCountry,
+    UnknownCountry,
+    TooShort,
+    TooLong
+}
+
+impl Error for PhoneError {}
+
+impl Display for PhoneError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str(match self {
+            Self::Empty => "Phone number is empty",
+            Self::InvalidChars => "Phone number contains invalid characters",
+            Self::NoCountry => "Phone number without country code",
+            Self::UnknownCountry => "Unknown country code of phone number",
+            Self::TooShort => "Phone number is too short",
+            Self::TooLong => "Phone number is too long"
+        })
+    }
+}
+
+/// Numbering plan of country
+#[derive(Debug, PartialEq, Eq)]
+pub struct PhoneCountry {
+    /// ISO 3166-1 alpha-2 code
+    pub code: &'static str,
+    pub calling_code: u16,
+    /// National prefix, i.e. `8` in Russia or `0` in Germany
+    pub trunk_prefix: &'static str,
+    /// Min length of national number
+    pub min_len: usize,
+    /// Max length of national number
+    pub max_len: usize,
+    /// Display format of national number, `#` is a digit
+    pub format: &'static str
+}
+
+/// Numbering plans of supported countries
+#[rustfmt::skip]
+pub static PHONE_COUNTRIES: [PhoneCountry; 47] = [
+    PhoneCountry::new("RU", 7, "8", 10, 10, "### ###-##-##"),
+    PhoneCountry::new("KZ", 7, "8", 10, 10, "### ###-##-##"),
+    PhoneCountry::new("US", 1, "1", 10, 10, "(###) ###-####"),
+    PhoneCountry::new("CA", 1, "1", 10, 10, "(###) ###-####"),
+    PhoneCountry::new("GB", 44, "0", 9, 10, "#### ######"),
+    PhoneCountry::new("DE", 49, "0", 6, 13, ""),
+    PhoneCountry::new("FR", 33, "0", 9, 9, "# ## ## ## ##"),
+    PhoneCountry::new("ES", 34, "", 9, 9, "### ## ## ##"),
+    PhoneCountry::new("IT", 39, "", 6, 11, ""),
+    PhoneCountry::new("NL", 31, "0", 9, 9, "## ### ####"),
+    PhoneCountry::new("BE", 32, "0", 8, 9, ""),
+    PhoneCountry::new("CH", 41, "0", 9, 9, "## ### ## ##"),
+    PhoneCountry::new("AT", 43, "0", 4, 13, ""),
+    PhoneCountry::new("PL", 48, "", 9, 9, "### ### ###"),
+    PhoneCountry::new("CZ", 420, "", 9, 9, "### ### ###"),
+    PhoneCountry::new("SE", 46, "0", 7, 13, ""),
+    PhoneCountry::new("NO", 47, "", 8, 8, "### ## ###"),
+    PhoneCountry::new("FI", 358, "0", 5, 12, ""),
+    PhoneCountry::new("DK", 45, "", 8, 8, "## ## ## ##"),
+    PhoneCountry::new("PT", 351, "", 9, 9, "### ### ###"),
+    PhoneCountry::new("GR", 30, "", 10, 10, ""),
+    PhoneCountry::new("IE", 353, "0", 7, 9, ""),
+    PhoneCountry::new("UA", 380, "0", 9, 9, "## ###-##-##"),
+    PhoneCountry::new("BY", 375, "8", 9, 9, "## ###-##-##"),
+    PhoneCountry::new("UZ", 998, "", 9, 9, "## ###-##-##"),
+    PhoneCountry::new("KG", 996, "0", 9, 9, "### ###-###"),
+    PhoneCountry::new("AM", 374, "0", 8, 8, "## ###-###"),
+    PhoneCountry::new("GE", 995, "0", 9, 9, "### ##-##-##"),
+    PhoneCountry::new("AZ", 994, "0", 9, 9, "## ###-##-##"),
+    PhoneCountry::new("TR", 90, "0", 10, 10, "### ### ## ##"),
+    PhoneCountry::new("IL", 972, "0", 8, 9, ""),
+    PhoneCountry::new("AE", 971, "0", 8, 9, ""),
+    PhoneCountry::new("IN", 91, "0", 10, 10, "##### #####"),
+    PhoneCountry::new("CN", 86, "0", 10, 11, ""),
+    PhoneCountry::new("JP", 81, "0", 9, 10, ""),
+    PhoneCountry::new("KR", 82, "0", 8, 10, ""),
+    PhoneCountry::new("AU", 61, "0", 9, 9, "### ### ###"),
+    PhoneCountry::new("NZ", 64, "0", 8, 10, ""),
+    PhoneCountry::new("BR", 55, "0", 10, 11, ""),
+    PhoneCountry::new("MX", 52, "", 10, 10, ""),
+    PhoneCountry::new("AR", 54, "0", 10, 10, ""),
+    PhoneCountry::new("ZA", 27, "0", 9, 9, "## ### ####"),
+    PhoneCountry::new("EG", 20, "0", 9, 10, ""),
+    PhoneCountry::new("TH", 66, "0", 8, 9, ""),
+    PhoneCountry::new("VN", 84, "0", 9, 10, ""),
+    PhoneCountry::new("ID", 62, "0", 8, 12, ""),
+    PhoneCountry::new("RS", 381, "0", 8, 9, "")
+];
+
+impl PhoneCountry {
+    const fn new(
+        code: &'static str,
+        calling_code: u16,
+        trunk_prefix: &'static str,
+        min_len: usize,
+        max_len: usize,
+        format: &'static str
+    ) -> Self {
+        Self { code, calling_code, trunk_prefix, min_len, max_len, format }
+    }
+
+    /// Country by ISO code, i.e. `RU` or `ru`
+    pub fn get(code: &str) -> Option<&'static Self> {
+        PHONE_COUNTRIES
+            .iter()
+            .find(|c| c.code.eq_ignore_ascii_case(code))
+    }
+
+    /// Country by region of locale, i.e. `ru_RU`, `en-US` or `de_DE.UTF-8`,
+    /// locale without region has no country
+    pub fn from_locale(locale: &str) -> Option<&'static Self> {
+        let locale = locale.split('.').next().unwrap_or_default();
+
+        locale
+            .split_once(['_', '-'])
+            .and_then(|(_, region)| Self::get(region))
+    }
+
+    #[inline]
+    fn is_valid_len(&self, len: usize) -> bool {
+        (self.min_len..=self.max_len).contains(&len)
+    }
+}
+
+/// Phone number in E.164 format
+///
+/// Numbers without international prefix (`+` or `00`) are parsed by rules of
+/// the default country which app sets from `BaseConfig` language and locales.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Phone {
+    country: &'static PhoneCountry,
+    /// National significant number
+    number: String
+}
+
+/// Index of default country in `PHONE_COUNTRIES` plus one, zero is none
+static DEFAULT_COUNTRY: AtomicUsize = AtomicUsize::new(0);
+
+impl Phone {
+    /// Max length of E.164 number without `+`
+    pub const MAX_LEN: usize = 15;
+
+    pub fn default_country() -> Option<&'static PhoneCountry> {
+        DEFAULT_COUNTRY
+            .load(Ordering::Relaxed)
+            .checked_sub(1)
+            .map(|idx| &PHONE_COUNTRIES[idx])
+    }
+
+    pub fn set_default_country(country: Option<&'static PhoneCountry>) {
+        let idx = country
+            .and_then(|country| PHONE_COUNTRIES.iter().position(|c| c == country))
+            .map(|idx| idx + 1)
+            .unwrap_or_default();
+
+        DEFAULT_COUNTRY.store(idx, Ordering::Relaxed);
+    }
+
+    /// Parses by rules of the default country
+    #[inline]
+    pub fn parse(phone: &str) -> Result<Self, PhoneError> {
+        Self::parse_with(phone, Self::default_country())
+    }
+
+    /// Parses by rules of *country*
+    pub fn parse_with(
+        phone: &str,
+        country: Option<&'static PhoneCountry>
+    ) -> Result<Self, PhoneError> {
+        let phone = phone.trim();
+        if phone.is_empty() {
+            return Err(PhoneError::Empty);
+        }
+
+        let (plus, rest) = match phone.strip_prefix('+') {
+            Some(rest) => (true, rest),
+            None => (false, phone)
+        };
+        if rest
+            .chars()
+            .all(|c| c.is_ascii_digit() || " -.()/".contains(c))
+            == false
+        {
+            return Err(PhoneError::InvalidChars);
+        }
+
+        let digits: String = rest.chars().filter(char::is_ascii_digit).collect();
+        if digits.is_empty() {
+            return Err(PhoneError::Empty);
+        }
+
+        if plus {
+            return Self::parse_international(&digits, country);
+        }
+        if let Some(digits) = digits.strip_prefix("00") {
+            return Self::parse_international(digits, country);
+        }
+
+        let Some(country) = country else {
+            return Err(PhoneError::NoCountry);
+        };
+
+        if country.is_valid_len(digits.len()) {
+            return Self::new(country, &digits);
+        }
+        if country.trunk_prefix.is_empty() == false
+            && let Some(number) = digits.strip_prefix(country.trunk_prefix)
+            && country.is_valid_len(number.len())
+        {
+            return Self::new(country, number);
+        }
+        // international number without `+`, i.e. `79161234567`
+        if digits.len() > country.max_len
+            && let Ok(phone) = Self::parse_international(&digits, Some(country))
+        {
+            return Ok(phone);
+        }
+
+        Self::new(country, &digits)
+    }
+
+    fn parse_international(
+        digits: &str,
+        country: Option<&'static PhoneCountry>
+    ) -> Result<Self, PhoneError> {
+        if digits.len() > Self::MAX_LEN {
+            return Err(PhoneError::TooLong);
+        }
+        // `+01...` isn't `+1`
+        if digits.starts_with('0') {
+            return Err(PhoneError::UnknownCountry);
+        }
+
+        for len in 1..=3.min(digits.len()) {
+            let Ok(code) = digits[..len].parse::<u16>() else {
+                continue;
+            };
+            // the default country is preferred for shared codes, i.e. RU and KZ
+            let found = country
+                .filter(|c| c.calling_code == code)
+                .or_else(|| PHONE_COUNTRIES.iter().find(|c| c.calling_code == code));
+
+            if let Some(found) = found {
+                return Self::new(found, &digits[len..]);
+            }
+        }
+
+        Err(PhoneError::UnknownCountry)
+    }
+
+    fn new(country: &'static PhoneCountry, number: &str) -> Result<Self, PhoneError> {
+        if number.len() < country.min_len {
+            return Err(PhoneError::TooShort);
+        }
+        if number.len() > country.max_len {
+            return Err(PhoneError::TooLong);
+        }
+
+        Ok(Self { country, number: number.into() })
+    }
+
+    #[inline]
+    pub fn country(&self) -> &'static PhoneCountry {
+        self.country
+    }
+
+    /// National significant number
+    #[inline]
+    pub fn number(&self) -> &str {
+        &self.number
+    }
+
+    /// `+79161234567`
+    pub fn e164(&self) -> String {
+        alloc::format!("+{}{}", self.country.calling_code, self.number)
+    }
+
+    /// `+7 916 123-45-67`
+    pub fn international(&self) -> String {
+        alloc::format!("+{} {}", self.country.calling_code, self.format_number())
+    }
+
+    /// `8 916 123-45-67`
+    pub fn national(&self) -> String {
+        match self.country.trunk_prefix {
+            "" => self.format_number(),
+            prefix => alloc::format!("{prefix} {}", self.format_number())
+        }
+    }
+
+    /// Number by country format or by groups of three digits
+    fn format_number(&self) -> String {
+        let format = self.country.format;
+        let mut out = String::with_capacity(self.number.len() * 2);
+
+        if format.chars().filter(|c| *c == '#').count() == self.number.len() {
+            let mut digits = self.number.chars();
+            for c in format.chars() {
+                out.push(if c == '#' { digits.next().unwrap() } else { c });
+            }
+            return out;
+        }
+
+        let len = self.number.len();
+        let mut pos = 0;
+        while pos < len {
+            let size = if len - pos == 4 { 4 } else { 3.min(len - pos) };
+            if pos > 0 {
+                out.push(' ');
+            }
+            out.push_str(&self.number[pos..pos + size]);
+            pos += size;
+        }
+
+        out
+    }
+}
+
+impl Display for Phone {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "+{}{}", self.country.calling_code, self.number)
+    }
+}
+
+impl FromStr for Phone {
+    type Err = PhoneError;
+
+    #[inline]
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s)
+    }
+}
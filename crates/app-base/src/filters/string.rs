@@ -9,27 +9,29 @@ use {
     heck::ToSnakeCase
 };
 
-pub fn strip_html(source: &str) -> String {
-    let mut result = String::new();
-    let mut inside = false;
-
-    for c in source.chars() {
-        if c == '<' {
-            inside = true;
-            continue;
-        }
-
-        if c == '>' {
-            inside = false;
-            continue;
-        }
-
-        if !inside {
-            result.push(c);
-        }
-    }
+#[inline]
+pub fn trim(str: &str) -> String {
+    str.trim().into()
+}
+
+#[inline]
+pub fn lowercase(str: &str) -> String {
+    str.to_lowercase()
+}
 
-    result
+#[inline]
+pub fn uppercase(str: &str) -> String {
+    str.to_uppercase()
+}
+
+/// Replaces sequences of whitespaces with one space and trims
+pub fn collapse_ws(str: &str) -> String {
+    str.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+#[inline]
+pub fn filter_digits(str: &str) -> String {
+    str.chars().filter(char::is_ascii_digit).collect()
 }
 
 #[inline]
@@ -0,0 +1,875 @@
+#[cfg(feature = "std")]
+use std::cell::RefCell;
+
+use {
+    crate::prelude::*,
+    alloc::{
+        boxed::Box,
+        format,
+        string::{String, ToString},
+        vec::{self, Vec}
+    },
+    core::{
+        error::Error,
+        fmt::{self, Display},
+        future::Future,
+        mem::take,
+        pin::Pin,
+        ptr::null_mut,
+        sync::atomic::{AtomicPtr, Ordering},
+        task::{Context, Poll}
+    }
+};
+
+#[cfg(feature = "std")]
+std::thread_local! {
+    static LANGUAGE: RefCell<Option<String>> = const { RefCell::new(None) };
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum I18nError {
+    InvalidCatalog(String),
+    InvalidPluralForms(String)
+}
+
+impl Error for I18nError {}
+
+impl Display for I18nError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidCatalog(e) => write!(f, "Invalid catalog: {e}"),
+            Self::InvalidPluralForms(e) => write!(f, "Invalid plural forms: {e}")
+        }
+    }
+}
+
+/// Translations of UI messages
+///
+/// Catalogs are gettext `.po` files loaded at boot from
+/// `{dirs.data}/locales` or `{dirs.prefix}/locales` (source tree and portable
+/// layout) by names `{locale}.po` or `{language}.po` for each language of
+/// `base.locales`.
+///
+/// Message is searched by the fallback chain of language, i.e. `pt_BR`,
+/// `pt`, then the default language, and the message itself is used if no
+/// catalog has it.
+///
+/// Registry is built by `load()` and builder methods, then `install()`
+/// publishes it for all threads. Installed registry is immutable and never
+/// freed, so references to its messages stay valid after the next boot.
+#[derive(Debug, Default)]
+pub struct I18n {
+    language: String,
+    catalogs: IndexMap<String, Catalog>,
+    fallbacks: IndexMap<String, Vec<String>>
+}
+
+static CURRENT: AtomicPtr<I18n> = AtomicPtr::new(null_mut());
+
+impl I18n {
+    pub const DIR_NAME: &str = "locales";
+    pub const EXTENSION: &str = "po";
+
+    /// Loads and installs catalogs of configured languages
+    #[inline]
+    pub fn init(config: &BaseConfig, dirs: &Dirs) -> Void {
+        Self::load(config, dirs)?.install();
+        ok()
+    }
+
+    /// Loads catalogs of configured languages, missing files are skipped
+    pub fn load(config: &BaseConfig, dirs: &Dirs) -> Ok<Self> {
+        let mut this = Self { language: config.language.clone(), ..Default::default() };
+        let search_dirs = Self::dirs(dirs);
+
+        let languages = config
+            .locales
+            .iter()
+            .map(|(language, locale)| (language.as_str(), locale.as_deref()))
+            .chain([(config.language.as_str(), None)]);
+
+        for (language, locale) in languages {
+            if this.catalogs.contains_key(language) {
+                continue;
+            }
+
+            let path = search_dirs
+                .iter()
+                .flat_map(|dir| {
+                    [locale, Some(language)]
+                        .into_iter()
+                        .flatten()
+                        .map(move |name| format!("{dir}/{name}.{}", Self::EXTENSION))
+                })
+                .find(|path| Dirs::access(path, libc::R_OK));
+            let Some(path) = path else {
+                continue;
+            };
+
+            let content = Dirs::read_file(&path)?;
+            let catalog = Catalog::parse(language, &String::from_utf8_lossy(&content))
+                .map_err(|e| format!("{e}: {path}"))?;
+            Env::is_debug()
+                .then(|| log::debug!("Loaded {} messages from {path}", catalog.len()));
+            this.catalogs.insert(language.into(), catalog);
+        }
+
+        this.into_ok()
+    }
+
+    /// Publishes registry for all threads instead of the current one
+    pub fn install(self) -> &'static Self {
+        let this = Box::leak(Box::new(self));
+        // the previous registry is leaked, its messages may be still used
+        CURRENT.swap(this, Ordering::AcqRel);
+        this
+    }
+
+    /// Installed registry, empty one before `install()`
+    fn current() -> &'static Self {
+        let current = CURRENT.load(Ordering::Acquire);
+        if current.is_null() == false {
+            return unsafe { &*current };
+        }
+
+        let empty = Box::into_raw(Box::<Self>::default());
+        match CURRENT.compare_exchange(
+            null_mut(),
+            empty,
+            Ordering::AcqRel,
+            Ordering::Acquire
+        ) {
+            Ok(..) => unsafe { &*empty },
+            Err(current) => {
+                drop(unsafe { Box::from_raw(empty) });
+                unsafe { &*current }
+            }
+        }
+    }
+
+    /// Directories of catalogs by priority
+    pub fn dirs(dirs: &Dirs) -> Vec<String> {
+        let mut list = Vec::with_capacity(2);
+        for dir in [&dirs.data, &dirs.prefix] {
+            let dir = format!("{dir}/{}", Self::DIR_NAME);
+            if list.contains(&dir) == false {
+                list.push(dir);
+            }
+        }
+        list
+    }
+
+    /// Default language
+    #[inline]
+    pub fn default_language() -> &'static str {
+        &Self::current().language
+    }
+
+    pub fn set_default_language(&mut self, language: &str) -> &mut Self {
+        self.language = language.into();
+        self
+    }
+
+    /// Adds or replaces catalog of its language
+    pub fn add_catalog(&mut self, catalog: Catalog) -> &mut Self {
+        self.catalogs.insert(catalog.language.clone(), catalog);
+        self
+    }
+
+    #[inline]
+    pub fn catalog(language: &str) -> Option<&'static Catalog> {
+        Self::current().catalogs.get(language)
+    }
+
+    /// Sets explicit fallback chain of *language*, i.e. `uk` to `ru`
+    pub fn set_fallbacks(&mut self, language: &str, fallbacks: &[&str]) -> &mut Self {
+        self.fallbacks.insert(
+            language.into(),
+            fallbacks.iter().map(|v| v.to_string()).collect()
+        );
+        self
+    }
+
+    /// Languages to search messages: *language*, its explicit fallbacks,
+    /// primary language without region and the default language
+    pub fn fallbacks(language: &str) -> Vec<&str> {
+        let this = Self::current();
+        let primary = language.split(['_', '-']).next().unwrap_or(language);
+
+        let mut chain = vec![language];
+        if let Some(fallbacks) = this.fallbacks.get(language) {
+            chain.extend(fallbacks.iter().map(String::as_str));
+        }
+        chain.push(primary);
+        chain.push(&this.language);
+
+        let mut res = Vec::with_capacity(chain.len());
+        for item in chain {
+            if item.is_empty() == false && res.contains(&item) == false {
+                res.push(item);
+            }
+        }
+
+        res
+    }
+
+    /// Translates message *id*, returns *id* if there is no translation
+    pub fn translate<'a>(language: &str, context: Option<&str>, id: &'a str) -> &'a str {
+        Self::fallbacks(language)
+            .into_iter()
+            .find_map(|language| Self::catalog(language)?.get(context, id))
+            .unwrap_or(id)
+    }
+
+    /// Translates message by plural form of *n*, returns *id* or *plural*
+    /// by English rule if there is no translation
+    pub fn translate_plural<'a>(
+        language: &str,
+        context: Option<&str>,
+        id: &'a str,
+        plural: &'a str,
+        n: u64
+    ) -> &'a str {
+        Self::fallbacks(language)
+            .into_iter()
+            .find_map(|language| Self::catalog(language)?.get_plural(context, id, n))
+            .unwrap_or(if n == 1 { id } else { plural })
+    }
+
+    /// Replaces `{name}` placeholders by *args*, unknown ones are kept
+    pub fn format(message: &str, args: &[(&str, &dyn Display)]) -> String {
+        if args.is_empty() || message.contains('{') == false {
+            return message.into();
+        }
+
+        let mut res = String::with_capacity(message.len());
+        let mut rest = message;
+
+        while let Some(start) = rest.find('{') {
+            res.push_str(&rest[..start]);
+            rest = &rest[start..];
+
+            let arg = rest.find('}').and_then(|end| {
+                let name = &rest[1..end];
+                args.iter().find(|(n, _)| *n == name).map(|(_, v)| (end, v))
+            });
+
+            match arg {
+                Some((end, value)) => {
+                    res.push_str(&value.to_string());
+                    rest = &rest[end + 1..];
+                },
+                None => {
+                    res.push('{');
+                    rest = &rest[1..];
+                }
+            }
+        }
+        res.push_str(rest);
+
+        res
+    }
+
+    /// Language of the current scope or the default language
+    pub fn language() -> String {
+        #[cfg(feature = "std")]
+        if let Some(language) = LANGUAGE.with_borrow(Clone::clone) {
+            return language;
+        }
+
+        Self::default_language().into()
+    }
+
+    /// Runs closure with *language* of the current scope
+    pub fn sync_scope<R>(language: impl Into<String>, f: impl FnOnce() -> R) -> R {
+        let mut language = Some(language.into());
+        let _scope = I18nScopeGuard::enter(&mut language);
+        f()
+    }
+
+    /// Wraps future, *language* is current while it is polled
+    pub fn scope<F: Future>(language: impl Into<String>, future: F) -> I18nScope<F> {
+        I18nScope { language: Some(language.into()), future }
+    }
+
+    /// Best language of *available* by `Accept-Language` header
+    ///
+    /// Languages are tried by descending q-values, exact tags are preferred
+    /// to primary ones, i.e. `en-US` matches `en_US`, then `en`.
+    pub fn negotiate<'a>(
+        header: &str,
+        available: impl IntoIterator<Item = &'a str>
+    ) -> Option<&'a str> {
+        let available: Vec<&str> = available.into_iter().collect();
+        let normalize = |tag: &str| tag.trim().replace('_', "-").to_ascii_lowercase();
+        let normalized: Vec<String> = available.iter().map(|v| normalize(v)).collect();
+
+        // tags with invalid q-values are skipped
+        let mut tags: Vec<(String, f32)> = header
+            .split(',')
+            .filter_map(|item| {
+                let (tag, params) = item.split_once(';').unwrap_or((item, ""));
+                let q = match params.trim().strip_prefix("q=") {
+                    Some(q) => {
+                        q.trim().parse().ok().filter(|q| (0.0..=1.0).contains(q))?
+                    },
+                    None => 1.0
+                };
+                Some((normalize(tag), q)).filter(|(tag, _)| tag.is_empty() == false)
+            })
+            .collect();
+        // stable sort keeps order of equal q-values
+        tags.sort_by(|a, b| b.1.total_cmp(&a.1));
+        // q=0 means "not acceptable"
+        let excluded =
+            |lang: &String| tags.iter().any(|(tag, q)| *q == 0.0 && tag == lang);
+
+        for (tag, _) in tags.iter().filter(|(_, q)| *q > 0.0) {
+            if tag == "*" {
+                return normalized
+                    .iter()
+                    .position(|v| excluded(v) == false)
+                    .map(|idx| available[idx]);
+            }
+
+            let primary = tag.split('-').next().unwrap_or_default();
+            let found = normalized.iter().position(|v| v == tag).or_else(|| {
+                normalized
+                    .iter()
+                    .position(|v| v == primary || v.split('-').next() == Some(primary))
+            });
+            if let Some(idx) = found {
+                return Some(available[idx]);
+            }
+        }
+
+        None
+    }
+
+    fn swap(language: &mut Option<String>) {
+        #[cfg(feature = "std")]
+        LANGUAGE.with_borrow_mut(|current| core::mem::swap(current, language));
+
+        #[cfg(not(feature = "std"))]
+        let _ = language;
+    }
+}
+
+/// Swaps language back on drop, so a panic inside of scope doesn't leave its
+/// language on the thread
+struct I18nScopeGuard<'a>(&'a mut Option<String>);
+
+impl<'a> I18nScopeGuard<'a> {
+    fn enter(language: &'a mut Option<String>) -> Self {
+        I18n::swap(language);
+        Self(language)
+    }
+}
+
+impl Drop for I18nScopeGuard<'_> {
+    fn drop(&mut self) {
+        I18n::swap(self.0);
+    }
+}
+
+/// Future with language of `I18n`
+pub struct I18nScope<F> {
+    language: Option<String>,
+    future: F
+}
+
+impl<F: Future> Future for I18nScope<F> {
+    type Output = F::Output;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        // SAFETY: future is never moved out of pinned self
+        let this = unsafe { self.get_unchecked_mut() };
+        let future = unsafe { Pin::new_unchecked(&mut this.future) };
+
+        let _scope = I18nScopeGuard::enter(&mut this.language);
+        future.poll(cx)
+    }
+}
+
+/// Messages of one language parsed from gettext `.po`
+///
+/// Fuzzy and obsolete entries are skipped as gettext does.
+#[derive(Debug, Clone, Default)]
+pub struct Catalog {
+    language: String,
+    plural: PluralRule,
+    /// Translations by context and message id
+    messages: IndexMap<String, Vec<String>>
+}
+
+impl Catalog {
+    pub fn new(language: &str) -> Self {
+        Self {
+            language: language.into(),
+            plural: PluralRule::for_language(language),
+            messages: Default::default()
+        }
+    }
+
+    pub fn parse(language: &str, content: &str) -> Result<Self, I18nError> {
+        let mut catalog = Self::new(language);
+        let mut entry = PoEntry::default();
+
+        for (num, line) in content.lines().enumerate() {
+            let line = line.trim();
+            let error =
+                |e: &str| I18nError::InvalidCatalog(format!("{e} at line {}", num + 1));
+
+            if line.is_empty() {
+                continue;
+            }
+            if let Some(comment) = line.strip_prefix('#') {
+                // comments start the next entry
+                if entry.field.is_some() {
+                    catalog.add_entry(take(&mut entry))?;
+                }
+                if comment.starts_with(',') && comment.contains("fuzzy") {
+                    entry.fuzzy = true;
+                }
+                continue;
+            }
+            if line.starts_with('"') {
+                let value = unquote(line).ok_or_else(|| error("Invalid string"))?;
+                entry
+                    .value_mut()
+                    .ok_or_else(|| error("Unexpected string"))?
+                    .push_str(&value);
+                continue;
+            }
+
+            let (keyword, value) =
+                line.split_once(' ').ok_or_else(|| error("Invalid line"))?;
+            let value = unquote(value.trim()).ok_or_else(|| error("Invalid string"))?;
+            let field = match keyword {
+                "msgctxt" => PoField::Context,
+                "msgid" => PoField::Id,
+                "msgid_plural" => PoField::Plural,
+                "msgstr" => PoField::Str(0),
+                keyword => {
+                    let idx = keyword
+                        .strip_prefix("msgstr[")
+                        .and_then(|v| v.strip_suffix(']'))
+                        .and_then(|v| v.parse::<usize>().ok())
+                        .ok_or_else(|| error("Unknown keyword"))?;
+                    PoField::Str(idx)
+                }
+            };
+
+            // msgctxt or msgid after msgstr starts the next entry
+            if matches!(field, PoField::Context | PoField::Id)
+                && matches!(entry.field, Some(PoField::Str(..)))
+            {
+                catalog.add_entry(take(&mut entry))?;
+            }
+            entry.field = Some(field);
+            *entry.value_mut().unwrap() = value;
+        }
+
+        if entry.field.is_some() {
+            catalog.add_entry(entry)?;
+        }
+
+        catalog.into_ok()
+    }
+
+    #[inline]
+    pub fn language(&self) -> &str {
+        &self.language
+    }
+
+    #[inline]
+    pub fn plural(&self) -> &PluralRule {
+        &self.plural
+    }
+
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.messages.len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.messages.is_empty()
+    }
+
+    /// Adds translation with plural *forms*, one form for singular messages
+    pub fn insert(&mut self, context: Option<&str>, id: &str, forms: Vec<String>) {
+        self.messages.insert(Self::key(context, id), forms);
+    }
+
+    pub fn get(&self, context: Option<&str>, id: &str) -> Option<&str> {
+        self.messages
+            .get(&Self::key(context, id))
+            .and_then(|forms| forms.first())
+            .map(String::as_str)
+            .filter(|v| v.is_empty() == false)
+    }
+
+    pub fn get_plural(&self, context: Option<&str>, id: &str, n: u64) -> Option<&str> {
+        self.messages
+            .get(&Self::key(context, id))
+            .and_then(|forms| forms.get(self.plural.index(n)))
+            .map(String::as_str)
+            .filter(|v| v.is_empty() == false)
+    }
+
+    fn key(context: Option<&str>, id: &str) -> String {
+        match context {
+            // the same separator as gettext uses
+            Some(context) => format!("{context}\u{4}{id}"),
+            None => id.into()
+        }
+    }
+
+    fn add_entry(&mut self, entry: PoEntry) -> Result<(), I18nError> {
+        let Some(id) = entry.id else {
+            return Err(I18nError::InvalidCatalog("Entry without msgid".into()));
+        };
+
+        // header
+        if id.is_empty() && entry.context.is_none() {
+            let header = entry.strs.first().map(String::as_str).unwrap_or_default();
+            for line in header.lines() {
+                if let Some(value) = line.strip_prefix("Plural-Forms:") {
+                    self.plural = PluralRule::parse(value)?;
+                }
+            }
+            return Ok(());
+        }
+
+        if entry.fuzzy == false {
+            self.insert(entry.context.as_deref(), &id, entry.strs);
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum PoField {
+    Context,
+    Id,
+    Plural,
+    Str(usize)
+}
+
+#[derive(Debug, Default)]
+struct PoEntry {
+    context: Option<String>,
+    id: Option<String>,
+    plural: Option<String>,
+    strs: Vec<String>,
+    fuzzy: bool,
+    field: Option<PoField>
+}
+
+impl PoEntry {
+    fn value_mut(&mut self) -> Option<&mut String> {
+        match self.field? {
+            PoField::Context => self.context.get_or_insert_default(),
+            PoField::Id => self.id.get_or_insert_default(),
+            PoField::Plural => self.plural.get_or_insert_default(),
+            PoField::Str(idx) => {
+                if self.strs.len() <= idx {
+                    self.strs.resize(idx + 1, String::new());
+                }
+                &mut self.strs[idx]
+            }
+        }
+        .into()
+    }
+}
+
+/// Unquotes string of `.po` with C escapes
+fn unquote(value: &str) -> Option<String> {
+    let value = value.strip_prefix('"')?.strip_suffix('"')?;
+    let mut res = String::with_capacity(value.len());
+    let mut chars = value.chars();
+
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            res.push(c);
+            continue;
+        }
+        res.push(match chars.next()? {
+            'n' => '\n',
+            't' => '\t',
+            'r' => '\r',
+            c @ ('"' | '\\') => c,
+            _ => return None
+        });
+    }
+
+    Some(res)
+}
+
+/// Plural rule of gettext `Plural-Forms` header, i.e.
+/// `nplurals=2; plural=(n != 1);`
+#[derive(Debug, Clone, PartialEq)]
+pub struct PluralRule {
+    nplurals: usize,
+    expr: PluralExpr
+}
+
+impl Default for PluralRule {
+    fn default() -> Self {
+        Self::parse(Self::DEFAULT).unwrap()
+    }
+}
+
+impl PluralRule {
+    pub const DEFAULT: &str = "nplurals=2; plural=(n != 1);";
+    /// Rules of languages which differ from the default one
+    pub const LANGUAGES: [(&[&str], &str); 6] = [
+        (&["fr", "pt_BR", "hy"], "nplurals=2; plural=(n > 1);"),
+        (
+            &["ru", "uk", "be", "sr", "hr", "bs"],
+            "nplurals=3; plural=(n%10==1 && n%100!=11 ? 0 : n%10>=2 && n%10<=4 && \
+             (n%100<10 || n%100>=20) ? 1 : 2);"
+        ),
+        (
+            &["pl"],
+            "nplurals=3; plural=(n==1 ? 0 : n%10>=2 && n%10<=4 && (n%100<10 || \
+             n%100>=20) ? 1 : 2);"
+        ),
+        (
+            &["cs", "sk"],
+            "nplurals=3; plural=(n==1) ? 0 : (n>=2 && n<=4) ? 1 : 2;"
+        ),
+        (
+            &["ja", "zh", "ko", "vi", "th", "id", "ms"],
+            "nplurals=1; plural=0;"
+        ),
+        (
+            &["ar"],
+            "nplurals=6; plural=(n==0 ? 0 : n==1 ? 1 : n==2 ? 2 : n%100>=3 && \
+             n%100<=10 ? 3 : n%100>=11 ? 4 : 5);"
+        )
+    ];
+
+    /// Rule of *language* or locale, the default rule for unknown ones
+    pub fn for_language(language: &str) -> Self {
+        let language = language.replace('-', "_");
+        let primary = language.split('_').next().unwrap_or_default();
+
+        [language.as_str(), primary]
+            .into_iter()
+            .find_map(|name| {
+                Self::LANGUAGES
+                    .iter()
+                    .find(|(languages, _)| languages.contains(&name))
+            })
+            .map(|(_, rule)| Self::parse(rule).unwrap())
+            .unwrap_or_default()
+    }
+
+    pub fn parse(value: &str) -> Result<Self, I18nError> {
+        let error = || I18nError::InvalidPluralForms(value.trim().into());
+        let mut nplurals = None;
+        let mut plural = None;
+
+        for item in value.split(';') {
+            match item.split_once('=') {
+                Some((k, v)) if k.trim() == "nplurals" => {
+                    nplurals = v.trim().parse::<usize>().ok().filter(|n| *n > 0);
+                },
+                Some((k, v)) if k.trim() == "plural" => plural = Some(v),
+                _ => {}
+            }
+        }
+
+        let (Some(nplurals), Some(plural)) = (nplurals, plural) else {
+            return Err(error());
+        };
+        let tokens = PluralExpr::tokens(plural).ok_or_else(error)?;
+        let mut parser = PluralParser { tokens: &tokens, pos: 0 };
+        let expr = parser.ternary().ok_or_else(error)?;
+        if parser.pos != tokens.len() {
+            return Err(error());
+        }
+
+        Self { nplurals, expr }.into_ok()
+    }
+
+    #[inline]
+    pub fn nplurals(&self) -> usize {
+        self.nplurals
+    }
+
+    /// Index of plural form for *n*
+    pub fn index(&self, n: u64) -> usize {
+        (self.expr.eval(n) as usize).min(self.nplurals - 1)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum PluralOp {
+    Or,
+    And,
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+    Add,
+    Sub,
+    Mul,
+    Div,
+    Rem
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum PluralExpr {
+    N,
+    Num(u64),
+    Not(Box<PluralExpr>),
+    Binary(PluralOp, Box<PluralExpr>, Box<PluralExpr>),
+    If(Box<PluralExpr>, Box<PluralExpr>, Box<PluralExpr>)
+}
+
+impl PluralExpr {
+    /// Binary operators by ascending precedence
+    const OPERATORS: [&[(&str, PluralOp)]; 6] = [
+        &[("||", PluralOp::Or)],
+        &[("&&", PluralOp::And)],
+        &[("==", PluralOp::Eq), ("!=", PluralOp::Ne)],
+        &[
+            ("<=", PluralOp::Le),
+            (">=", PluralOp::Ge),
+            ("<", PluralOp::Lt),
+            (">", PluralOp::Gt)
+        ],
+        &[("+", PluralOp::Add), ("-", PluralOp::Sub)],
+        &[
+            ("*", PluralOp::Mul),
+            ("/", PluralOp::Div),
+            ("%", PluralOp::Rem)
+        ]
+    ];
+
+    fn tokens(value: &str) -> Option<Vec<&str>> {
+        const SYMBOLS: [&str; 18] = [
+            "||", "&&", "==", "!=", "<=", ">=", "<", ">", "+", "-", "*", "/", "%", "!",
+            "?", ":", "(", ")"
+        ];
+        let mut tokens = Vec::new();
+        let mut rest = value.trim_start();
+
+        while rest.is_empty() == false {
+            let len = if rest.starts_with('n') {
+                1
+            } else if rest.starts_with(|c: char| c.is_ascii_digit()) {
+                rest.find(|c: char| c.is_ascii_digit() == false)
+                    .unwrap_or(rest.len())
+            } else {
+                SYMBOLS.iter().find(|s| rest.starts_with(*s))?.len()
+            };
+            tokens.push(&rest[..len]);
+            rest = rest[len..].trim_start();
+        }
+
+        Some(tokens)
+    }
+
+    fn eval(&self, n: u64) -> u64 {
+        match self {
+            Self::N => n,
+            Self::Num(v) => *v,
+            Self::Not(v) => (v.eval(n) == 0).into(),
+            Self::If(cond, a, b) => {
+                if cond.eval(n) != 0 {
+                    a.eval(n)
+                } else {
+                    b.eval(n)
+                }
+            },
+            Self::Binary(op, a, b) => {
+                let (a, b) = (a.eval(n), b.eval(n));
+                match op {
+                    PluralOp::Or => (a != 0 || b != 0).into(),
+                    PluralOp::And => (a != 0 && b != 0).into(),
+                    PluralOp::Eq => (a == b).into(),
+                    PluralOp::Ne => (a != b).into(),
+                    PluralOp::Lt => (a < b).into(),
+                    PluralOp::Le => (a <= b).into(),
+                    PluralOp::Gt => (a > b).into(),
+                    PluralOp::Ge => (a >= b).into(),
+                    PluralOp::Add => a.wrapping_add(b),
+                    PluralOp::Sub => a.wrapping_sub(b),
+                    PluralOp::Mul => a.wrapping_mul(b),
+                    PluralOp::Div => a.checked_div(b).unwrap_or_default(),
+                    PluralOp::Rem => a.checked_rem(b).unwrap_or_default()
+                }
+            }
+        }
+    }
+}
+
+struct PluralParser<'a> {
+    tokens: &'a [&'a str],
+    pos: usize
+}
+
+impl PluralParser<'_> {
+    fn next_if(&mut self, token: &str) -> bool {
+        let found = self.tokens.get(self.pos) == Some(&token);
+        if found {
+            self.pos += 1;
+        }
+        found
+    }
+
+    fn ternary(&mut self) -> Option<PluralExpr> {
+        let cond = self.binary(0)?;
+        if self.next_if("?") == false {
+            return Some(cond);
+        }
+
+        let a = self.ternary()?;
+        self.next_if(":").then_some(())?;
+        let b = self.ternary()?;
+
+        Some(PluralExpr::If(cond.into(), a.into(), b.into()))
+    }
+
+    fn binary(&mut self, level: usize) -> Option<PluralExpr> {
+        let Some(operators) = PluralExpr::OPERATORS.get(level) else {
+            return self.unary();
+        };
+
+        let mut expr = self.binary(level + 1)?;
+        'outer: loop {
+            for (token, op) in operators.iter() {
+                if self.next_if(token) {
+                    let rhs = self.binary(level + 1)?;
+                    expr = PluralExpr::Binary(*op, expr.into(), rhs.into());
+                    continue 'outer;
+                }
+            }
+            return Some(expr);
+        }
+    }
+
+    fn unary(&mut self) -> Option<PluralExpr> {
+        if self.next_if("!") {
+            return Some(PluralExpr::Not(self.unary()?.into()));
+        }
+        if self.next_if("(") {
+            let expr = self.ternary()?;
+            return self.next_if(")").then_some(expr);
+        }
+
+        let token = *self.tokens.get(self.pos)?;
+        self.pos += 1;
+        match token {
+            "n" => Some(PluralExpr::N),
+            token => token.parse().ok().map(PluralExpr::Num)
+        }
+    }
+}
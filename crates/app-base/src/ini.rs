@@ -4,10 +4,15 @@ use libc_print::std_name::*;
 use {
     crate::{
         base::{BaseFromInto, Ok},
-        binds,
-        prelude::Env
+        binds
+    },
+    alloc::{
+        boxed::Box,
+        ffi::CString,
+        format,
+        string::{String, ToString},
+        vec::{self, Vec}
     },
-    alloc::{boxed::Box, ffi::CString, format, string::String, vec::Vec},
     core::{
         error::Error,
         ffi::{CStr, c_char, c_int, c_void},
@@ -18,27 +23,38 @@ use {
 };
 
 type IniMap = crate::base::IndexMap<Box<str>, Option<Box<str>>>;
+type IniArrays = crate::base::IndexMap<Box<str>, Vec<Box<str>>>;
 
 #[derive(Debug, PartialEq, Eq)]
 pub enum IniError {
     FileNotFound(String),
-    InvalidParse(String)
+    InvalidParse(String),
+    Syntax { file: String, line: usize, column: usize, message: String }
 }
 impl Error for IniError {}
 
 impl Display for IniError {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
-        let s = match self {
-            Self::FileNotFound(s) => s,
-            Self::InvalidParse(s) => s
-        };
-        write!(f, "{s}")
+        match self {
+            Self::FileNotFound(s) => write!(f, "{s}"),
+            Self::InvalidParse(s) => write!(f, "{s}"),
+            Self::Syntax { file, line, column, message } => {
+                write!(f, "{file}:{line}:{column}: {message}")
+            }
+        }
     }
 }
 
+/// Parsed ini file
+///
+/// Keys are flattened to `section.name`, the last of repeated keys wins.
+/// Values of keys with `[]` suffix are collected to arrays and also stored
+/// as comma separated list. Indented lines continue the value of previous
+/// key.
 #[derive(Default, Debug, Clone)]
 pub struct Ini {
-    items: IniMap
+    items: IniMap,
+    arrays: IniArrays
 }
 
 impl Deref for Ini {
@@ -68,33 +84,66 @@ impl<'a> IntoIterator for &'a Ini {
     }
 }
 
+impl FromStr for Ini {
+    type Err = crate::base::Err;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s, "<string>")
+    }
+}
+
 impl Ini {
+    pub const ARRAY_SEPARATOR: &str = ",";
+
     pub fn from_file(path: &dyn AsRef<str>) -> Ok<Self> {
-        let mut this = Self { items: Default::default() };
-        let c_path = CString::from_str(path.as_ref())?;
+        let content = Self::read_file(path.as_ref())?;
+        Self::parse(&content, path.as_ref())
+    }
 
-        unsafe {
-            if libc::access(c_path.as_ptr(), libc::F_OK) != 0 {
-                Err(IniError::FileNotFound(format!(
-                    "File not found: {}",
-                    path.as_ref()
-                )))?;
+    /// Parses ini content
+    ///
+    /// *file* is used only in error messages.
+    pub fn parse(content: &str, file: &str) -> Ok<Self> {
+        let mut context =
+            IniContext { lines: content.lines().collect(), values: Vec::new() };
+
+        let c_content = CString::new(content).map_err(|e| {
+            let (line, column) = context.position(e.nul_position());
+            IniError::Syntax {
+                file: file.into(),
+                line,
+                column,
+                message: "unexpected NUL character".into()
             }
+        })?;
 
-            if binds::ini_parse(
-                c_path.as_ptr(),
+        let res = unsafe {
+            binds::ini_parse_string(
+                c_content.as_ptr(),
                 Some(Self::ini_parse_callback),
-                (&mut this.items as *mut IniMap).cast()
-            ) != 0
-            {
+                (&mut context as *mut IniContext).cast()
+            )
+        };
+
+        match res {
+            0 => context.into_ini(file),
+            line if line > 0 => Err(context.syntax_error(file, line as usize))?,
+            _ => {
                 Err(IniError::InvalidParse(format!(
-                    "Could not parse config file: {}",
-                    path.as_ref()
-                )))?;
-            }
+                    "Could not parse config file: {file}"
+                )))?
+            },
         }
+    }
+
+    #[inline]
+    pub fn get_array(&self, key: &str) -> Option<&[Box<str>]> {
+        self.arrays.get(key).map(Vec::as_slice)
+    }
 
-        this.into_ok()
+    #[inline]
+    pub fn arrays(&self) -> &IniArrays {
+        &self.arrays
     }
 
     pub fn setenv(&self, overwrite: bool) -> Ok<&Self> {
@@ -121,78 +170,256 @@ impl Ini {
         Self::setenv_from_file(path, overwrite)
     }
 
+    #[cfg(feature = "std")]
+    pub(crate) fn read_file(path: &str) -> Ok<String> {
+        match std::fs::read(path) {
+            Ok(content) => String::from_utf8_lossy(&content).into_owned().into_ok(),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                Err(IniError::FileNotFound(format!("File not found: {path}")))?
+            },
+            Err(e) => Err(IniError::InvalidParse(format!("{e}: {path}")))?
+        }
+    }
+
+    #[cfg(not(feature = "std"))]
+    pub(crate) fn read_file(path: &str) -> Ok<String> {
+        let c_path = CString::from_str(path)?;
+        let mut content = Vec::new();
+
+        unsafe {
+            if libc::access(c_path.as_ptr(), libc::F_OK) != 0 {
+                Err(IniError::FileNotFound(format!("File not found: {path}")))?;
+            }
+
+            let file = libc::fopen(c_path.as_ptr(), c"r".as_ptr());
+            if file.is_null() {
+                Err(IniError::InvalidParse(format!(
+                    "Could not open file: {path}"
+                )))?;
+            }
+
+            let mut buf = [0u8; 4096];
+            loop {
+                let len = libc::fread(buf.as_mut_ptr().cast(), 1, buf.len(), file);
+                content.extend_from_slice(&buf[..len]);
+                if len < buf.len() {
+                    break;
+                }
+            }
+
+            libc::fclose(file);
+        }
+
+        String::from_utf8_lossy(&content).into_owned().into_ok()
+    }
+
+    /// Removes surrounding quotes and unescapes `\"`, `\'`, `\\`, `\n`, `\r`,
+    /// `\t` inside of quoted value. Unquoted values are returned as is.
+    ///
+    /// Returns byte offset of invalid char on error.
+    fn unquote(value: &str) -> Result<String, (usize, &'static str)> {
+        let Some(quote) = value.chars().next().filter(|c| ['\'', '"'].contains(c)) else {
+            return Ok(value.into());
+        };
+
+        let mut result = String::with_capacity(value.len());
+        let mut chars = value.char_indices().skip(1);
+
+        while let Some((pos, c)) = chars.next() {
+            match c {
+                '\\' => {
+                    match chars.next() {
+                        Some((.., 'n')) => result.push('\n'),
+                        Some((.., 'r')) => result.push('\r'),
+                        Some((.., 't')) => result.push('\t'),
+                        Some((.., c @ ('\\' | '\'' | '"'))) => result.push(c),
+                        Some((.., c)) => {
+                            result.push('\\');
+                            result.push(c);
+                        },
+                        None => return Err((pos, "unterminated escape sequence"))
+                    }
+                },
+                c if c == quote => {
+                    let rest = &value[pos + 1..];
+                    let trimmed = rest.trim_start();
+                    if trimmed.is_empty() {
+                        return Ok(result);
+                    }
+                    return Err((
+                        pos + 1 + rest.len() - trimmed.len(),
+                        "unexpected characters after quoted value"
+                    ));
+                },
+                c => result.push(c)
+            }
+        }
+
+        Err((0, "unterminated quoted value"))
+    }
+
     extern "C" fn ini_parse_callback(
         context: *mut c_void,
         section: *const c_char,
         name: *const c_char,
-        value: *const c_char
+        value: *const c_char,
+        lineno: c_int
     ) -> c_int {
-        if context.is_null() || name.is_null() || value.is_null() || section.is_null() {
+        if context.is_null() || name.is_null() || section.is_null() {
             return 0;
         }
 
-        let items: &mut IniMap = unsafe { &mut *context.cast() };
+        let context: &mut IniContext = unsafe { &mut *context.cast() };
         let section = unsafe { CStr::from_ptr(section) };
         let name = unsafe { CStr::from_ptr(name) };
-        let value = unsafe { CStr::from_ptr(value) };
+        let value = (value.is_null() == false).then(|| {
+            unsafe { CStr::from_ptr(value) }
+                .to_string_lossy()
+                .into_owned()
+        });
+
+        context.add(
+            &section.to_string_lossy(),
+            &name.to_string_lossy(),
+            value,
+            lineno as usize
+        );
 
-        let key: String = if section.is_empty() {
-            name.to_string_lossy().into_owned()
+        return 1;
+    }
+}
+
+/// Raw value with positions of its lines: (value offset, line, column)
+struct IniValue {
+    key: String,
+    value: Option<String>,
+    array: bool,
+    lines: Vec<(usize, usize, usize)>
+}
+
+struct IniContext<'a> {
+    lines: Vec<&'a str>,
+    values: Vec<IniValue>
+}
+
+impl IniContext<'_> {
+    fn add(&mut self, section: &str, name: &str, value: Option<String>, line: usize) {
+        let (name, array) = match name.strip_suffix("[]") {
+            Some(name) => (name.trim_end(), true),
+            None => (name, false)
+        };
+        let key = if section.is_empty() {
+            name.to_string()
         } else {
-            section.to_string_lossy().into_owned() + "." + &name.to_string_lossy()
+            [section, ".", name].concat()
         };
+        let raw = self
+            .lines
+            .get(line.wrapping_sub(1))
+            .copied()
+            .unwrap_or_default();
+        let indent = raw.len() - raw.trim_start().len();
 
-        let mut value: String = value.to_string_lossy().into_owned();
-
-        if let (Some(fc), Some(lc)) = (value.chars().next(), value.chars().last())
-            && ['\'', '\"'].contains(&fc)
-            && fc == lc
-            && value.chars().count() > 1
+        // Indented line continues multi-line value
+        if indent > 0
+            && let Some(last) = self.values.last_mut()
+            && last.key == key
         {
-            value = value.trim_matches(fc).into();
-        };
+            let last_value = last.value.get_or_insert_default();
+            if last_value.is_empty() == false {
+                last_value.push('\n');
+            }
+            last.lines.push((last_value.len(), line, indent + 1));
+            last_value.push_str(value.as_deref().unwrap_or_default());
+            return;
+        }
 
-        items.insert(
-            key.into(),
-            if value.is_empty() { None } else { Some(value.into()) }
-        );
+        let column = value
+            .as_deref()
+            .and_then(|value| {
+                let pos = raw.find(['=', ':'])? + 1;
+                raw[pos..].find(value).map(|p| pos + p + 1)
+            })
+            .unwrap_or(indent + 1);
 
-        return 1;
+        self.values
+            .push(IniValue { key, value, array, lines: vec![(0, line, column)] });
     }
-}
 
-/// Loads .env file variables
-///
-/// Returns zero if initialization is successfull.
-/// Otherwise returns int less zero.
-#[unsafe(no_mangle)]
-pub extern "C" fn dotenv(overwrite: bool) -> c_int {
-    let res = match Ini::dotenv(&".env", overwrite) {
-        Ok(..) => {
-            unsafe { Env::reset() };
-            0
-        },
-        Err(e) => {
-            match e.downcast_ref::<IniError>() {
-                // don't panic if file not exists
-                Some(IniError::FileNotFound(..)) => -1,
-                _ => panic!("dotenv error: {e}")
+    /// Converts byte offset of content to line and column
+    fn position(&self, offset: usize) -> (usize, usize) {
+        let mut start = 0;
+
+        for (n, line) in self.lines.iter().enumerate() {
+            let end = start + line.len() + 1;
+            if offset < end {
+                return (n + 1, offset - start + 1);
             }
+            start = end;
         }
-    };
-
-    if Env::is_test() {
-        match Ini::dotenv(&".env.test", true) {
-            Ok(..) => unsafe { Env::reset() },
-            Err(e) => {
-                match e.downcast_ref::<IniError>() {
-                    // don't panic if file not exists
-                    Some(IniError::FileNotFound(..)) => (),
-                    _ => panic!("dotenv.test error: {e}")
+
+        (self.lines.len().max(1), 1)
+    }
+
+    fn syntax_error(&self, file: &str, line: usize) -> IniError {
+        let raw = self
+            .lines
+            .get(line.wrapping_sub(1))
+            .copied()
+            .unwrap_or_default()
+            .trim_end();
+        let indent = raw.len() - raw.trim_start().len();
+
+        let (column, message) = if raw.trim_start().starts_with('[') {
+            (raw.len() + 1, "expected ']' at the end of section name")
+        } else {
+            (indent + 1, "invalid line")
+        };
+
+        IniError::Syntax { file: file.into(), line, column, message: message.into() }
+    }
+
+    fn into_ini(self, file: &str) -> Ok<Ini> {
+        let mut ini = Ini::default();
+
+        for item in self.values.iter() {
+            let value = match item.value.as_deref() {
+                Some(value) => {
+                    Ini::unquote(value).map_err(|(offset, message)| {
+                        let (start, line, column) = item
+                            .lines
+                            .iter()
+                            .rfind(|(start, ..)| *start <= offset)
+                            .copied()
+                            .unwrap_or((0, 0, 0));
+                        IniError::Syntax {
+                            file: file.into(),
+                            line,
+                            column: column + offset - start,
+                            message: message.into()
+                        }
+                    })?
+                },
+                None => String::new()
+            };
+            let key: Box<str> = item.key.as_str().into();
+
+            if item.array {
+                let array = ini.arrays.entry(key.clone()).or_default();
+                if value.is_empty() == false {
+                    array.push(value.into());
                 }
+                let joined = array.join(Ini::ARRAY_SEPARATOR);
+                ini.items
+                    .insert(key, (joined.is_empty() == false).then(|| joined.into()));
+            } else {
+                ini.items.insert(
+                    key,
+                    if value.is_empty() { None } else { Some(value.into()) }
+                );
             }
         }
-    }
 
-    res
+        ini.into_ok()
+    }
 }
@@ -3,7 +3,19 @@
 #[allow(unused_imports)]
 #[macro_use]
 extern crate core;
-extern crate alloc;
+// Allows alloc paths in code of app-macros for crates without `extern crate`
+#[doc(hidden)]
+pub extern crate alloc;
+// Allows paths of app-macros inside of the crate
+extern crate self as app_base;
+// Allows printing in code of app-macros for std and no_std crates
+#[doc(hidden)]
+#[cfg(feature = "std")]
+pub use std::println;
+
+#[doc(hidden)]
+#[cfg(not(feature = "std"))]
+pub use libc_print::std_name::println;
 
 #[cfg(not(feature = "std"))]
 mod no_std;
@@ -12,17 +24,30 @@ mod binds;
 
 pub mod base_config;
 pub mod log_config;
+pub mod log_context;
 pub mod app;
 pub mod ini;
+pub mod dotenv;
 pub mod base;
 pub mod log;
+pub mod log_file;
+pub mod log_ring;
+pub mod log_sink;
+pub mod log_syslog;
 pub mod env;
 pub mod mem_stats;
+pub mod metrics;
 pub mod prelude;
 pub mod macros;
 pub mod args;
 pub mod convert;
+pub mod extend;
+pub mod fields;
+pub mod crash_report;
 pub mod dirs;
 pub mod di;
 pub mod serde;
+pub mod spin_lock;
 pub mod filters;
+pub mod i18n;
+pub mod time;
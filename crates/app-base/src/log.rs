@@ -1,18 +1,13 @@
 use {
-    crate::{
-        alloc::{ffi::CString, string::ToString},
-        prelude::*
-    },
-    alloc::{boxed::Box, format, string::String},
+    crate::{alloc::string::ToString, prelude::*},
+    alloc::{format, string::String, sync::Arc, vec::Vec},
     core::{
         ffi::{CStr, c_char},
-        mem::{transmute, zeroed},
+        mem::transmute,
         ops::{Deref, DerefMut},
-        ptr::null_mut,
-        str::FromStr,
-        sync::atomic::{AtomicBool, Ordering}
+        sync::atomic::{AtomicBool, AtomicUsize, Ordering}
     },
-    log::{Level, LevelFilter, Log},
+    log::{Level, LevelFilter, Log, kv},
     yansi::Paint
 };
 #[cfg(not(feature = "std"))]
@@ -70,27 +65,39 @@ extern "C" fn log_msg(level: LogLevel, target: *const c_char, msg: *const c_char
 /// Set max log level in C
 #[unsafe(no_mangle)]
 extern "C" fn log_max_level(level: LogLevel) {
-    log::set_max_level(level.into());
+    Logger::from_static().set_level(level.into());
 }
 
 /// Logger
 #[derive(FromStatic)]
 pub struct Logger {
     config: LogConfig,
-    file: Option<Box<libc::FILE>>,
-    lock: AtomicBool
+    sinks: Arc<Vec<LogSinkEntry>>,
+    #[cfg(feature = "std")]
+    writer: Option<LogWriter>,
+    levels: LogLevels,
+    ring: LogRing,
+    /// Timezone of timestamps, the configured one of `Time` at `configure()`
+    timezone: Arc<Timezone>,
+    /// Sinks are closed, records aren't written until `configure()`
+    closed: AtomicBool
 }
 
 impl Default for Logger {
     fn default() -> Self {
         let mut log = Logger {
             config: Default::default(),
-            file: Default::default(),
-            lock: Default::default()
+            sinks: Default::default(),
+            #[cfg(feature = "std")]
+            writer: None,
+            levels: Default::default(),
+            ring: Default::default(),
+            timezone: Default::default(),
+            closed: AtomicBool::new(false)
         };
 
         let mut config = LogConfig::default();
-        config.load_env();
+        let _ = config.load_env();
         log.configure(&config).unwrap();
 
         log
@@ -119,114 +126,104 @@ impl DerefMut for Logger {
 
 impl Logger {
     pub fn configure(&mut self, config: &LogConfig) -> Void {
+        let directives = config.directives()?;
         self.log_close();
         self.config.clone_from(config);
 
-        if let Some(path) = self.config.file.as_ref()
-            && path.is_empty() == false
-        {
-            Dirs::mkdir(Dirs::dirname(path))?;
-            unsafe {
-                let file =
-                    libc::fopen(CString::new(path.as_str())?.as_ptr(), c"a+".as_ptr());
-                if file.is_null() {
-                    Err(format!("Could not open log file: {path}"))?;
-                }
-                self.file = Box::from_raw(file).into();
-            }
+        self.sinks = LogSinkEntry::from_config(&self.config)?.into();
+        self.ring.set_capacity(self.config.ring);
+        self.timezone = Time::timezone();
+
+        #[cfg(feature = "std")]
+        if self.config.buffer > 0 {
+            self.writer = LogWriter::start(
+                self.sinks.clone(),
+                self.config.buffer,
+                self.config.overflow
+            )?
+            .into();
         }
 
-        log::set_max_level(self.config.level);
+        self.levels = LogLevels::new(directives);
+        log::set_max_level(self.levels.max());
+        self.closed.store(false, Ordering::SeqCst);
 
         Env::is_debug().then(|| log::trace!("Configured {:?}", self.config));
 
         ok()
     }
 
-    /// Close log file descriptor
-    #[unsafe(no_mangle)]
-    pub extern "C" fn log_close(&mut self) {
-        if let Some(file) = self.file.take() {
-            unsafe { libc::fclose(Box::into_raw(file)) };
-
-            Env::is_debug().then(|| {
-                log::trace!(
-                    "LOG FILE CLOSED: {}",
-                    self.config
-                        .file
-                        .as_ref()
-                        .unwrap_or(&String::with_capacity(0))
-                )
-            });
+    /// Sets global level, levels of filter directives stay
+    ///
+    /// Levels are changed atomically, so it's safe while other threads log.
+    /// `level` of config keeps the configured one.
+    pub fn set_level(&self, level: LevelFilter) {
+        let mut config = self.config.clone();
+        config.level = level;
+        // filter is checked by `configure()`
+        if let Ok(directives) = config.directives() {
+            self.levels.set(directives);
         }
+
+        log::set_max_level(self.levels.max());
     }
 
-    pub fn get_closer(&'static mut self) -> LogCloser {
-        LogCloser { logger: self }
+    /// Level of the most specific directive matched to target
+    #[inline]
+    pub fn target_level(&self, target: &str) -> LevelFilter {
+        self.levels.get(target)
     }
 
-    fn time() -> String {
-        unsafe {
-            let mut time: libc::timeval = zeroed();
-            libc::gettimeofday(&mut time as *mut _, null_mut());
-            let local = &*libc::localtime(&time.tv_sec);
-            const BUFF_LEN: usize = 60;
-            let buff = &mut [0i8; BUFF_LEN] as *mut _;
-            #[cfg(target_arch = "aarch64")]
-            let buff = buff as *mut u8;
-
-            libc::strftime(buff, BUFF_LEN, c"%F %T".as_ptr(), local);
-            libc::sprintf(
-                buff.wrapping_add(libc::strlen(buff)),
-                c".%06ld".as_ptr(),
-                time.tv_usec
-            );
-            libc::strftime(
-                buff.wrapping_add(libc::strlen(buff)),
-                6,
-                c"%z".as_ptr(),
-                local
-            );
-
-            CStr::from_ptr(buff).to_string_lossy().to_string()
-        }
+    /// Last log lines in text format kept for crash reports
+    #[inline]
+    pub fn ring(&self) -> &LogRing {
+        &self.ring
     }
-}
 
-impl Log for Logger {
-    fn enabled(&self, metadata: &log::Metadata) -> bool {
-        metadata.level() <= log::max_level()
+    /// Number of records dropped by background writer on overflow
+    pub fn dropped(&self) -> u64 {
+        #[cfg(feature = "std")]
+        return self
+            .writer
+            .as_ref()
+            .map(|w| w.dropped())
+            .unwrap_or_default();
+
+        #[cfg(not(feature = "std"))]
+        0
     }
 
-    fn log(&self, record: &log::Record) {
-        if self.enabled(record.metadata()) == false {
+    /// Writes pending records and closes sinks on shutdown
+    ///
+    /// Sinks stay in place until `configure()` and a file sink waits for its
+    /// current write, so it's safe while other threads log, their later
+    /// records are skipped. Panics only flush the logger, since the process
+    /// may keep running after a caught panic.
+    #[unsafe(no_mangle)]
+    pub extern "C" fn log_close(&self) {
+        if self.closed.swap(true, Ordering::SeqCst) {
             return;
         }
 
-        if let Some(filter) = self.config.filter.as_ref() {
-            let target = record.target();
-            let mut allow = true;
-
-            for value in filter.iter() {
-                if let Some(value) = value.strip_prefix("!") {
-                    if target.starts_with(value) {
-                        allow = false;
-                        break;
-                    }
-                } else if target.starts_with(value) {
-                    allow = true;
-                    break;
-                } else {
-                    allow = false;
-                }
+        #[cfg(feature = "std")]
+        if let Some(writer) = self.writer.as_ref() {
+            writer.stop();
+            if writer.dropped() > 0 {
+                eprintln!("Log records dropped on overflow: {}", writer.dropped());
             }
+        }
 
-            if allow == false {
-                return;
-            }
+        for entry in self.sinks.iter() {
+            entry.sink.flush();
+            entry.sink.close();
         }
+    }
 
-        let allow_color = self.config.color && self.file.is_none();
+    pub fn get_closer(&'static self) -> LogCloser {
+        LogCloser { logger: self }
+    }
+
+    fn format_text(record: &log::Record, allow_color: bool) -> String {
         let level = if allow_color {
             match record.level() {
                 l @ Level::Info => l.bright_green().to_string(),
@@ -238,9 +235,9 @@ impl Log for Logger {
         } else {
             record.level().to_string()
         };
-        let out = format!(
-            "[{}] [pid:{} tid:{}] {:<len$} [{}] {}\n",
-            Self::time(),
+        let mut out = format!(
+            "[{}] [pid:{} tid:{}] {:<len$} [{}] {}",
+            Self::time(c"%F %T"),
             unsafe { libc::getpid() },
             unsafe { libc::pthread_self() as usize },
             format!("[{}]", level),
@@ -249,38 +246,232 @@ impl Log for Logger {
             len = if allow_color { 16 } else { 7 }
         );
 
-        if self.file.is_none() {
-            eprint!("{out}");
+        LogContext::with(|fields| {
+            for (key, value) in fields {
+                out.push_str(&format!(" {key}={value}"));
+            }
+        });
+        let _ = record.key_values().visit(&mut KeyValues(
+            |key: kv::Key, value: kv::Value| {
+                out.push_str(&format!(" {key}={value}"));
+            }
+        ));
+        out.push('\n');
+
+        out
+    }
+
+    fn format_json(record: &log::Record) -> String {
+        let mut out = serde_json::json!({
+            "timestamp": Self::time(c"%FT%T"),
+            "level": record.level().as_str(),
+            "target": record.target(),
+            "pid": unsafe { libc::getpid() },
+            "tid": unsafe { libc::pthread_self() as usize },
+            "message": record.args().to_string(),
+            "module_path": record.module_path(),
+            "file": record.file(),
+            "line": record.line()
+        });
+
+        if let Some(fields) = out.as_object_mut() {
+            LogContext::with(|context| {
+                for (key, value) in context {
+                    if fields.contains_key(key.as_ref()) == false {
+                        fields.insert(key.to_string(), value.as_str().into());
+                    }
+                }
+            });
+            let _ = record.key_values().visit(&mut KeyValues(
+                |key: kv::Key, value: kv::Value| {
+                    // key-values never replace fields of record
+                    if fields.contains_key(key.as_str()) == false {
+                        fields.insert(key.to_string(), Self::json_value(&value));
+                    }
+                }
+            ));
+        }
+
+        let mut out = out.to_string();
+        out.push('\n');
+
+        out
+    }
+
+    fn json_value(value: &kv::Value) -> serde_json::Value {
+        if let Some(v) = value.to_bool() {
+            v.into()
+        } else if let Some(v) = value.to_i64() {
+            v.into()
+        } else if let Some(v) = value.to_u64() {
+            v.into()
+        } else if let Some(v) = value.to_f64() {
+            v.into()
+        } else if let Some(v) = value.to_borrowed_str() {
+            v.into()
+        } else {
+            value.to_string().into()
+        }
+    }
+
+    /// Current time with microseconds and offset in the configured timezone
+    /// or UTC
+    pub(crate) fn time(format: &CStr) -> String {
+        let logger = Self::from_static();
+        let (timestamp, nanos) = Time::unix();
+        let time = match logger.utc {
+            true => DateTime::utc(timestamp, nanos),
+            false => logger.timezone.at(timestamp, nanos)
+        };
+
+        let mut out = time.strftime(format);
+        out.push_str(&format!(".{:06}", time.nanos() / 1000));
+        out.push_str(&time.strftime(c"%z"));
+
+        out
+    }
+}
+
+impl Log for Logger {
+    fn enabled(&self, metadata: &log::Metadata) -> bool {
+        metadata.level() <= self.target_level(metadata.target())
+    }
+
+    fn log(&self, record: &log::Record) {
+        if self.enabled(record.metadata()) == false || self.closed.load(Ordering::Relaxed)
+        {
             return;
         }
 
-        while self.lock.swap(true, Ordering::SeqCst) {
-            #[cfg(not(feature = "std"))]
-            unsafe {
-                libc::sched_yield();
-            }
-            #[cfg(feature = "std")]
-            std::thread::yield_now();
+        let mut text = None;
+        let mut text_color = None;
+        let mut json = None;
+        let records: Vec<(usize, Vec<u8>)> = self
+            .sinks
+            .iter()
+            .enumerate()
+            .filter(|(.., entry)| record.level() <= entry.level)
+            .map(|(n, entry)| {
+                if let Some(out) = entry.sink.format(record) {
+                    return (n, out);
+                }
+                let out = match (entry.format, entry.color) {
+                    (LogFormat::Text, false) => {
+                        text.get_or_insert_with(|| Self::format_text(record, false))
+                    },
+                    (LogFormat::Text, true) => {
+                        text_color.get_or_insert_with(|| Self::format_text(record, true))
+                    },
+                    (LogFormat::Json, ..) => {
+                        json.get_or_insert_with(|| Self::format_json(record))
+                    },
+                };
+                (n, out.clone().into_bytes())
+            })
+            .collect();
+
+        if self.ring.capacity() > 0 {
+            self.ring
+                .push(text.get_or_insert_with(|| Self::format_text(record, false)));
         }
 
-        unsafe {
-            libc::fputs(
-                CString::from_str(&out)
-                    .inspect_err(|_| self.lock.store(false, Ordering::SeqCst))
-                    .unwrap()
-                    .as_ptr(),
-                self.file.as_ref().unwrap().as_ref() as *const _ as *mut _
-            );
+        #[cfg(feature = "std")]
+        let records = match self.writer.as_ref() {
+            Some(writer) => writer.send(records),
+            None => Some(records)
+        };
+        #[cfg(not(feature = "std"))]
+        let records = Some(records);
+
+        for (n, out) in records.into_iter().flatten() {
+            self.sinks[n].sink.write(&out);
         }
+    }
 
-        self.lock.store(false, Ordering::SeqCst);
+    fn flush(&self) {
+        #[cfg(feature = "std")]
+        if let Some(writer) = self.writer.as_ref() {
+            writer.flush();
+            return;
+        }
+
+        self.sinks.iter().for_each(|entry| entry.sink.flush());
+    }
+}
+
+/// Default level and levels of filter directives by target prefix
+///
+/// Prefixes are set by `Logger::configure()` only, levels are atomic, so
+/// they're changed while other threads log.
+#[derive(Default)]
+struct LogLevels {
+    default: AtomicUsize,
+    directives: Vec<(String, AtomicUsize)>
+}
+
+impl LogLevels {
+    fn new((default, directives): (LevelFilter, Vec<(String, LevelFilter)>)) -> Self {
+        Self {
+            default: AtomicUsize::new(default as usize),
+            directives: directives
+                .into_iter()
+                .map(|(prefix, level)| (prefix, AtomicUsize::new(level as usize)))
+                .collect()
+        }
+    }
+
+    /// Sets levels of the same directives, i.e. by changed global level
+    fn set(&self, (default, directives): (LevelFilter, Vec<(String, LevelFilter)>)) {
+        self.default.store(default as usize, Ordering::Relaxed);
+        for ((.., old), (.., new)) in self.directives.iter().zip(directives) {
+            old.store(new as usize, Ordering::Relaxed);
+        }
+    }
+
+    fn get(&self, target: &str) -> LevelFilter {
+        let level = self
+            .directives
+            .iter()
+            .find(|(prefix, ..)| target.starts_with(prefix.as_str()))
+            .map(|(.., level)| level)
+            .unwrap_or(&self.default);
+
+        Self::filter(level.load(Ordering::Relaxed))
+    }
+
+    fn max(&self) -> LevelFilter {
+        self.directives
+            .iter()
+            .map(|(.., level)| level)
+            .chain([&self.default])
+            .map(|level| Self::filter(level.load(Ordering::Relaxed)))
+            .fold(LevelFilter::Off, Ord::max)
     }
 
-    fn flush(&self) {}
+    fn filter(level: usize) -> LevelFilter {
+        LevelFilter::iter().nth(level).unwrap_or(LevelFilter::Trace)
+    }
+}
+
+/// Visitor of key-values of log record
+pub(crate) struct KeyValues<F>(pub F);
+
+impl<'kvs, F> kv::VisitSource<'kvs> for KeyValues<F>
+where
+    F: FnMut(kv::Key<'kvs>, kv::Value<'kvs>)
+{
+    fn visit_pair(
+        &mut self,
+        key: kv::Key<'kvs>,
+        value: kv::Value<'kvs>
+    ) -> Result<(), kv::Error> {
+        (self.0)(key, value);
+        Ok(())
+    }
 }
 
 pub struct LogCloser {
-    logger: &'static mut Logger
+    logger: &'static Logger
 }
 
 impl Drop for LogCloser {
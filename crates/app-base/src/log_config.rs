@@ -1,19 +1,134 @@
 use {
     crate::prelude::*,
     alloc::{
+        format,
         string::{String, ToString},
         vec::Vec
     },
+    core::{fmt::Display, str::FromStr},
     log::LevelFilter
 };
 
-#[derive(Debug, Clone, ExtendFromIter)]
+/// Format of log records
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, ExtendFromIter)]
+pub enum LogFormat {
+    /// `[time] [pid tid] [LEVEL] [target] msg`
+    #[default]
+    Text,
+    /// One JSON object per line
+    Json
+}
+
+impl Display for LogFormat {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::Text => write!(f, "text"),
+            Self::Json => write!(f, "json")
+        }
+    }
+}
+
+/// Behavior of background log writer when its buffer is full
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, ExtendFromIter)]
+pub enum LogOverflow {
+    /// Waits for free space in buffer
+    #[default]
+    Block,
+    /// Drops record and counts it
+    Drop
+}
+
+impl Display for LogOverflow {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::Block => write!(f, "block"),
+            Self::Drop => write!(f, "drop")
+        }
+    }
+}
+
+/// Sink of log records: `name[:level][:format]`, e.g. `stderr:warn`,
+/// `file:debug:json`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LogSinkConfig {
+    pub name: String,
+    /// Caps levels of filter for the sink
+    pub level: Option<LevelFilter>,
+    pub format: Option<LogFormat>
+}
+
+impl FromStr for LogSinkConfig {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parts = s.split(':').map(str::trim);
+        let mut sink = Self {
+            name: parts.next().unwrap_or_default().to_lowercase(),
+            level: None,
+            format: None
+        };
+
+        if sink.name.is_empty() {
+            Err(format!("Empty log sink name: {s}"))?;
+        }
+
+        for part in parts {
+            if let Ok(level) = part.parse() {
+                sink.level = Some(level);
+            } else if let Ok(format) = part.parse() {
+                sink.format = Some(format);
+            } else {
+                Err(format!("Unknown log sink option '{part}': {s}"))?;
+            }
+        }
+
+        Ok(sink)
+    }
+}
+
+#[derive(Debug, Clone, ExtendFromIter, AppConfig)]
+#[config(
+    prefix = "base.log",
+    arg_prefix = "log",
+    env_prefix = "LOG",
+    after_args = "load_debug"
+)]
 pub struct LogConfig {
     #[extend_parse]
     pub level: LevelFilter,
+    pub format: LogFormat,
     pub color: bool,
+    /// Writes timestamps in UTC instead of `base.timezone`
+    pub utc: bool,
+    #[config(dir = "log")]
     pub file: Option<String>,
-    pub filter: Option<Vec<String>>
+    /// `RUST_LOG` style directives, e.g. `app=debug,app_async::db=trace,!sqlx`
+    pub filter: Option<Vec<String>>,
+    /// Rotates log file when it exceeds the size in bytes, zero disables
+    pub rotate_size: u64,
+    /// Rotates log file at the first record of a new day
+    pub rotate_daily: bool,
+    /// Number of rotated files to keep
+    pub rotate_keep: usize,
+    /// Compresses rotated files with gzip (std only)
+    pub rotate_compress: bool,
+    /// Sinks, `file` if file is set or `stderr` by default
+    pub sinks: Option<Vec<String>>,
+    /// Size of background writer buffer in records, zero writes synchronously
+    /// (std only)
+    pub buffer: usize,
+    pub overflow: LogOverflow,
+    /// Identifier of application in journald and syslog
+    pub ident: String,
+    /// Socket of journald native protocol
+    pub journald: String,
+    /// Syslog address, `unix:/dev/log` or `udp:127.0.0.1:514`
+    pub syslog: String,
+    /// Syslog facility code, 1 is user-level messages
+    pub syslog_facility: u8,
+    /// Number of last log lines kept in memory for crash reports, zero
+    /// disables
+    pub ring: usize
 }
 
 impl Default for LogConfig {
@@ -24,76 +139,105 @@ impl Default for LogConfig {
             } else {
                 LevelFilter::Info
             },
+            format: LogFormat::default(),
             color: false,
+            utc: false,
             file: None,
-            filter: None
+            filter: None,
+            rotate_size: 0,
+            rotate_daily: false,
+            rotate_keep: 7,
+            rotate_compress: false,
+            sinks: None,
+            buffer: 0,
+            overflow: LogOverflow::default(),
+            ident: "app".into(),
+            journald: "/run/systemd/journal/socket".into(),
+            syslog: "unix:/dev/log".into(),
+            syslog_facility: 1,
+            ring: 100
         }
     }
 }
 
-impl LoadArgs for LogConfig {
-    fn init_args(&mut self, args: &mut Args) {
-        args.add_options([
-            ("log-level", None, None),
-            ("log-color", None, None),
-            ("log-file", None, None),
-            ("log-filter", None, None)
-        ])
-        .unwrap();
-    }
-
-    fn load_args(&mut self, args: &Args) {
-        #[rustfmt::skip]
-        self.extend(
-            [
-                ("level", args.get("log-level")),
-                ("color", args.get("log-color")),
-                ("file", args.get("log-file")),
-                ("filter", args.get("log-filter")),
-            ]
-            .iter()
-            .map(convert::tuple_result_option_str)
-        );
-        if self.file.eq(&Some(String::default())) {
-            self.file = None;
-        }
+impl LogConfig {
+    /// `--debug` raises level to debug
+    fn load_debug(&mut self, args: &Args) {
         if args.get("debug").unwrap_or_default().is_some()
             && self.level < LevelFilter::Debug
         {
             self.level = LevelFilter::Debug;
         }
     }
-}
 
-impl LoadDirs for LogConfig {
-    fn load_dirs(&mut self, dirs: &Dirs) {
-        if self.file.eq(&Some(String::default())) {
-            self.file = None;
-        }
-        if dirs.log.is_empty() == false
-            && let Some(file) = self.file.as_mut()
-            && file.starts_with("/") == false
-        {
-            file.insert(0, '/');
-            file.insert_str(0, dirs.log.trim_end_matches('/'));
+    /// Parses sinks, defaults to `file` if file is set, otherwise `stderr`
+    pub fn sinks(&self) -> Ok<Vec<LogSinkConfig>> {
+        let sinks = self
+            .sinks
+            .iter()
+            .flatten()
+            .filter(|v| v.trim().is_empty() == false)
+            .map(|v| v.parse::<LogSinkConfig>())
+            .collect::<Result<Vec<_>, _>>()?;
+
+        if sinks.is_empty() == false {
+            return sinks.into_ok();
         }
+
+        let name = if self.file.as_ref().is_some_and(|f| f.is_empty() == false) {
+            "file"
+        } else {
+            "stderr"
+        };
+
+        Vec::from([LogSinkConfig { name: name.into(), level: None, format: None }])
+            .into_ok()
     }
-}
 
-impl LoadEnv for LogConfig {
-    fn load_env(&mut self) {
-        self.extend(
-            [
-                ("level", getenv("LOG_LEVEL")),
-                ("file", getenv("LOG_FILE")),
-                ("color", getenv("LOG_COLOR")),
-                ("filter", getenv("LOG_FILTER"))
-            ]
-            .iter()
-            .map(convert::tuple_option_str)
-        );
-        if self.file.eq(&Some(String::default())) {
-            self.file = None;
+    /// Parses filter directives to default level and levels of target prefixes
+    ///
+    /// Directive `target` uses the global level, `target=level` sets own level,
+    /// `!target` disables target, bare `level` sets default level.
+    /// If any target is allowed, other targets are disabled unless default
+    /// level is set. Directives are sorted from the most specific, unknown
+    /// level of target is an error.
+    pub fn directives(&self) -> Ok<(LevelFilter, Vec<(String, LevelFilter)>)> {
+        let mut default = None;
+        let mut directives = Vec::new();
+        let mut has_allowed = false;
+
+        for value in self.filter.iter().flatten().map(|v| v.trim()) {
+            if value.is_empty() {
+                continue;
+            }
+
+            if let Some(target) = value.strip_prefix("!") {
+                directives.push((target.trim().to_string(), LevelFilter::Off));
+            } else if let Some((target, level)) = value.split_once('=') {
+                let level = match level.trim().parse::<LevelFilter>() {
+                    Ok(level) => level,
+                    Err(_) => {
+                        Err(format!(
+                            "Unknown log level '{}' of filter: {value}",
+                            level.trim()
+                        ))?
+                    },
+                };
+                directives.push((target.trim().to_string(), level));
+                has_allowed = true;
+            } else if let Ok(level) = value.parse::<LevelFilter>() {
+                default = Some(level);
+            } else {
+                directives.push((value.to_string(), self.level));
+                has_allowed = true;
+            }
         }
+
+        directives.sort_by(|a, b| b.0.len().cmp(&a.0.len()));
+
+        let default =
+            default.unwrap_or(if has_allowed { LevelFilter::Off } else { self.level });
+
+        (default, directives).into_ok()
     }
 }
@@ -0,0 +1,134 @@
+#[cfg(feature = "std")]
+use std::cell::{Cell, RefCell};
+
+use {
+    alloc::{borrow::Cow, string::String, vec::Vec},
+    core::{
+        future::Future,
+        pin::Pin,
+        task::{Context, Poll}
+    }
+};
+
+pub type LogField = (Cow<'static, str>, String);
+
+#[cfg(feature = "std")]
+std::thread_local! {
+    static FIELDS: RefCell<Vec<LogField>> = const { RefCell::new(Vec::new()) };
+    static SCOPES: Cell<usize> = const { Cell::new(0) };
+}
+
+/// Key-value fields attached to every log record of the current scope,
+/// such as `request_id`, `user_id` or `task_id`
+///
+/// Fields are kept in thread-local storage (std only). Async scopes set
+/// their fields only while polled, so they follow the task between threads.
+pub struct LogContext;
+
+impl LogContext {
+    /// Runs closure with fields added to the context
+    pub fn sync_scope<R>(
+        fields: impl IntoIterator<Item = (impl Into<Cow<'static, str>>, String)>,
+        f: impl FnOnce() -> R
+    ) -> R {
+        let mut fields = Self::collect(fields);
+        let _scope = Self::enter(&mut fields);
+        f()
+    }
+
+    /// Wraps future, fields are added to the context while it is polled
+    pub fn scope<F: Future>(
+        fields: impl IntoIterator<Item = (impl Into<Cow<'static, str>>, String)>,
+        future: F
+    ) -> LogScope<F> {
+        LogScope { fields: Self::collect(fields), future }
+    }
+
+    /// Adds field to the current scope, does nothing outside of scopes
+    /// so fields don't stay on the thread for unrelated work
+    pub fn insert(key: impl Into<Cow<'static, str>>, value: String) {
+        #[cfg(feature = "std")]
+        if SCOPES.get() > 0 {
+            FIELDS.with_borrow_mut(|fields| fields.push((key.into(), value)));
+        }
+
+        #[cfg(not(feature = "std"))]
+        let _ = (key, value);
+    }
+
+    /// Calls *f* with fields of the current context
+    pub fn with<R>(f: impl FnOnce(&[LogField]) -> R) -> R {
+        #[cfg(feature = "std")]
+        return FIELDS.with_borrow(|fields| f(fields));
+
+        #[cfg(not(feature = "std"))]
+        f(&[])
+    }
+
+    fn collect(
+        fields: impl IntoIterator<Item = (impl Into<Cow<'static, str>>, String)>
+    ) -> Vec<LogField> {
+        fields.into_iter().map(|(k, v)| (k.into(), v)).collect()
+    }
+
+    /// Moves scope fields to the context until the guard is dropped
+    fn enter(fields: &mut Vec<LogField>) -> LogScopeGuard<'_> {
+        #[cfg(feature = "std")]
+        let len = FIELDS.with_borrow_mut(|context| {
+            SCOPES.set(SCOPES.get() + 1);
+            let len = context.len();
+            context.append(fields);
+            len
+        });
+
+        #[cfg(not(feature = "std"))]
+        let len = 0;
+
+        LogScopeGuard { len, fields }
+    }
+
+    /// Moves scope fields back including inserted ones
+    fn leave(len: usize, fields: &mut Vec<LogField>) {
+        #[cfg(feature = "std")]
+        FIELDS.with_borrow_mut(|context| {
+            SCOPES.set(SCOPES.get().saturating_sub(1));
+            fields.extend(context.drain(len.min(context.len())..));
+        });
+
+        #[cfg(not(feature = "std"))]
+        let _ = (len, fields);
+    }
+}
+
+/// Leaves scope on drop, so a panic inside of scope doesn't leave its fields
+/// on the thread
+struct LogScopeGuard<'a> {
+    /// Length of context before the scope
+    len: usize,
+    fields: &'a mut Vec<LogField>
+}
+
+impl Drop for LogScopeGuard<'_> {
+    fn drop(&mut self) {
+        LogContext::leave(self.len, self.fields);
+    }
+}
+
+/// Future with log context fields
+pub struct LogScope<F> {
+    fields: Vec<LogField>,
+    future: F
+}
+
+impl<F: Future> Future for LogScope<F> {
+    type Output = F::Output;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        // SAFETY: future is never moved out of pinned self
+        let this = unsafe { self.get_unchecked_mut() };
+        let future = unsafe { Pin::new_unchecked(&mut this.future) };
+
+        let _scope = LogContext::enter(&mut this.fields);
+        future.poll(cx)
+    }
+}
@@ -0,0 +1,314 @@
+use {
+    crate::prelude::*,
+    alloc::{boxed::Box, ffi::CString, format, string::String},
+    core::{
+        cell::UnsafeCell,
+        ffi::c_int,
+        ptr::null_mut,
+        sync::atomic::{
+            AtomicBool, AtomicI32, AtomicPtr, AtomicU64, AtomicUsize, Ordering
+        }
+    }
+};
+#[cfg(not(feature = "std"))]
+#[allow(unused_imports)]
+use libc_print::std_name::*;
+
+/// Incremented by SIGHUP, log files are reopened when it changes
+static REOPEN: AtomicUsize = AtomicUsize::new(0);
+
+extern "C" fn sighup_handler(_: c_int) {
+    REOPEN.fetch_add(1, Ordering::SeqCst);
+}
+
+/// Mutex of libc, threads wait for the file asleep instead of spinning
+///
+/// Mutex is boxed, so it isn't moved after it's used.
+struct FileLock(Box<UnsafeCell<libc::pthread_mutex_t>>);
+
+// pthread mutex is shared between threads by design
+unsafe impl Send for FileLock {}
+unsafe impl Sync for FileLock {}
+
+impl FileLock {
+    fn new() -> Self {
+        Self(Box::new(UnsafeCell::new(libc::PTHREAD_MUTEX_INITIALIZER)))
+    }
+
+    fn lock(&self) -> FileLockGuard<'_> {
+        unsafe { libc::pthread_mutex_lock(self.0.get()) };
+        FileLockGuard(self)
+    }
+}
+
+/// Unlocks on drop, so a panic while the file is locked doesn't block others
+struct FileLockGuard<'a>(&'a FileLock);
+
+impl Drop for FileLockGuard<'_> {
+    fn drop(&mut self) {
+        unsafe { libc::pthread_mutex_unlock(self.0.0.get()) };
+    }
+}
+
+/// Log file with rotation by size and day
+///
+/// File is reopened on SIGHUP, so it can be rotated by external tools like
+/// logrotate. Writes, flushes and closing are serialized by a mutex.
+pub struct LogFile {
+    path: String,
+    rotate_size: u64,
+    rotate_daily: bool,
+    rotate_keep: usize,
+    rotate_compress: bool,
+    file: AtomicPtr<libc::FILE>,
+    size: AtomicU64,
+    day: AtomicI32,
+    reopen: AtomicUsize,
+    lock: FileLock,
+    /// Closed by `close()`, writes are skipped until `reopen()`
+    closed: AtomicBool,
+    /// Rotated file is compressed, next rotation waits for it
+    compressing: AtomicBool
+}
+
+impl Drop for LogFile {
+    fn drop(&mut self) {
+        self.close();
+    }
+}
+
+impl LogFile {
+    pub fn open(path: &str, config: &LogConfig) -> Ok<Self> {
+        static SIGHUP: AtomicBool = AtomicBool::new(false);
+
+        if SIGHUP.swap(true, Ordering::SeqCst) == false {
+            unsafe {
+                libc::signal(
+                    libc::SIGHUP,
+                    sighup_handler as extern "C" fn(c_int) as libc::sighandler_t
+                )
+            };
+        }
+
+        #[cfg(not(feature = "std"))]
+        if config.rotate_compress {
+            Err("Compression of rotated log files requires std")?;
+        }
+
+        Dirs::mkdir(Dirs::dirname(path))?;
+
+        let this = Self {
+            path: path.into(),
+            rotate_size: config.rotate_size,
+            rotate_daily: config.rotate_daily,
+            rotate_keep: config.rotate_keep,
+            rotate_compress: config.rotate_compress,
+            file: AtomicPtr::new(null_mut()),
+            size: AtomicU64::new(0),
+            day: AtomicI32::new(Self::today()),
+            reopen: AtomicUsize::new(REOPEN.load(Ordering::SeqCst)),
+            lock: FileLock::new(),
+            closed: AtomicBool::new(false),
+            compressing: AtomicBool::new(false)
+        };
+        this.open_file()?;
+
+        this.into_ok()
+    }
+
+    #[inline]
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    /// Writes line to the file, rotates it before if needed
+    ///
+    /// Rotated file is compressed after unlocking, so other threads aren't
+    /// blocked by compression.
+    pub fn write(&self, out: &str) {
+        let Ok(c_out) = CString::new(out) else {
+            return;
+        };
+
+        let lock = self.lock.lock();
+        if self.closed.load(Ordering::SeqCst) {
+            return;
+        }
+
+        let mut rotated = None;
+        let reopen = REOPEN.load(Ordering::SeqCst);
+        if self.reopen.swap(reopen, Ordering::SeqCst) != reopen {
+            self.open_file()
+                .unwrap_or_else(|e| eprintln!("Could not reopen log file: {e}"));
+        } else if self.is_rotate_needed(out.len() as u64) {
+            rotated = self.rotate_files().unwrap_or_else(|e| {
+                eprintln!("Could not rotate log file: {e}");
+                None
+            });
+        }
+
+        let file = self.file.load(Ordering::SeqCst);
+        if file.is_null() == false {
+            unsafe { libc::fputs(c_out.as_ptr(), file) };
+            self.size.fetch_add(out.len() as u64, Ordering::SeqCst);
+        }
+
+        drop(lock);
+
+        if let Some(rotated) = rotated {
+            self.compress_rotated(&rotated)
+                .unwrap_or_else(|e| eprintln!("Could not compress log file: {e}"));
+        }
+    }
+
+    pub fn flush(&self) {
+        let _lock = self.lock.lock();
+        let file = self.file.load(Ordering::SeqCst);
+        if file.is_null() == false {
+            unsafe { libc::fflush(file) };
+        }
+    }
+
+    /// Closes the file, SIGHUP doesn't open it again
+    pub fn close(&self) {
+        let _lock = self.lock.lock();
+        self.closed.store(true, Ordering::SeqCst);
+        self.close_file();
+    }
+
+    /// Closes and opens the file again, i.e. after external rotation
+    pub fn reopen(&self) -> Void {
+        let _lock = self.lock.lock();
+        self.closed.store(false, Ordering::SeqCst);
+        self.open_file()
+    }
+
+    fn close_file(&self) {
+        let file = self.file.swap(null_mut(), Ordering::SeqCst);
+        if file.is_null() == false {
+            unsafe { libc::fclose(file) };
+        }
+    }
+
+    fn open_file(&self) -> Void {
+        self.close_file();
+
+        let c_path = CString::new(self.path.as_str())?;
+        let file = unsafe { libc::fopen(c_path.as_ptr(), c"a+".as_ptr()) };
+        if file.is_null() {
+            Err(format!("Could not open log file: {}", self.path))?;
+        }
+
+        let size = unsafe {
+            libc::fseek(file, 0, libc::SEEK_END);
+            libc::ftell(file)
+        };
+        self.size.store(size.max(0) as u64, Ordering::SeqCst);
+        self.file.store(file, Ordering::SeqCst);
+
+        ok()
+    }
+
+    /// Shifts rotated files `{path}.1` -> `{path}.2` and moves current file to
+    /// `{path}.1`
+    pub fn rotate(&self) -> Void {
+        let lock = self.lock.lock();
+        let rotated = self.rotate_files()?;
+        drop(lock);
+
+        if let Some(rotated) = rotated {
+            self.compress_rotated(&rotated)?;
+        }
+
+        ok()
+    }
+
+    /// Renames files of rotation, returns rotated file if it's compressed
+    fn rotate_files(&self) -> Ok<Option<String>> {
+        self.close_file();
+
+        for ext in ["", ".gz"] {
+            Self::remove(&format!("{}.{}{ext}", self.path, self.rotate_keep.max(1)));
+            for n in (1..self.rotate_keep).rev() {
+                Self::rename(
+                    &format!("{}.{n}{ext}", self.path),
+                    &format!("{}.{}{ext}", self.path, n + 1)
+                );
+            }
+        }
+
+        let mut rotated = None;
+        if self.rotate_keep == 0 {
+            Self::remove(&self.path);
+        } else {
+            let path = format!("{}.1", self.path);
+            Self::rename(&self.path, &path);
+            if self.rotate_compress {
+                self.compressing.store(true, Ordering::SeqCst);
+                rotated = Some(path);
+            }
+        }
+
+        self.day.store(Self::today(), Ordering::SeqCst);
+        self.open_file()?;
+
+        rotated.into_ok()
+    }
+
+    fn compress_rotated(&self, path: &str) -> Void {
+        let result = Self::compress(path);
+        self.compressing.store(false, Ordering::SeqCst);
+        result
+    }
+
+    /// Rotation is postponed while the previous rotated file is compressed
+    fn is_rotate_needed(&self, len: u64) -> bool {
+        let size = self.size.load(Ordering::SeqCst);
+
+        self.compressing.load(Ordering::SeqCst) == false
+            && ((self.rotate_size > 0 && size > 0 && size + len > self.rotate_size)
+                || (self.rotate_daily
+                    && self.day.load(Ordering::SeqCst) != Self::today()))
+    }
+
+    #[cfg(feature = "std")]
+    fn compress(path: &str) -> Void {
+        use {
+            flate2::{Compression, write::GzEncoder},
+            std::{fs::File, io}
+        };
+
+        let mut src = File::open(path)?;
+        let mut encoder =
+            GzEncoder::new(File::create(format!("{path}.gz"))?, Compression::default());
+        io::copy(&mut src, &mut encoder)?;
+        encoder.finish()?;
+        std::fs::remove_file(path)?;
+
+        ok()
+    }
+
+    #[cfg(not(feature = "std"))]
+    fn compress(_path: &str) -> Void {
+        // Compression is rejected by `open()` without std
+        ok()
+    }
+
+    fn rename(from: &str, to: &str) {
+        if let (Ok(from), Ok(to)) = (CString::new(from), CString::new(to)) {
+            unsafe { libc::rename(from.as_ptr(), to.as_ptr()) };
+        }
+    }
+
+    fn remove(path: &str) {
+        if let Ok(path) = CString::new(path) {
+            unsafe { libc::unlink(path.as_ptr()) };
+        }
+    }
+
+    /// Day in the configured timezone as `year * 1000 + day of year`
+    fn today() -> i32 {
+        let time = Time::now();
+        time.date().0 as i32 * 1000 + time.ordinal() as i32
+    }
+}
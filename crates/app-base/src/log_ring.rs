@@ -0,0 +1,53 @@
+use {
+    crate::spin_lock::SpinLock,
+    alloc::{collections::VecDeque, string::String, vec::Vec},
+    core::sync::atomic::{AtomicUsize, Ordering}
+};
+
+/// Last log lines kept in memory, i.e. for crash reports
+#[derive(Default)]
+pub struct LogRing {
+    lines: SpinLock<VecDeque<String>>,
+    capacity: AtomicUsize
+}
+
+impl LogRing {
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        self.capacity.load(Ordering::Relaxed)
+    }
+
+    /// Sets max number of lines, zero disables the ring
+    pub fn set_capacity(&self, capacity: usize) {
+        self.capacity.store(capacity, Ordering::Relaxed);
+        self.lines.with(|lines| {
+            while lines.len() > capacity {
+                lines.pop_front();
+            }
+        });
+    }
+
+    /// Adds line, the oldest line is removed if the ring is full
+    pub fn push(&self, line: &str) {
+        let capacity = self.capacity();
+        if capacity == 0 {
+            return;
+        }
+
+        self.lines.with(|lines| {
+            while lines.len() >= capacity {
+                lines.pop_front();
+            }
+            lines.push_back(line.trim_end().into());
+        });
+    }
+
+    /// Lines from the oldest
+    pub fn lines(&self) -> Vec<String> {
+        self.lines.with(|lines| lines.iter().cloned().collect())
+    }
+
+    pub fn clear(&self) {
+        self.lines.with(|lines| lines.clear());
+    }
+}
@@ -0,0 +1,230 @@
+use {
+    crate::prelude::*,
+    alloc::{boxed::Box, format, string::String, vec::Vec},
+    log::LevelFilter
+};
+#[cfg(not(feature = "std"))]
+#[allow(unused_imports)]
+use libc_print::std_name::*;
+#[cfg(feature = "std")]
+use {
+    alloc::sync::Arc,
+    core::sync::atomic::{AtomicU64, Ordering},
+    std::{
+        sync::{
+            Mutex, RwLock,
+            mpsc::{SyncSender, TrySendError, sync_channel}
+        },
+        thread::{JoinHandle, ThreadId}
+    }
+};
+
+/// Destination of formatted log records
+pub trait LogSink: Send + Sync {
+    fn write(&self, out: &[u8]);
+
+    /// Own encoding of record, otherwise record is formatted by `LogFormat`
+    fn format(&self, _record: &log::Record) -> Option<Vec<u8>> {
+        None
+    }
+
+    fn flush(&self) {}
+
+    fn close(&self) {}
+}
+
+/// Standard error output
+pub struct StderrSink;
+
+impl LogSink for StderrSink {
+    fn write(&self, out: &[u8]) {
+        eprint!("{}", String::from_utf8_lossy(out));
+    }
+}
+
+impl LogSink for LogFile {
+    fn write(&self, out: &[u8]) {
+        LogFile::write(self, &String::from_utf8_lossy(out));
+    }
+
+    fn flush(&self) {
+        LogFile::flush(self);
+    }
+
+    fn close(&self) {
+        LogFile::close(self);
+    }
+}
+
+/// Sink with its own level and format
+pub struct LogSinkEntry {
+    pub name: String,
+    pub level: LevelFilter,
+    pub format: LogFormat,
+    pub color: bool,
+    pub sink: Box<dyn LogSink>
+}
+
+impl LogSinkEntry {
+    /// Creates sinks by configuration
+    pub fn from_config(config: &LogConfig) -> Ok<Vec<Self>> {
+        let mut sinks = Vec::new();
+
+        for sink_config in config.sinks()? {
+            let (sink, color): (Box<dyn LogSink>, bool) = match sink_config.name.as_str()
+            {
+                "stderr" => (Box::new(StderrSink), config.color),
+                "file" => {
+                    let Some(path) =
+                        config.file.as_ref().filter(|f| f.is_empty() == false)
+                    else {
+                        return Err("Log sink 'file' requires log.file".into());
+                    };
+                    (Box::new(LogFile::open(path, config)?), false)
+                },
+                "journald" => (Box::new(JournaldSink::new(config)?), false),
+                "syslog" => (Box::new(SyslogSink::new(config)?), false),
+                name => Err(format!("Unknown log sink: {name}"))?
+            };
+
+            sinks.push(Self {
+                name: sink_config.name,
+                level: sink_config.level.unwrap_or(LevelFilter::Trace),
+                format: sink_config.format.unwrap_or(config.format),
+                color,
+                sink
+            });
+        }
+
+        sinks.into_ok()
+    }
+}
+
+#[cfg(feature = "std")]
+enum LogMessage {
+    /// Formatted records by index of sink
+    Write(Vec<(usize, Vec<u8>)>),
+    Flush(SyncSender<()>)
+}
+
+/// Background thread writing log records to sinks
+#[cfg(feature = "std")]
+pub struct LogWriter {
+    sender: RwLock<Option<SyncSender<LogMessage>>>,
+    thread: Mutex<Option<JoinHandle<()>>>,
+    thread_id: ThreadId,
+    overflow: LogOverflow,
+    dropped: AtomicU64
+}
+
+#[cfg(feature = "std")]
+impl LogWriter {
+    pub fn start(
+        sinks: Arc<Vec<LogSinkEntry>>,
+        buffer: usize,
+        overflow: LogOverflow
+    ) -> Ok<Self> {
+        let (sender, receiver) = sync_channel::<LogMessage>(buffer);
+
+        let thread = std::thread::Builder::new()
+            .name("log-writer".into())
+            .spawn(move || {
+                for message in receiver {
+                    match message {
+                        LogMessage::Write(records) => {
+                            for (n, out) in records {
+                                sinks[n].sink.write(&out);
+                            }
+                        },
+                        LogMessage::Flush(done) => {
+                            sinks.iter().for_each(|s| s.sink.flush());
+                            let _ = done.send(());
+                        }
+                    }
+                }
+                sinks.iter().for_each(|s| s.sink.flush());
+            })?;
+
+        Self {
+            sender: Some(sender).into(),
+            thread_id: thread.thread().id(),
+            thread: Some(thread).into(),
+            overflow,
+            dropped: AtomicU64::new(0)
+        }
+        .into_ok()
+    }
+
+    /// Sends records to the writer, returns them back if the writer is stopped
+    pub fn send(&self, records: Vec<(usize, Vec<u8>)>) -> Option<Vec<(usize, Vec<u8>)>> {
+        let sender = self.sender.read().unwrap_or_else(|e| e.into_inner());
+        let Some(sender) = sender.as_ref() else {
+            return Some(records);
+        };
+
+        match self.overflow {
+            LogOverflow::Block => {
+                sender.send(LogMessage::Write(records)).err().map(|e| {
+                    match e.0 {
+                        LogMessage::Write(records) => records,
+                        LogMessage::Flush(..) => Vec::new()
+                    }
+                })
+            },
+            LogOverflow::Drop => {
+                match sender.try_send(LogMessage::Write(records)) {
+                    Ok(..) => None,
+                    Err(TrySendError::Full(..)) => {
+                        self.dropped.fetch_add(1, Ordering::Relaxed);
+                        None
+                    },
+                    Err(TrySendError::Disconnected(LogMessage::Write(records))) => {
+                        Some(records)
+                    },
+                    Err(TrySendError::Disconnected(..)) => None
+                }
+            },
+        }
+    }
+
+    /// Waits until all sent records are written
+    pub fn flush(&self) {
+        // The writer can't wait for itself, i.e. in panic hook of a sink
+        if std::thread::current().id() == self.thread_id {
+            return;
+        }
+
+        let sender = self.sender.read().unwrap_or_else(|e| e.into_inner());
+        if let Some(sender) = sender.as_ref() {
+            let (done, wait) = sync_channel(1);
+            if sender.send(LogMessage::Flush(done)).is_ok() {
+                let _ = wait.recv();
+            }
+        }
+    }
+
+    /// Number of records dropped on overflow
+    #[inline]
+    pub fn dropped(&self) -> u64 {
+        self.dropped.load(Ordering::Relaxed)
+    }
+
+    /// Writes pending records and stops the thread
+    pub fn stop(&self) {
+        self.sender
+            .write()
+            .unwrap_or_else(|e| e.into_inner())
+            .take();
+        let thread = self.thread.lock().unwrap_or_else(|e| e.into_inner()).take();
+        if let Some(thread) = thread {
+            let _ = thread.join();
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl Drop for LogWriter {
+    fn drop(&mut self) {
+        self.stop();
+    }
+}
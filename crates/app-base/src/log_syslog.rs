@@ -0,0 +1,326 @@
+use {
+    crate::prelude::*,
+    alloc::{ffi::CString, format, string::String, vec::Vec},
+    core::{
+        ffi::c_int,
+        mem::{size_of, zeroed},
+        str::FromStr
+    },
+    log::{Level, kv}
+};
+#[cfg(not(feature = "std"))]
+#[allow(unused_imports)]
+use libc_print::std_name::*;
+
+/// Syslog severity of log level
+fn severity(level: Level) -> u8 {
+    match level {
+        Level::Error => 3,
+        Level::Warn => 4,
+        Level::Info => 6,
+        Level::Debug | Level::Trace => 7
+    }
+}
+
+/// Datagram socket with fixed destination address
+struct Datagram {
+    fd: c_int,
+    addr: libc::sockaddr_storage,
+    addr_len: libc::socklen_t
+}
+
+unsafe impl Send for Datagram {}
+unsafe impl Sync for Datagram {}
+
+impl Drop for Datagram {
+    fn drop(&mut self) {
+        unsafe { libc::close(self.fd) };
+    }
+}
+
+impl Datagram {
+    /// Local socket by path
+    fn unix(path: &str) -> Ok<Self> {
+        let mut addr: libc::sockaddr_un = unsafe { zeroed() };
+        if path.len() >= addr.sun_path.len() {
+            Err(format!("Socket path is too long: {path}"))?;
+        }
+        addr.sun_family = libc::AF_UNIX as libc::sa_family_t;
+        addr.sun_path
+            .iter_mut()
+            .zip(path.bytes())
+            .for_each(|(dst, src)| *dst = src as _);
+
+        Self::new(libc::AF_UNIX, &addr, size_of::<libc::sockaddr_un>())
+    }
+
+    /// UDP socket by `ipv4:port`
+    fn udp(address: &str) -> Ok<Self> {
+        let Some((host, port)) = address.rsplit_once(':') else {
+            return Err(format!("Invalid UDP address: {address}").into());
+        };
+        let port = u16::from_str(port).map_err(|e| format!("{e}: {address}"))?;
+        let host = CString::new(host)?;
+
+        let mut addr: libc::sockaddr_in = unsafe { zeroed() };
+        addr.sin_family = libc::AF_INET as libc::sa_family_t;
+        addr.sin_port = port.to_be();
+        if unsafe {
+            libc::inet_pton(
+                libc::AF_INET,
+                host.as_ptr(),
+                (&mut addr.sin_addr as *mut _).cast()
+            )
+        } != 1
+        {
+            Err(format!("Invalid IPv4 address: {address}"))?;
+        }
+
+        Self::new(libc::AF_INET, &addr, size_of::<libc::sockaddr_in>())
+    }
+
+    fn new<T>(domain: c_int, addr: &T, addr_len: usize) -> Ok<Self> {
+        let fd =
+            unsafe { libc::socket(domain, libc::SOCK_DGRAM | libc::SOCK_CLOEXEC, 0) };
+        if fd < 0 {
+            Err("Could not create log socket")?;
+        }
+
+        let mut this = Self { fd, addr: unsafe { zeroed() }, addr_len: addr_len as _ };
+        unsafe {
+            core::ptr::copy_nonoverlapping(
+                (addr as *const T).cast::<u8>(),
+                (&mut this.addr as *mut libc::sockaddr_storage).cast::<u8>(),
+                addr_len
+            )
+        };
+
+        this.into_ok()
+    }
+
+    fn send(&self, data: &[u8]) {
+        unsafe {
+            libc::sendto(
+                self.fd,
+                data.as_ptr().cast(),
+                data.len(),
+                libc::MSG_NOSIGNAL,
+                (&self.addr as *const libc::sockaddr_storage).cast(),
+                self.addr_len
+            )
+        };
+    }
+}
+
+/// Sink of systemd journal native protocol
+///
+/// Sends `PRIORITY`, `SYSLOG_IDENTIFIER`, `CODE_FILE`, `CODE_LINE` and other
+/// fields, key-values of record are sent as uppercase fields.
+pub struct JournaldSink {
+    socket: Datagram,
+    ident: String
+}
+
+impl JournaldSink {
+    pub fn new(config: &LogConfig) -> Ok<Self> {
+        Self {
+            socket: Datagram::unix(&config.journald)?,
+            ident: config.ident.clone()
+        }
+        .into_ok()
+    }
+
+    /// Appends field, values with new lines are length prefixed
+    fn field(out: &mut Vec<u8>, name: &str, value: &str) {
+        out.extend_from_slice(name.as_bytes());
+        if value.contains('\n') {
+            out.push(b'\n');
+            out.extend_from_slice(&(value.len() as u64).to_le_bytes());
+        } else {
+            out.push(b'=');
+        }
+        out.extend_from_slice(value.as_bytes());
+        out.push(b'\n');
+    }
+
+    /// Converts key to valid field name: uppercase letters, digits and
+    /// underscore
+    fn field_name(key: &str) -> Option<String> {
+        let name: String = key
+            .chars()
+            .map(|c| {
+                if c.is_ascii_alphanumeric() {
+                    c.to_ascii_uppercase()
+                } else {
+                    '_'
+                }
+            })
+            .collect();
+
+        name.starts_with(|c: char| c.is_ascii_uppercase())
+            .then_some(name)
+    }
+}
+
+impl LogSink for JournaldSink {
+    fn write(&self, out: &[u8]) {
+        self.socket.send(out);
+    }
+
+    fn format(&self, record: &log::Record) -> Option<Vec<u8>> {
+        let mut out = Vec::with_capacity(256);
+
+        Self::field(&mut out, "MESSAGE", &format!("{}", record.args()));
+        Self::field(
+            &mut out,
+            "PRIORITY",
+            &format!("{}", severity(record.level()))
+        );
+        Self::field(&mut out, "SYSLOG_IDENTIFIER", &self.ident);
+        Self::field(
+            &mut out,
+            "SYSLOG_PID",
+            &format!("{}", unsafe { libc::getpid() })
+        );
+        Self::field(&mut out, "TID", &format!("{}", unsafe { libc::gettid() }));
+        Self::field(&mut out, "TARGET", record.target());
+        if let Some(file) = record.file() {
+            Self::field(&mut out, "CODE_FILE", file);
+        }
+        if let Some(line) = record.line() {
+            Self::field(&mut out, "CODE_LINE", &format!("{line}"));
+        }
+        if let Some(module) = record.module_path() {
+            Self::field(&mut out, "CODE_MODULE", module);
+        }
+
+        LogContext::with(|fields| {
+            for (key, value) in fields {
+                if let Some(name) = Self::field_name(key) {
+                    Self::field(&mut out, &name, value);
+                }
+            }
+        });
+        let _ = record.key_values().visit(&mut KeyValues(
+            |key: kv::Key, value: kv::Value| {
+                if let Some(name) = Self::field_name(key.as_str()) {
+                    Self::field(&mut out, &name, &format!("{value}"));
+                }
+            }
+        ));
+
+        out.into()
+    }
+}
+
+/// RFC 5424 syslog sink over unix socket or UDP
+pub struct SyslogSink {
+    socket: Datagram,
+    ident: String,
+    hostname: String,
+    facility: u8
+}
+
+impl SyslogSink {
+    pub fn new(config: &LogConfig) -> Ok<Self> {
+        let socket = match config.syslog.split_once(':') {
+            Some(("unix", path)) => Datagram::unix(path)?,
+            Some(("udp", address)) => Datagram::udp(address)?,
+            _ => Err(format!("Invalid syslog address: {}", config.syslog))?
+        };
+
+        Self {
+            socket,
+            ident: Self::header_value(&config.ident, 48),
+            hostname: Self::header_value(&Self::hostname(), 255),
+            facility: config.syslog_facility.min(23)
+        }
+        .into_ok()
+    }
+
+    fn hostname() -> String {
+        let mut buf = [0u8; 256];
+        if unsafe { libc::gethostname(buf.as_mut_ptr().cast(), buf.len()) } != 0 {
+            return String::new();
+        }
+        let len = buf.iter().position(|&c| c == 0).unwrap_or(buf.len());
+
+        String::from_utf8_lossy(&buf[..len]).into_owned()
+    }
+
+    /// Printable ASCII value of header or `-`
+    fn header_value(value: &str, max_len: usize) -> String {
+        let value: String = value
+            .chars()
+            .filter(|c| c.is_ascii_graphic())
+            .take(max_len)
+            .collect();
+
+        if value.is_empty() { "-".into() } else { value }
+    }
+
+    /// Escapes `"`, `\` and `]` in structured data value
+    fn param_value(value: &str) -> String {
+        let mut out = String::with_capacity(value.len());
+        for c in value.chars() {
+            if matches!(c, '"' | '\\' | ']') {
+                out.push('\\');
+            }
+            out.push(c);
+        }
+        out
+    }
+
+    /// Appends structured data parameter
+    fn param(params: &mut String, key: &str, value: &str) {
+        let name = Self::header_value(key, 32).replace(['=', ']', '"'], "_");
+        params.push_str(&format!(" {name}=\"{}\"", Self::param_value(value)));
+    }
+
+    /// `+0300` to `+03:00` as required by RFC 3339
+    fn timestamp() -> String {
+        let mut time = Logger::time(c"%FT%T");
+        if time.len() > 2 {
+            time.insert(time.len() - 2, ':');
+        }
+        time
+    }
+}
+
+impl LogSink for SyslogSink {
+    fn write(&self, out: &[u8]) {
+        self.socket.send(out);
+    }
+
+    fn format(&self, record: &log::Record) -> Option<Vec<u8>> {
+        let mut params = format!("target=\"{}\"", Self::param_value(record.target()));
+        if let Some(file) = record.file() {
+            params.push_str(&format!(" file=\"{}\"", Self::param_value(file)));
+        }
+        if let Some(line) = record.line() {
+            params.push_str(&format!(" line=\"{line}\""));
+        }
+        LogContext::with(|fields| {
+            for (key, value) in fields {
+                Self::param(&mut params, key, value);
+            }
+        });
+        let _ = record.key_values().visit(&mut KeyValues(
+            |key: kv::Key, value: kv::Value| {
+                Self::param(&mut params, key.as_str(), &format!("{value}"));
+            }
+        ));
+
+        format!(
+            "<{}>1 {} {} {} {} - [log@32473 {params}] {}",
+            self.facility * 8 + severity(record.level()),
+            Self::timestamp(),
+            self.hostname,
+            self.ident,
+            unsafe { libc::getpid() },
+            record.args()
+        )
+        .into_bytes()
+        .into()
+    }
+}
@@ -12,3 +12,44 @@ macro_rules! type_name_simple {
             .unwrap()
     };
 }
+
+/// Translates message by `I18n` to the language of the current scope
+///
+/// `t!("Hello")`, `t!("Hello, {name}!", name = user)`,
+/// `t!("{n} file", "{n} files", count)` for plural forms with `{n}`,
+/// `t!(lang = "ru"; "Hello")`, `t!(context = "menu"; "Open")` or
+/// `t!(lang = "ru", context = "menu"; "Open")`.
+#[macro_export]
+macro_rules! t {
+    (
+        @ $lang:expr, $context:expr;
+        $id:literal, $plural:literal, $n:expr $(, $k:ident = $v:expr)* $(,)?
+    ) => {{
+        let n = $n;
+        $crate::i18n::I18n::format(
+            $crate::i18n::I18n::translate_plural(&$lang, $context, $id, $plural, n as u64),
+            &[
+                ("n", &n as &dyn ::core::fmt::Display)
+                $(, (stringify!($k), &$v as &dyn ::core::fmt::Display))*
+            ]
+        )
+    }};
+    (@ $lang:expr, $context:expr; $id:literal $(, $k:ident = $v:expr)* $(,)?) => {
+        $crate::i18n::I18n::format(
+            $crate::i18n::I18n::translate(&$lang, $context, $id),
+            &[$((stringify!($k), &$v as &dyn ::core::fmt::Display)),*]
+        )
+    };
+    (lang = $lang:expr, context = $context:expr; $($rest:tt)+) => {
+        $crate::t!(@ $lang, Some($context); $($rest)+)
+    };
+    (lang = $lang:expr; $($rest:tt)+) => {
+        $crate::t!(@ $lang, None; $($rest)+)
+    };
+    (context = $context:expr; $($rest:tt)+) => {
+        $crate::t!(@ $crate::i18n::I18n::language(), Some($context); $($rest)+)
+    };
+    ($($rest:tt)+) => {
+        $crate::t!(@ $crate::i18n::I18n::language(), None; $($rest)+)
+    };
+}
@@ -0,0 +1,417 @@
+use {
+    crate::prelude::*,
+    alloc::{
+        boxed::Box,
+        format,
+        string::{String, ToString},
+        sync::Arc,
+        vec::Vec
+    },
+    core::{
+        fmt::Write,
+        sync::atomic::{AtomicU64, Ordering}
+    }
+};
+
+/// Counter which value only increases
+#[derive(Debug, Clone, Default)]
+pub struct Counter(Arc<AtomicU64>);
+
+impl Counter {
+    #[inline]
+    pub fn inc(&self) {
+        self.inc_by(1);
+    }
+
+    #[inline]
+    pub fn inc_by(&self, value: u64) {
+        self.0.fetch_add(value, Ordering::Relaxed);
+    }
+
+    #[inline]
+    pub fn get(&self) -> u64 {
+        self.0.load(Ordering::Relaxed)
+    }
+}
+
+/// Gauge which value goes up and down
+#[derive(Debug, Clone, Default)]
+pub struct Gauge(Arc<AtomicU64>);
+
+impl Gauge {
+    #[inline]
+    pub fn set(&self, value: f64) {
+        self.0.store(value.to_bits(), Ordering::Relaxed);
+    }
+
+    #[inline]
+    pub fn add(&self, value: f64) {
+        add_f64(&self.0, value);
+    }
+
+    #[inline]
+    pub fn inc(&self) {
+        self.add(1.0);
+    }
+
+    #[inline]
+    pub fn dec(&self) {
+        self.add(-1.0);
+    }
+
+    #[inline]
+    pub fn get(&self) -> f64 {
+        f64::from_bits(self.0.load(Ordering::Relaxed))
+    }
+}
+
+/// Histogram of observed values by buckets of upper bounds
+#[derive(Debug, Clone)]
+pub struct Histogram(Arc<HistogramData>);
+
+#[derive(Debug)]
+struct HistogramData {
+    bounds: Box<[f64]>,
+    counts: Box<[AtomicU64]>,
+    sum: AtomicU64,
+    count: AtomicU64
+}
+
+impl Default for Histogram {
+    fn default() -> Self {
+        Self::new(&Self::DEFAULT_BUCKETS)
+    }
+}
+
+impl Histogram {
+    /// Buckets of durations in seconds
+    pub const DEFAULT_BUCKETS: [f64; 11] = [
+        0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0
+    ];
+
+    /// Bounds are sorted, `+Inf` bucket is always added
+    pub fn new(bounds: &[f64]) -> Self {
+        let mut bounds: Vec<_> =
+            bounds.iter().copied().filter(|b| b.is_finite()).collect();
+        bounds.sort_by(f64::total_cmp);
+        bounds.dedup();
+
+        Self(
+            HistogramData {
+                counts: bounds.iter().map(|_| AtomicU64::new(0)).collect(),
+                bounds: bounds.into(),
+                sum: AtomicU64::new(0),
+                count: AtomicU64::new(0)
+            }
+            .into()
+        )
+    }
+
+    pub fn observe(&self, value: f64) {
+        let data = &self.0;
+        if let Some(idx) = data.bounds.iter().position(|b| value <= *b) {
+            data.counts[idx].fetch_add(1, Ordering::Relaxed);
+        }
+        add_f64(&data.sum, value);
+        data.count.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Observes seconds elapsed from *start*
+    #[cfg(feature = "std")]
+    #[inline]
+    pub fn observe_since(&self, start: std::time::Instant) {
+        self.observe(start.elapsed().as_secs_f64());
+    }
+
+    #[inline]
+    pub fn count(&self) -> u64 {
+        self.0.count.load(Ordering::Relaxed)
+    }
+
+    #[inline]
+    pub fn sum(&self) -> f64 {
+        f64::from_bits(self.0.sum.load(Ordering::Relaxed))
+    }
+
+    /// Cumulative counts of buckets without `+Inf`
+    pub fn buckets(&self) -> Vec<(f64, u64)> {
+        let mut total = 0;
+        self.0
+            .bounds
+            .iter()
+            .zip(self.0.counts.iter())
+            .map(|(bound, count)| {
+                total += count.load(Ordering::Relaxed);
+                (*bound, total)
+            })
+            .collect()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MetricKind {
+    Counter,
+    Gauge,
+    Histogram
+}
+
+impl MetricKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Counter => "counter",
+            Self::Gauge => "gauge",
+            Self::Histogram => "histogram"
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum Metric {
+    Counter(Counter),
+    Gauge(Gauge),
+    Histogram(Histogram)
+}
+
+/// Collector updates metrics before rendering, i.e. gauges of memory
+pub type MetricsCollector = Arc<dyn Fn(&Metrics) + Send + Sync>;
+
+struct MetricFamily {
+    help: String,
+    kind: MetricKind,
+    /// Metrics by rendered labels
+    series: IndexMap<String, Metric>
+}
+
+#[derive(Default)]
+struct MetricsData {
+    families: IndexMap<String, MetricFamily>,
+    collectors: Vec<MetricsCollector>
+}
+
+/// Registry of metrics rendered in Prometheus text format
+///
+/// Metrics are registered on the first request and the same handle is
+/// returned for the same name and labels, so handles can be kept by callers.
+#[derive(FromStatic)]
+pub struct Metrics {
+    data: SpinLock<MetricsData>
+}
+
+impl Default for Metrics {
+    fn default() -> Self {
+        let metrics = Self { data: Default::default() };
+        metrics.add_collector(Self::collect_process);
+
+        metrics
+    }
+}
+
+impl Metrics {
+    pub const CONTENT_TYPE: &str = "text/plain; version=0.0.4; charset=utf-8";
+
+    pub fn counter(&self, name: &str, help: &str, labels: &[(&str, &str)]) -> Counter {
+        match self.register(name, help, labels, MetricKind::Counter, || {
+            Metric::Counter(Default::default())
+        }) {
+            Metric::Counter(counter) => counter,
+            _ => unreachable!()
+        }
+    }
+
+    pub fn gauge(&self, name: &str, help: &str, labels: &[(&str, &str)]) -> Gauge {
+        match self.register(name, help, labels, MetricKind::Gauge, || {
+            Metric::Gauge(Default::default())
+        }) {
+            Metric::Gauge(gauge) => gauge,
+            _ => unreachable!()
+        }
+    }
+
+    /// Histogram with *buckets*, `Histogram::DEFAULT_BUCKETS` if empty
+    pub fn histogram(
+        &self,
+        name: &str,
+        help: &str,
+        labels: &[(&str, &str)],
+        buckets: &[f64]
+    ) -> Histogram {
+        match self.register(name, help, labels, MetricKind::Histogram, || {
+            Metric::Histogram(match buckets.is_empty() {
+                true => Histogram::default(),
+                false => Histogram::new(buckets)
+            })
+        }) {
+            Metric::Histogram(histogram) => histogram,
+            _ => unreachable!()
+        }
+    }
+
+    /// Removes all series of metric *name*
+    pub fn unregister(&self, name: &str) {
+        self.data.with(|data| data.families.shift_remove(name));
+    }
+
+    pub fn add_collector(&self, collector: impl Fn(&Metrics) + Send + Sync + 'static) {
+        self.data
+            .with(|data| data.collectors.push(Arc::new(collector)));
+    }
+
+    /// Metrics in Prometheus text format
+    pub fn render(&self) -> String {
+        for collector in self.data.with(|data| data.collectors.clone()) {
+            collector(self);
+        }
+
+        self.data.with(|data| {
+            let mut out = String::new();
+
+            for (name, family) in data.families.iter() {
+                let help = family.help.replace('\\', "\\\\").replace('\n', "\\n");
+                let _ = writeln!(out, "# HELP {name} {help}");
+                let _ = writeln!(out, "# TYPE {name} {}", family.kind.as_str());
+
+                for (labels, metric) in family.series.iter() {
+                    match metric {
+                        Metric::Counter(counter) => {
+                            Self::sample(&mut out, name, labels, None, counter.get())
+                        },
+                        Metric::Gauge(gauge) => {
+                            Self::sample(&mut out, name, labels, None, value(gauge.get()))
+                        },
+                        Metric::Histogram(histogram) => {
+                            let bucket = format!("{name}_bucket");
+                            for (bound, count) in histogram.buckets() {
+                                let le = value(bound);
+                                Self::sample(&mut out, &bucket, labels, Some(&le), count);
+                            }
+                            let count = histogram.count();
+                            Self::sample(&mut out, &bucket, labels, Some("+Inf"), count);
+                            let sum = value(histogram.sum());
+                            Self::sample(
+                                &mut out,
+                                &format!("{name}_sum"),
+                                labels,
+                                None,
+                                sum
+                            );
+                            Self::sample(
+                                &mut out,
+                                &format!("{name}_count"),
+                                labels,
+                                None,
+                                count
+                            );
+                        }
+                    }
+                }
+            }
+
+            out
+        })
+    }
+
+    /// Updates gauges of process memory
+    pub fn collect_process(&self) {
+        #[cfg(feature = "std")]
+        if let Some(usage) = memory_stats::memory_stats() {
+            self.gauge(
+                "process_resident_memory_bytes",
+                "Resident memory size in bytes",
+                &[]
+            )
+            .set(usage.physical_mem as f64);
+            self.gauge(
+                "process_virtual_memory_bytes",
+                "Virtual memory size in bytes",
+                &[]
+            )
+            .set(usage.virtual_mem as f64);
+        }
+    }
+
+    fn register(
+        &self,
+        name: &str,
+        help: &str,
+        labels: &[(&str, &str)],
+        kind: MetricKind,
+        new: impl FnOnce() -> Metric
+    ) -> Metric {
+        let labels = labels
+            .iter()
+            .map(|(k, v)| {
+                let v = v
+                    .replace('\\', "\\\\")
+                    .replace('"', "\\\"")
+                    .replace('\n', "\\n");
+                format!("{k}=\"{v}\"")
+            })
+            .collect::<Vec<_>>()
+            .join(",");
+
+        let metric = self.data.with(|data| {
+            let family = data.families.entry(name.into()).or_insert_with(|| {
+                MetricFamily { help: help.into(), kind, series: Default::default() }
+            });
+
+            (family.kind == kind)
+                .then(|| family.series.entry(labels).or_insert_with(new).clone())
+                .ok_or(family.kind)
+        });
+
+        metric.unwrap_or_else(|registered| {
+            log::warn!(
+                "Metric '{name}' is already registered as {}",
+                registered.as_str()
+            );
+            // not registered metric, so it is not rendered
+            match kind {
+                MetricKind::Counter => Metric::Counter(Default::default()),
+                MetricKind::Gauge => Metric::Gauge(Default::default()),
+                MetricKind::Histogram => Metric::Histogram(Default::default())
+            }
+        })
+    }
+
+    fn sample(
+        out: &mut String,
+        name: &str,
+        labels: &str,
+        le: Option<&str>,
+        value: impl ToString
+    ) {
+        out.push_str(name);
+        match (labels.is_empty(), le) {
+            (true, None) => {},
+            (false, None) => {
+                let _ = write!(out, "{{{labels}}}");
+            },
+            (true, Some(le)) => {
+                let _ = write!(out, "{{le=\"{le}\"}}");
+            },
+            (false, Some(le)) => {
+                let _ = write!(out, "{{{labels},le=\"{le}\"}}");
+            }
+        }
+        out.push(' ');
+        out.push_str(&value.to_string());
+        out.push('\n');
+    }
+}
+
+/// Value in Prometheus format
+fn value(value: f64) -> String {
+    if value.is_nan() {
+        "NaN".into()
+    } else if value.is_infinite() {
+        if value > 0.0 { "+Inf" } else { "-Inf" }.into()
+    } else {
+        value.to_string()
+    }
+}
+
+fn add_f64(atomic: &AtomicU64, value: f64) {
+    let _ = atomic.fetch_update(Ordering::Relaxed, Ordering::Relaxed, |bits| {
+        Some((f64::from_bits(bits) + value).to_bits())
+    });
+}
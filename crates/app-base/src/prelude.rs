@@ -2,6 +2,8 @@
 pub use {crate::no_std::*, libc_print::std_name::*};
 
 pub use crate::{
-    app::*, args::*, base::*, base_config::*, convert, di::*, dirs::*, env::*, filters,
-    ini::*, log::*, log_config::*, macros::*, mem_stats::*
+    app::*, args::*, base::*, base_config::*, convert, crash_report::*, di::*, dirs::*,
+    dotenv::*, env::*, extend::*, fields::*, filters, i18n::*, ini::*, log::*,
+    log_config::*, log_context::*, log_file::*, log_ring::*, log_sink::*, log_syslog::*,
+    macros::*, mem_stats::*, metrics::*, spin_lock::*, time::*
 };
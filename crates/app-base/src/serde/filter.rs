@@ -0,0 +1,56 @@
+use {
+    crate::filters::Filter,
+    core::ops::{Deref, DerefMut},
+    serde::{Deserialize, Deserializer}
+};
+
+/// Deserializes value and applies its filters:
+/// `#[serde(deserialize_with = "filtered")]`
+pub fn filtered<'de, D, T>(d: D) -> Result<T, D::Error>
+where
+    D: Deserializer<'de>,
+    T: Deserialize<'de> + Filter
+{
+    let mut value = T::deserialize(d)?;
+    value.filter();
+    Ok(value)
+}
+
+/// Value filtered during deserialization, i.e. `web::Form<Filtered<UserForm>>`
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Filtered<T>(pub T);
+
+impl<T> Filtered<T> {
+    #[inline]
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<'de, T> Deserialize<'de> for Filtered<T>
+where
+    T: Deserialize<'de> + Filter
+{
+    fn deserialize<D>(d: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>
+    {
+        filtered(d).map(Self)
+    }
+}
+
+impl<T> Deref for Filtered<T> {
+    type Target = T;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<T> DerefMut for Filtered<T> {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
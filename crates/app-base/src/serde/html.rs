@@ -0,0 +1,46 @@
+use {
+    crate::filters::{
+        sanitize_html as filter_sanitize_html, strip_html as filter_strip_html
+    },
+    alloc::string::String,
+    serde::{Deserialize, Deserializer}
+};
+
+/// Sanitizes HTML by the global `HtmlSanitizer`
+pub fn sanitize_html<'de, D>(d: D) -> Result<String, D::Error>
+where
+    D: Deserializer<'de>
+{
+    let s: String = Deserialize::deserialize(d)?;
+    Ok(filter_sanitize_html(&s))
+}
+
+pub fn sanitize_html_option<'de, D>(d: D) -> Result<Option<String>, D::Error>
+where
+    D: Deserializer<'de>
+{
+    let s: Option<String> = Deserialize::deserialize(d)?;
+    match s.as_deref().map(filter_sanitize_html) {
+        Some(value) if value.trim().is_empty() == false => Ok(Some(value)),
+        _ => Ok(None)
+    }
+}
+
+pub fn strip_html<'de, D>(d: D) -> Result<String, D::Error>
+where
+    D: Deserializer<'de>
+{
+    let s: String = Deserialize::deserialize(d)?;
+    Ok(filter_strip_html(&s))
+}
+
+pub fn strip_html_option<'de, D>(d: D) -> Result<Option<String>, D::Error>
+where
+    D: Deserializer<'de>
+{
+    let s: Option<String> = Deserialize::deserialize(d)?;
+    match s.as_deref().map(filter_strip_html) {
+        Some(value) if value.trim().is_empty() == false => Ok(Some(value)),
+        _ => Ok(None)
+    }
+}
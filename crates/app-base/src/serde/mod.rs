@@ -1,5 +1,8 @@
+mod filter;
+mod html;
 mod merge;
+mod patch;
 mod string;
 mod phone;
 
-pub use {merge::*, phone::*, string::*};
+pub use {filter::*, html::*, merge::*, patch::*, phone::*, string::*};
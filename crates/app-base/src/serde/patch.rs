@@ -0,0 +1,442 @@
+use {
+    crate::prelude::*,
+    alloc::{borrow::Cow, format, string::String, vec::Vec},
+    core::{error::Error, fmt::Display},
+    serde::{Deserialize, Serialize},
+    serde_json::{Map, Value}
+};
+
+/// Operation of JSON Patch (RFC 6902)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "op", rename_all = "lowercase")]
+pub enum PatchOperation {
+    Add { path: String, value: Value },
+    Remove { path: String },
+    Replace { path: String, value: Value },
+    Move { from: String, path: String },
+    Copy { from: String, path: String },
+    Test { path: String, value: Value }
+}
+
+impl PatchOperation {
+    pub fn path(&self) -> &str {
+        match self {
+            Self::Add { path, .. }
+            | Self::Remove { path }
+            | Self::Replace { path, .. }
+            | Self::Move { path, .. }
+            | Self::Copy { path, .. }
+            | Self::Test { path, .. } => path
+        }
+    }
+}
+
+/// JSON Patch (RFC 6902), it is serialized as array of operations
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct JsonPatch(pub Vec<PatchOperation>);
+
+impl JsonPatch {
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    #[inline]
+    pub fn iter(&self) -> impl Iterator<Item = &PatchOperation> {
+        self.0.iter()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PatchError {
+    InvalidPointer(String),
+    PathNotFound(String),
+    InvalidIndex(String),
+    /// Value is moved into itself
+    InvalidMove(String),
+    TestFailed(String)
+}
+
+impl Error for PatchError {}
+
+impl Display for PatchError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::InvalidPointer(p) => write!(f, "Invalid JSON pointer: '{p}'"),
+            Self::PathNotFound(p) => write!(f, "Path not found: '{p}'"),
+            Self::InvalidIndex(p) => write!(f, "Invalid array index: '{p}'"),
+            Self::InvalidMove(p) => write!(f, "Could not move value into itself: '{p}'"),
+            Self::TestFailed(p) => write!(f, "Test failed: '{p}'")
+        }
+    }
+}
+
+/// Strategy of merging arrays by `merge_patch_with()`
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum ArrayMerge {
+    /// Patch array replaces target array like RFC 7386
+    #[default]
+    Replace,
+    /// Items of patch array are appended
+    Append,
+    /// Objects with equal values of key are merged, others are appended
+    ByKey(Cow<'static, str>)
+}
+
+/// Options of `merge_patch_with()`
+///
+/// Paths of arrays are JSON pointers of object keys without array indexes,
+/// i.e. `/order/items` for items of all orders in `{"order": {"items": []}}`.
+#[derive(Debug, Clone, Default)]
+pub struct MergeOptions {
+    /// Strategy of arrays without own strategy
+    pub arrays: ArrayMerge,
+    pub paths: IndexMap<String, ArrayMerge>
+}
+
+impl MergeOptions {
+    pub fn with_arrays(&mut self, arrays: ArrayMerge) -> &mut Self {
+        self.arrays = arrays;
+        self
+    }
+
+    pub fn with_path(
+        &mut self,
+        path: impl Into<String>,
+        arrays: ArrayMerge
+    ) -> &mut Self {
+        self.paths.insert(path.into(), arrays);
+        self
+    }
+
+    fn arrays(&self, path: &str) -> &ArrayMerge {
+        self.paths.get(path).unwrap_or(&self.arrays)
+    }
+}
+
+/// JSON Merge Patch (RFC 7386) and JSON Patch (RFC 6902) of `serde_json::Value`
+///
+/// Typed values are patched by `BaseFromInto::json_merge_patched()`,
+/// `json_patched()` and `json_diff()`.
+pub trait PatchJson {
+    /// Merges *patch*, `null` deletes key, arrays are replaced
+    fn merge_patch(&mut self, patch: Value);
+
+    /// Merges *patch* with strategies of arrays
+    fn merge_patch_with(&mut self, patch: Value, options: &MergeOptions);
+
+    /// Applies all operations or nothing if one of them fails
+    fn apply_patch(&mut self, patch: &JsonPatch) -> Result<(), PatchError>;
+
+    /// Patch which changes `self` to *other*
+    fn diff_patch(&self, other: &Value) -> JsonPatch;
+}
+
+impl PatchJson for Value {
+    #[inline]
+    fn merge_patch(&mut self, patch: Value) {
+        merge_patch(self, patch, &MergeOptions::default(), &mut String::new());
+    }
+
+    #[inline]
+    fn merge_patch_with(&mut self, patch: Value, options: &MergeOptions) {
+        merge_patch(self, patch, options, &mut String::new());
+    }
+
+    fn apply_patch(&mut self, patch: &JsonPatch) -> Result<(), PatchError> {
+        let mut value = self.clone();
+        for operation in patch.iter() {
+            apply_operation(&mut value, operation)?;
+        }
+        *self = value;
+
+        Ok(())
+    }
+
+    #[inline]
+    fn diff_patch(&self, other: &Value) -> JsonPatch {
+        json_diff(self, other)
+    }
+}
+
+/// Patch which changes *a* to *b*
+pub fn json_diff(a: &Value, b: &Value) -> JsonPatch {
+    let mut patch = JsonPatch::default();
+    diff(a, b, &mut String::new(), &mut patch.0);
+    patch
+}
+
+/// Merge Patch which changes *a* to *b*
+pub fn json_merge_diff(a: &Value, b: &Value) -> Value {
+    match (a, b) {
+        (Value::Object(a), Value::Object(b)) => {
+            let mut patch = Map::new();
+            for key in a.keys().filter(|key| b.contains_key(*key) == false) {
+                patch.insert(key.clone(), Value::Null);
+            }
+            for (key, value) in b {
+                match a.get(key) {
+                    Some(prev) if prev == value => {},
+                    Some(prev) => {
+                        patch.insert(key.clone(), json_merge_diff(prev, value));
+                    },
+                    None => {
+                        patch.insert(key.clone(), value.clone());
+                    }
+                }
+            }
+            Value::Object(patch)
+        },
+        _ => b.clone()
+    }
+}
+
+/// Escapes token of JSON pointer: `~` to `~0` and `/` to `~1`
+pub fn json_pointer_escape(token: &str) -> Cow<'_, str> {
+    if token.contains(['~', '/']) {
+        token.replace('~', "~0").replace('/', "~1").into()
+    } else {
+        token.into()
+    }
+}
+
+fn merge_patch(
+    target: &mut Value,
+    patch: Value,
+    options: &MergeOptions,
+    path: &mut String
+) {
+    let patch = match patch {
+        Value::Object(patch) => patch,
+        Value::Array(patch) if target.is_array() => {
+            let target = target.as_array_mut().unwrap();
+            match options.arrays(path) {
+                ArrayMerge::Replace => *target = patch,
+                ArrayMerge::Append => target.extend(patch),
+                ArrayMerge::ByKey(key) => {
+                    for item in patch {
+                        let found = item.get(key.as_ref()).and_then(|id| {
+                            target.iter_mut().find(|t| t.get(key.as_ref()) == Some(id))
+                        });
+                        match found {
+                            Some(found) => merge_patch(found, item, options, path),
+                            None => target.push(item)
+                        }
+                    }
+                },
+            }
+            return;
+        },
+        patch => {
+            *target = patch;
+            return;
+        }
+    };
+
+    if target.is_object() == false {
+        *target = Value::Object(Map::new());
+    }
+    let object = target.as_object_mut().unwrap();
+
+    for (key, value) in patch {
+        if value.is_null() {
+            object.remove(&key);
+            continue;
+        }
+
+        let len = path.len();
+        path.push('/');
+        path.push_str(&json_pointer_escape(&key));
+        merge_patch(
+            object.entry(key).or_insert(Value::Null),
+            value,
+            options,
+            path
+        );
+        path.truncate(len);
+    }
+}
+
+fn diff(a: &Value, b: &Value, path: &mut String, ops: &mut Vec<PatchOperation>) {
+    match (a, b) {
+        (Value::Object(a), Value::Object(b)) => {
+            for key in a.keys().filter(|key| b.contains_key(*key) == false) {
+                ops.push(PatchOperation::Remove { path: child(path, key) });
+            }
+            for (key, value) in b {
+                match a.get(key) {
+                    Some(prev) => {
+                        let len = path.len();
+                        path.push('/');
+                        path.push_str(&json_pointer_escape(key));
+                        diff(prev, value, path, ops);
+                        path.truncate(len);
+                    },
+                    None => {
+                        ops.push(PatchOperation::Add {
+                            path: child(path, key),
+                            value: value.clone()
+                        })
+                    },
+                }
+            }
+        },
+        (Value::Array(a), Value::Array(b)) => {
+            let common = a.len().min(b.len());
+            for idx in 0..common {
+                let len = path.len();
+                path.push_str(&format!("/{idx}"));
+                diff(&a[idx], &b[idx], path, ops);
+                path.truncate(len);
+            }
+            // from the end, so indexes are not shifted
+            for idx in (common..a.len()).rev() {
+                ops.push(PatchOperation::Remove { path: format!("{path}/{idx}") });
+            }
+            for value in &b[common..] {
+                ops.push(PatchOperation::Add {
+                    path: format!("{path}/-"),
+                    value: value.clone()
+                });
+            }
+        },
+        (a, b) if a == b => {},
+        (.., b) => {
+            ops.push(PatchOperation::Replace { path: path.clone(), value: b.clone() })
+        },
+    }
+}
+
+fn child(path: &str, key: &str) -> String {
+    format!("{path}/{}", json_pointer_escape(key))
+}
+
+fn apply_operation(
+    value: &mut Value,
+    operation: &PatchOperation
+) -> Result<(), PatchError> {
+    match operation {
+        PatchOperation::Add { path, value: new } => add(value, path, new.clone()),
+        PatchOperation::Remove { path } => remove(value, path).map(|_| ()),
+        PatchOperation::Replace { path, value: new } => {
+            *get_mut(value, path)? = new.clone();
+            Ok(())
+        },
+        PatchOperation::Move { from, path } => {
+            if path.starts_with(from.as_str()) && path[from.len()..].starts_with('/') {
+                return Err(PatchError::InvalidMove(path.clone()));
+            }
+            if from == path {
+                return get_mut(value, path).map(|_| ());
+            }
+            let moved = remove(value, from)?;
+            add(value, path, moved)
+        },
+        PatchOperation::Copy { from, path } => {
+            let copied = get_mut(value, from)?.clone();
+            add(value, path, copied)
+        },
+        PatchOperation::Test { path, value: expected } => {
+            if get_mut(value, path)? != expected {
+                return Err(PatchError::TestFailed(path.clone()));
+            }
+            Ok(())
+        }
+    }
+}
+
+/// Tokens of JSON pointer (RFC 6901)
+fn tokens(path: &str) -> Result<Vec<String>, PatchError> {
+    if path.is_empty() {
+        return Ok(Vec::new());
+    }
+    let Some(path) = path.strip_prefix('/') else {
+        return Err(PatchError::InvalidPointer(path.into()));
+    };
+
+    path.split('/')
+        .map(|token| token.replace("~1", "/").replace("~0", "~"))
+        .collect::<Vec<_>>()
+        .into_ok()
+}
+
+fn index(token: &str, len: usize, path: &str) -> Result<usize, PatchError> {
+    let valid = token.is_empty() == false
+        && token.bytes().all(|b| b.is_ascii_digit())
+        && (token == "0" || token.starts_with('0') == false);
+
+    match valid.then(|| token.parse::<usize>().ok()).flatten() {
+        Some(idx) if idx < len => Ok(idx),
+        Some(..) => Err(PatchError::PathNotFound(path.into())),
+        None => Err(PatchError::InvalidIndex(path.into()))
+    }
+}
+
+fn get_mut<'a>(value: &'a mut Value, path: &str) -> Result<&'a mut Value, PatchError> {
+    let mut value = value;
+    for token in tokens(path)? {
+        value = match value {
+            Value::Object(object) => object.get_mut(&token),
+            Value::Array(array) => {
+                let idx = index(&token, array.len(), path)?;
+                array.get_mut(idx)
+            },
+            _ => None
+        }
+        .ok_or_else(|| PatchError::PathNotFound(path.into()))?;
+    }
+
+    Ok(value)
+}
+
+/// Parent of value by *path* and the last token
+fn parent<'a>(
+    value: &'a mut Value,
+    path: &str
+) -> Result<(&'a mut Value, String), PatchError> {
+    let mut tokens = tokens(path)?;
+    let Some(last) = tokens.pop() else {
+        return Err(PatchError::InvalidPointer(path.into()));
+    };
+    let parent_path = &path[..path.rfind('/').unwrap_or_default()];
+
+    Ok((get_mut(value, parent_path)?, last))
+}
+
+fn add(value: &mut Value, path: &str, new: Value) -> Result<(), PatchError> {
+    if path.is_empty() {
+        *value = new;
+        return Ok(());
+    }
+
+    match parent(value, path)? {
+        (Value::Object(object), key) => {
+            object.insert(key, new);
+        },
+        (Value::Array(array), token) if token == "-" => array.push(new),
+        (Value::Array(array), token) => {
+            // index can be equal to length
+            let idx = index(&token, array.len() + 1, path)?;
+            array.insert(idx, new);
+        },
+        _ => return Err(PatchError::PathNotFound(path.into()))
+    }
+
+    Ok(())
+}
+
+fn remove(value: &mut Value, path: &str) -> Result<Value, PatchError> {
+    match parent(value, path)? {
+        (Value::Object(object), key) => {
+            object
+                .remove(&key)
+                .ok_or_else(|| PatchError::PathNotFound(path.into()))
+        },
+        (Value::Array(array), token) => {
+            let idx = index(&token, array.len(), path)?;
+            Ok(array.remove(idx))
+        },
+        _ => Err(PatchError::PathNotFound(path.into()))
+    }
+}
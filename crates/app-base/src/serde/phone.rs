@@ -1,29 +1,26 @@
 use {
-    crate::filters::filter_phone,
+    crate::filters::normalize_phone,
     alloc::string::String,
-    serde::{Deserialize, Deserializer}
+    serde::{Deserialize, Deserializer, de::Error}
 };
 
+/// Normalizes phone number to E.164, invalid number is an error
 pub fn phone<'de, D>(d: D) -> Result<String, D::Error>
 where
     D: Deserializer<'de>
 {
     let s: String = Deserialize::deserialize(d)?;
-    Ok(filter_phone(&s))
+    normalize_phone(&s).map_err(D::Error::custom)
 }
 
+/// Normalizes phone number to E.164, empty string is `None`
 pub fn phone_option<'de, D>(d: D) -> Result<Option<String>, D::Error>
 where
     D: Deserializer<'de>
 {
     let s: Option<String> = Deserialize::deserialize(d)?;
-    match s.as_ref() {
-        Some(value) => {
-            match filter_phone(value) {
-                value if !value.is_empty() => Ok(Some(value)),
-                _ => Ok(None)
-            }
-        },
-        None => Ok(None)
+    match s.as_deref().map(str::trim) {
+        None | Some("") => Ok(None),
+        Some(value) => normalize_phone(value).map(Some).map_err(D::Error::custom)
     }
 }
@@ -0,0 +1,769 @@
+use {
+    crate::prelude::*,
+    alloc::{ffi::CString, format, string::String, sync::Arc, vec::Vec},
+    core::{
+        error::Error,
+        ffi::CStr,
+        fmt::{self, Display},
+        mem::zeroed
+    },
+    serde::{Serialize, Serializer}
+};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TimeError {
+    UnknownTimezone(String),
+    InvalidTimezone(String)
+}
+
+impl Error for TimeError {}
+
+impl Display for TimeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnknownTimezone(e) => write!(f, "Unknown timezone: {e}"),
+            Self::InvalidTimezone(e) => write!(f, "Invalid timezone: {e}")
+        }
+    }
+}
+
+const SECS_PER_DAY: i64 = 86_400;
+
+/// Local time type of timezone, i.e. `CEST` with offset `+02:00`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LocalType {
+    /// Offset from UTC in seconds, positive to the east
+    pub offset: i32,
+    pub dst: bool,
+    pub abbr: String
+}
+
+impl LocalType {
+    fn fixed(offset: i32) -> Self {
+        Self { offset, dst: false, abbr: Self::offset_abbr(offset) }
+    }
+
+    /// `UTC`, `+03` or `-0930`
+    fn offset_abbr(offset: i32) -> String {
+        match offset {
+            0 => "UTC".into(),
+            offset if offset % 3600 == 0 => format!("{:+03}", offset / 3600),
+            offset => {
+                let sign = if offset < 0 { '-' } else { '+' };
+                let offset = offset.unsigned_abs();
+                format!("{sign}{:02}{:02}", offset / 3600, offset % 3600 / 60)
+            }
+        }
+    }
+}
+
+/// Day of POSIX TZ rule `Mm.w.d`, day *d* (0 is Sunday) of week *w* (5 is
+/// the last) of month *m*
+///
+/// Julian day forms `Jn` and `n` aren't supported, tzdata doesn't use them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct RuleDay(u8, u8, u8);
+
+impl RuleDay {
+    /// Days since epoch of the day in *year*
+    fn days(self, year: i64) -> i64 {
+        let Self(month, week, weekday) = self;
+        let (first, first_weekday) = civil_days(year, month as i32, 1);
+        let len = civil_days(year, month as i32 + 1, 1).0 - first;
+
+        let mut day =
+            (weekday as i64 - first_weekday as i64).rem_euclid(7) + (week as i64 - 1) * 7;
+        while day >= len {
+            day -= 7;
+        }
+        first + day
+    }
+}
+
+/// POSIX TZ rule, i.e. `CET-1CEST,M3.5.0,M10.5.0/3`, used for times after
+/// the last transition of TZif file
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Rule {
+    std: LocalType,
+    dst: Option<(LocalType, (RuleDay, i32), (RuleDay, i32))>
+}
+
+impl Rule {
+    fn parse(s: &str) -> Option<Self> {
+        let mut parser = RuleParser(s.as_bytes());
+
+        let std_abbr = parser.abbr()?;
+        let std_offset = -parser.time()?;
+        let std = LocalType { offset: std_offset, dst: false, abbr: std_abbr };
+
+        if parser.0.is_empty() {
+            return Some(Self { std, dst: None });
+        }
+
+        let dst_abbr = parser.abbr()?;
+        let dst_offset = match parser.0.first() {
+            Some(b',') | None => std_offset + 3600,
+            Some(_) => -parser.time()?
+        };
+        let dst = LocalType { offset: dst_offset, dst: true, abbr: dst_abbr };
+
+        // US rules are the default of POSIX
+        let (start, end) = match parser.0.is_empty() {
+            true => ((RuleDay(3, 2, 0), 7200), (RuleDay(11, 1, 0), 7200)),
+            false => (parser.transition()?, parser.transition()?)
+        };
+
+        parser
+            .0
+            .is_empty()
+            .then_some(Self { std, dst: Some((dst, start, end)) })
+    }
+
+    fn local_type(&self, timestamp: i64) -> &LocalType {
+        let Some((dst, (start_day, start_time), (end_day, end_time))) = &self.dst else {
+            return &self.std;
+        };
+
+        let year = broken_down(timestamp + self.std.offset as i64).tm_year as i64 + 1900;
+        let start = start_day.days(year) * SECS_PER_DAY + *start_time as i64
+            - self.std.offset as i64;
+        let end =
+            end_day.days(year) * SECS_PER_DAY + *end_time as i64 - dst.offset as i64;
+
+        let is_dst = match start < end {
+            true => start <= timestamp && timestamp < end,
+            // southern hemisphere
+            false => (end <= timestamp && timestamp < start) == false
+        };
+
+        if is_dst { dst } else { &self.std }
+    }
+}
+
+struct RuleParser<'a>(&'a [u8]);
+
+impl RuleParser<'_> {
+    /// `CET` or quoted `<+03>`
+    fn abbr(&mut self) -> Option<String> {
+        let (abbr, rest) = match self.0.strip_prefix(b"<") {
+            Some(rest) => {
+                let len = rest.iter().position(|c| *c == b'>')?;
+                (&rest[..len], &rest[len + 1..])
+            },
+            None => {
+                let len = self
+                    .0
+                    .iter()
+                    .take_while(|c| c.is_ascii_alphabetic())
+                    .count();
+                self.0.split_at(len)
+            }
+        };
+        self.0 = rest;
+
+        (abbr.len() >= 3).then(|| String::from_utf8_lossy(abbr).into_owned())
+    }
+
+    /// `[+-]hh[:mm[:ss]]` in seconds
+    fn time(&mut self) -> Option<i32> {
+        let sign = match self.0.first() {
+            Some(b'-') => -1,
+            _ => 1
+        };
+        if let Some(b'-' | b'+') = self.0.first() {
+            self.0 = &self.0[1..];
+        }
+
+        let mut seconds = 0;
+        for (i, multiplier) in [3600, 60, 1].into_iter().enumerate() {
+            if i > 0 {
+                match self.0.strip_prefix(b":") {
+                    Some(rest) => self.0 = rest,
+                    None => break
+                }
+            }
+            seconds += self.number()? * multiplier;
+        }
+
+        Some(sign * seconds)
+    }
+
+    fn number(&mut self) -> Option<i32> {
+        let len = self.0.iter().take_while(|c| c.is_ascii_digit()).count();
+        let (number, rest) = self.0.split_at(len);
+        self.0 = rest;
+
+        core::str::from_utf8(number).ok()?.parse().ok()
+    }
+
+    /// `,Mm.w.d[/time]` start or end of DST
+    fn transition(&mut self) -> Option<(RuleDay, i32)> {
+        self.0 = self.0.strip_prefix(b",M")?;
+
+        let month = self.number().filter(|n| (1..=12).contains(n))?;
+        self.0 = self.0.strip_prefix(b".")?;
+        let week = self.number().filter(|n| (1..=5).contains(n))?;
+        self.0 = self.0.strip_prefix(b".")?;
+        let weekday = self.number().filter(|n| (0..=6).contains(n))?;
+
+        let time = match self.0.strip_prefix(b"/") {
+            Some(rest) => {
+                self.0 = rest;
+                self.time()?
+            },
+            None => 7200
+        };
+
+        Some((RuleDay(month as u8, week as u8, weekday as u8), time))
+    }
+}
+
+/// Timezone of zoneinfo database, POSIX TZ rule or fixed offset
+///
+/// Named zones are loaded from TZif files of `$TZDIR` or
+/// `/usr/share/zoneinfo`, so no timezone data is compiled into the app.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Timezone {
+    name: String,
+    /// Transition times and indexes of their local types
+    transitions: Vec<(i64, usize)>,
+    types: Vec<LocalType>,
+    rule: Option<Rule>
+}
+
+impl Default for Timezone {
+    fn default() -> Self {
+        Self::utc()
+    }
+}
+
+impl Display for Timezone {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.name)
+    }
+}
+
+impl Timezone {
+    pub const ZONEINFO_DIR: &str = "/usr/share/zoneinfo";
+    /// TZif files of zoneinfo are a few kilobytes
+    pub const MAX_FILE_SIZE: usize = 64 * 1024;
+
+    pub fn utc() -> Self {
+        Self::fixed("UTC", 0)
+    }
+
+    /// Timezone with constant *offset* in seconds
+    pub fn fixed(name: &str, offset: i32) -> Self {
+        Self {
+            name: name.into(),
+            transitions: Vec::new(),
+            types: Vec::from([LocalType::fixed(offset)]),
+            rule: None
+        }
+    }
+
+    /// Loads timezone by *name*
+    ///
+    /// Name is `UTC`, offset like `+03:00`, zone like `Europe/Moscow` of
+    /// `$TZDIR` or POSIX TZ rule. Names may come from users and templates, so
+    /// paths out of `$TZDIR` are rejected, see `load_file()`.
+    pub fn load(name: &str) -> Result<Self, TimeError> {
+        let name = name.trim();
+        let path = name.strip_prefix(':').unwrap_or(name);
+
+        if matches!(path, "" | "UTC" | "Z" | "Etc/UTC") {
+            return Ok(Self::fixed(name, 0));
+        }
+        if let Some(offset) = Self::parse_offset(path) {
+            return Ok(Self::fixed(name, offset));
+        }
+        if path.split('/').any(|v| v.is_empty() || v.starts_with('.')) {
+            return Err(TimeError::UnknownTimezone(name.into()));
+        }
+
+        let dir = getenv("TZDIR").unwrap_or_else(|| Self::ZONEINFO_DIR.into());
+        match Self::read(&format!("{}/{path}", dir.trim_end_matches('/'))) {
+            Some(data) => Self::parse_file(name, &data),
+            // POSIX TZ string which is not a file, i.e. `EST5EDT,M3.2.0,M11.1.0`
+            None => {
+                Self::from_rule(name, path)
+                    .map_err(|_| TimeError::UnknownTimezone(name.into()))
+            },
+        }
+    }
+
+    /// Loads TZif file of trusted *path*, i.e. `/etc/localtime` or path of
+    /// `TZ` variable
+    pub fn load_file(name: &str, path: &str) -> Result<Self, TimeError> {
+        match Self::read(path) {
+            Some(data) => Self::parse_file(name, &data),
+            None => Err(TimeError::UnknownTimezone(name.into()))
+        }
+    }
+
+    /// Timezone of POSIX TZ *rule*, i.e. `CET-1CEST,M3.5.0,M10.5.0/3`
+    pub fn from_rule(name: &str, rule: &str) -> Result<Self, TimeError> {
+        let rule = Rule::parse(rule).ok_or_else(|| {
+            TimeError::InvalidTimezone(format!("Invalid rule '{rule}': {name}"))
+        })?;
+
+        Ok(Self {
+            name: name.into(),
+            transitions: Vec::new(),
+            types: Vec::from([rule.std.clone()]),
+            rule: Some(rule)
+        })
+    }
+
+    /// Content of file, reading stops after `MAX_FILE_SIZE`
+    fn read(path: &str) -> Option<Vec<u8>> {
+        let c_path = CString::new(path).ok()?;
+        let file = unsafe { libc::fopen(c_path.as_ptr(), c"rb".as_ptr()) };
+        if file.is_null() {
+            return None;
+        }
+
+        let mut data = alloc::vec![0u8; Self::MAX_FILE_SIZE + 1];
+        let len = unsafe { libc::fread(data.as_mut_ptr().cast(), 1, data.len(), file) };
+        // directory is opened, but it can't be read
+        let failed = unsafe { libc::ferror(file) } != 0;
+        unsafe { libc::fclose(file) };
+        data.truncate(len);
+
+        (failed == false).then_some(data)
+    }
+
+    fn parse_file(name: &str, data: &[u8]) -> Result<Self, TimeError> {
+        if data.len() > Self::MAX_FILE_SIZE {
+            Err(TimeError::InvalidTimezone(format!(
+                "File is too large: {name}"
+            )))?
+        }
+
+        Self::parse(name, data)
+    }
+
+    /// Parses TZif file of RFC 8536
+    pub fn parse(name: &str, data: &[u8]) -> Result<Self, TimeError> {
+        let invalid = |e: &str| TimeError::InvalidTimezone(format!("{e}: {name}"));
+
+        let header = |data: &[u8]| -> Option<[usize; 6]> {
+            let data = data.strip_prefix(b"TZif")?.get(16..40)?;
+            let mut counts = [0; 6];
+            for (i, count) in counts.iter_mut().enumerate() {
+                *count =
+                    u32::from_be_bytes(data[i * 4..i * 4 + 4].try_into().ok()?) as usize;
+            }
+            Some(counts)
+        };
+        // isutcnt, isstdcnt, leapcnt, timecnt, typecnt, charcnt
+        let block_len = |[isut, isstd, leap, time, types, chars]: [usize; 6],
+                         time_len: usize| {
+            time * (time_len + 1)
+                + types * 6
+                + chars
+                + leap * (time_len + 4)
+                + isstd
+                + isut
+        };
+
+        let counts = header(data).ok_or_else(|| invalid("Invalid header"))?;
+        let (counts, time_len, mut data) = match data[4] {
+            0 => (counts, 4, &data[44..]),
+            _ => {
+                let data = data
+                    .get(44 + block_len(counts, 4)..)
+                    .ok_or_else(|| invalid("Unexpected end of data"))?;
+                let counts = header(data).ok_or_else(|| invalid("Invalid header"))?;
+                (counts, 8, &data[44..])
+            }
+        };
+
+        let [.., time_count, type_count, char_count] = counts;
+        let len = block_len(counts, time_len);
+        if data.len() < len || type_count == 0 {
+            Err(invalid("Unexpected end of data"))?;
+        }
+
+        let times = &data[..time_count * time_len];
+        let indexes = &data[time_count * time_len..time_count * (time_len + 1)];
+        let infos = &data[time_count * (time_len + 1)..][..type_count * 6];
+        let chars = &data[time_count * (time_len + 1) + type_count * 6..][..char_count];
+
+        let transitions = times
+            .chunks(time_len)
+            .zip(indexes)
+            .map(|(time, index)| {
+                let time = match time_len {
+                    4 => i32::from_be_bytes(time.try_into().unwrap()) as i64,
+                    _ => i64::from_be_bytes(time.try_into().unwrap())
+                };
+                match (*index as usize) < type_count {
+                    true => Ok((time, *index as usize)),
+                    false => Err(invalid("Invalid type index"))
+                }
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let types = infos
+            .chunks(6)
+            .map(|info| {
+                let abbr = chars.get(info[5] as usize..).unwrap_or_default();
+                let len = abbr.iter().position(|c| *c == 0).unwrap_or(abbr.len());
+                LocalType {
+                    offset: i32::from_be_bytes(info[..4].try_into().unwrap()),
+                    dst: info[4] != 0,
+                    abbr: String::from_utf8_lossy(&abbr[..len]).into_owned()
+                }
+            })
+            .collect();
+
+        data = &data[len..];
+        let rule = match time_len {
+            8 => {
+                data.strip_prefix(b"\n")
+                    .and_then(|data| data.split(|c| *c == b'\n').next())
+                    .filter(|rule| rule.is_empty() == false)
+                    .map(|rule| {
+                        Rule::parse(&String::from_utf8_lossy(rule))
+                            .ok_or_else(|| invalid("Invalid footer"))
+                    })
+                    .transpose()?
+            },
+            _ => None
+        };
+
+        Ok(Self { name: name.into(), transitions, types, rule })
+    }
+
+    #[inline]
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Local type in effect at *timestamp*
+    pub fn local_type(&self, timestamp: i64) -> &LocalType {
+        let index = self
+            .transitions
+            .partition_point(|(time, _)| *time <= timestamp);
+
+        match (index, &self.rule) {
+            (index, Some(rule)) if index == self.transitions.len() => {
+                rule.local_type(timestamp)
+            },
+            (0, _) => &self.types[0],
+            (index, _) => &self.types[self.transitions[index - 1].1]
+        }
+    }
+
+    /// Offset from UTC in seconds at *timestamp*
+    #[inline]
+    pub fn offset(&self, timestamp: i64) -> i32 {
+        self.local_type(timestamp).offset
+    }
+
+    /// Local date and time of unix *timestamp*
+    pub fn at(&self, timestamp: i64, nanos: u32) -> DateTime {
+        let local = self.local_type(timestamp);
+        DateTime {
+            timestamp,
+            nanos,
+            offset: local.offset,
+            dst: local.dst,
+            abbr: local.abbr.clone()
+        }
+    }
+
+    /// `+03:00`, `-0530` or `+03` in seconds
+    fn parse_offset(s: &str) -> Option<i32> {
+        let sign = match s.as_bytes().first()? {
+            b'+' => 1,
+            b'-' => -1,
+            _ => None?
+        };
+        let s = s[1..].replace(':', "");
+        if s.is_empty() || s.len() > 4 || s.bytes().all(|c| c.is_ascii_digit()) == false {
+            return None;
+        }
+
+        let (hours, minutes) = match s.len() {
+            1 | 2 => (s.parse::<i32>().ok()?, 0),
+            len => {
+                (
+                    s[..len - 2].parse().ok()?,
+                    s[len - 2..].parse::<i32>().ok()?
+                )
+            },
+        };
+        (hours <= 24 && minutes < 60).then_some(sign * (hours * 3600 + minutes * 60))
+    }
+}
+
+/// Date and time with offset of timezone
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DateTime {
+    timestamp: i64,
+    nanos: u32,
+    offset: i32,
+    dst: bool,
+    abbr: String
+}
+
+impl Display for DateTime {
+    /// RFC 3339, i.e. `2024-05-01T10:00:00.250+03:00`
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (year, month, day) = self.date();
+        let (hour, minute, second) = self.time();
+        write!(
+            f,
+            "{year:04}-{month:02}-{day:02}T{hour:02}:{minute:02}:{second:02}"
+        )?;
+
+        match self.nanos {
+            0 => {},
+            nanos if nanos % 1_000_000 == 0 => write!(f, ".{:03}", nanos / 1_000_000)?,
+            nanos if nanos % 1_000 == 0 => write!(f, ".{:06}", nanos / 1_000)?,
+            nanos => write!(f, ".{nanos:09}")?
+        }
+
+        match self.offset {
+            0 => f.write_str("Z"),
+            offset => {
+                let sign = if offset < 0 { '-' } else { '+' };
+                let offset = offset.unsigned_abs();
+                write!(f, "{sign}{:02}:{:02}", offset / 3600, offset % 3600 / 60)
+            }
+        }
+    }
+}
+
+impl Serialize for DateTime {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl DateTime {
+    /// Date and time of unix *timestamp* in UTC
+    pub fn utc(timestamp: i64, nanos: u32) -> Self {
+        Self { timestamp, nanos, offset: 0, dst: false, abbr: "UTC".into() }
+    }
+
+    /// The same moment in *timezone*
+    #[inline]
+    pub fn with_timezone(&self, timezone: &Timezone) -> Self {
+        timezone.at(self.timestamp, self.nanos)
+    }
+
+    #[inline]
+    pub fn timestamp(&self) -> i64 {
+        self.timestamp
+    }
+
+    #[inline]
+    pub fn nanos(&self) -> u32 {
+        self.nanos
+    }
+
+    /// Offset from UTC in seconds
+    #[inline]
+    pub fn offset(&self) -> i32 {
+        self.offset
+    }
+
+    #[inline]
+    pub fn is_dst(&self) -> bool {
+        self.dst
+    }
+
+    /// Abbreviation of timezone, i.e. `MSK`
+    #[inline]
+    pub fn abbr(&self) -> &str {
+        &self.abbr
+    }
+
+    /// Local year, month and day
+    pub fn date(&self) -> (i64, u32, u32) {
+        let tm = broken_down(self.local());
+        (
+            tm.tm_year as i64 + 1900,
+            tm.tm_mon as u32 + 1,
+            tm.tm_mday as u32
+        )
+    }
+
+    /// Local hour, minute and second
+    pub fn time(&self) -> (u32, u32, u32) {
+        let tm = broken_down(self.local());
+        (tm.tm_hour as u32, tm.tm_min as u32, tm.tm_sec as u32)
+    }
+
+    /// Day of year, 0 is January 1
+    #[inline]
+    pub fn ordinal(&self) -> u32 {
+        broken_down(self.local()).tm_yday as u32
+    }
+
+    /// Day of week, 0 is Sunday
+    #[inline]
+    pub fn weekday(&self) -> u32 {
+        broken_down(self.local()).tm_wday as u32
+    }
+
+    /// Formats by `strftime` *format*, i.e. `%F %T %z`
+    pub fn format(&self, format: &str) -> String {
+        match CString::new(format) {
+            Ok(format) => self.strftime(&format),
+            Err(_) => String::new()
+        }
+    }
+
+    pub(crate) fn strftime(&self, format: &CStr) -> String {
+        let abbr = CString::new(self.abbr.as_str()).unwrap_or_default();
+
+        let mut tm = broken_down(self.local());
+        tm.tm_isdst = self.dst as i32;
+        tm.tm_gmtoff = self.offset as _;
+        tm.tm_zone = abbr.as_ptr() as _;
+
+        let mut buf = [0u8; 256];
+        let len = unsafe {
+            libc::strftime(buf.as_mut_ptr().cast(), buf.len(), format.as_ptr(), &tm)
+        };
+
+        String::from_utf8_lossy(&buf[..len]).into_owned()
+    }
+
+    /// Seconds of local time since epoch
+    #[inline]
+    fn local(&self) -> i64 {
+        self.timestamp + self.offset as i64
+    }
+}
+
+struct TimeData {
+    timezone: Arc<Timezone>,
+    zones: IndexMap<String, Arc<Timezone>>
+}
+
+/// Current time in the configured timezone
+///
+/// Timezone is `base.timezone` loaded at boot, until then it is `TZ`
+/// variable or `/etc/localtime` of the system. Other zones are loaded once
+/// and cached, so per-user timezones are cheap.
+#[derive(FromStatic)]
+pub struct Time {
+    data: SpinLock<TimeData>
+}
+
+impl Default for Time {
+    fn default() -> Self {
+        // `TZ` of the system is trusted, so it may be a path like `:/etc/localtime`
+        let timezone = getenv("TZ")
+            .and_then(|name| {
+                match name.strip_prefix(':').unwrap_or(&name) {
+                    path if path.starts_with('/') => {
+                        Timezone::load_file(&name, path).ok()
+                    },
+                    _ => Timezone::load(&name).ok()
+                }
+            })
+            .or_else(|| Timezone::load_file("/etc/localtime", "/etc/localtime").ok())
+            .unwrap_or_default();
+
+        Self {
+            data: SpinLock::new(TimeData {
+                timezone: timezone.into(),
+                zones: Default::default()
+            })
+        }
+    }
+}
+
+impl Time {
+    pub const MAX_ZONES: usize = 1024;
+
+    /// Loads the configured timezone, unknown timezone fails boot
+    ///
+    /// Logger takes the timezone in `Logger::configure()` which runs after.
+    pub fn init(config: &BaseConfig) -> Void {
+        let timezone = Self::zone(&config.timezone)?;
+        Self::from_static()
+            .data
+            .with(|data| data.timezone = timezone);
+
+        ok()
+    }
+
+    /// The configured timezone
+    pub fn timezone() -> Arc<Timezone> {
+        Self::from_static().data.with(|data| data.timezone.clone())
+    }
+
+    /// Timezone by *name*, loaded once
+    ///
+    /// Names come from users, so up to `MAX_ZONES` zones are cached, others
+    /// are loaded on each call.
+    pub fn zone(name: &str) -> Result<Arc<Timezone>, TimeError> {
+        let name = name.trim();
+        let this = Self::from_static();
+        if let Some(timezone) = this.data.with(|data| data.zones.get(name).cloned()) {
+            return Ok(timezone);
+        }
+
+        let timezone = Arc::new(Timezone::load(name)?);
+        this.data.with(|data| {
+            if data.zones.len() < Self::MAX_ZONES {
+                data.zones.insert(name.into(), timezone.clone());
+            }
+        });
+
+        Ok(timezone)
+    }
+
+    /// Unix timestamp and nanoseconds
+    pub fn unix() -> (i64, u32) {
+        let mut time: libc::timespec = unsafe { zeroed() };
+        unsafe { libc::clock_gettime(libc::CLOCK_REALTIME, &mut time) };
+
+        (time.tv_sec as i64, time.tv_nsec as u32)
+    }
+
+    /// Current time in the configured timezone
+    pub fn now() -> DateTime {
+        let (timestamp, nanos) = Self::unix();
+        Self::timezone().at(timestamp, nanos)
+    }
+
+    #[inline]
+    pub fn now_utc() -> DateTime {
+        let (timestamp, nanos) = Self::unix();
+        DateTime::utc(timestamp, nanos)
+    }
+
+    /// Current time in timezone *name*, i.e. timezone of user
+    pub fn now_in(name: &str) -> Result<DateTime, TimeError> {
+        let (timestamp, nanos) = Self::unix();
+        Ok(Self::zone(name)?.at(timestamp, nanos))
+    }
+}
+
+/// Broken-down UTC time of unix *timestamp*
+fn broken_down(timestamp: i64) -> libc::tm {
+    let mut tm: libc::tm = unsafe { zeroed() };
+    unsafe { libc::gmtime_r(&(timestamp as libc::time_t), &mut tm) };
+
+    tm
+}
+
+/// Days since epoch and day of week (0 is Sunday) of civil date, month out
+/// of range is carried over to year
+fn civil_days(year: i64, month: i32, day: i32) -> (i64, u32) {
+    let mut tm: libc::tm = unsafe { zeroed() };
+    tm.tm_year = (year - 1900) as i32;
+    tm.tm_mon = month - 1;
+    tm.tm_mday = day;
+    let timestamp = unsafe { libc::timegm(&mut tm) } as i64;
+
+    (timestamp.div_euclid(SECS_PER_DAY), tm.tm_wday as u32)
+}
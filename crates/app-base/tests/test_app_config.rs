@@ -1,6 +1,7 @@
 use {
     app_base::{app::*, prelude::*},
-    std::env::set_current_dir
+    serde::{Deserialize, Serialize},
+    std::{collections::BTreeMap, env::set_current_dir, sync::Arc}
 };
 
 type MyConfig = AppConfig<AppSimpleConfig>;
@@ -47,3 +48,171 @@ fn test_app_config() -> Void {
 
     ok()
 }
+
+#[derive(Debug, Default, ExtendFromIter, AppConfig, Serialize, Deserialize)]
+#[config(prefix = "test.inner", env_prefix = "TEST_INNER")]
+struct InnerConfig {
+    #[config(secret)]
+    password: String,
+    #[config(dir = "data")]
+    path: String,
+    #[config(no_env)]
+    hosts: Vec<String>,
+    #[config(no_arg)]
+    limits: BTreeMap<String, u32>
+}
+
+#[derive(Debug, Default, ExtendFromIter, AppConfig, Serialize, Deserialize)]
+#[config(prefix = "test")]
+struct OuterConfig {
+    #[config(arg = "name", env = "TEST_OUTER_NAME")]
+    name: Option<String>,
+    #[config(skip)]
+    hidden: u32,
+    #[config(nested)]
+    inner: Arc<InnerConfig>
+}
+
+#[test]
+fn test_app_config_derive() -> Void {
+    let mut config = OuterConfig::default();
+    let mut args = Args::new([("exe", "0".into(), None)])?;
+    config.init_args(&mut args);
+    args.parse_args(
+        [
+            "app", "--name", "foo", "--test-inner-hosts=a,b", "--test-inner-path=files"
+        ]
+        .map(String::from)
+        .to_vec()
+    )?;
+    config.load_args(&args)?;
+
+    unsafe { std::env::set_var("TEST_INNER_PASSWORD", "secret") };
+    unsafe { std::env::set_var("TEST_INNER_HOSTS", "c") };
+    config.load_env()?;
+
+    let mut dirs = Dirs::default();
+    dirs.data = "/srv/".into();
+    config.load_dirs(&dirs);
+    config
+        .inner
+        .try_mut()?
+        .limits
+        .extend([("b".into(), 2), ("a".into(), 1)]);
+
+    assert_eq!((config.name.as_deref(), config.hidden), (Some("foo"), 0));
+    assert_eq!(config.inner.password, "secret");
+    assert_eq!(config.inner.hosts, ["a", "b"]);
+
+    let config_dump: Vec<(&str, String)> = config.iter().collect();
+    assert_eq!(
+        config_dump,
+        [
+            ("test.name", "foo".into()),
+            ("test.inner.password", "******".into()),
+            ("test.inner.path", "/srv/files".into()),
+            ("test.inner.hosts", "a,b".into()),
+            ("test.inner.limits", "a=1\nb=2".into())
+        ]
+    );
+    assert!(args.get("test-inner-limits").is_err());
+    assert!(args.get("test-hidden").is_err());
+
+    ok()
+}
+
+#[derive(Debug, Default, ExtendFromIter, AppConfig)]
+#[config(env_prefix = "TEST_ERRORS")]
+struct LimitsConfig {
+    #[config(no_arg)]
+    limits: BTreeMap<String, u32>
+}
+
+#[derive(Debug, Default, ExtendFromIter, AppConfig)]
+struct ErrorsConfig {
+    #[config(nested)]
+    inner: Arc<LimitsConfig>
+}
+
+#[test]
+fn test_app_config_errors() -> Void {
+    let mut config = ErrorsConfig::default();
+
+    unsafe { std::env::set_var("TEST_ERRORS_LIMITS", "a = 1, b = x") };
+    let errors = config.load_env().unwrap_err();
+
+    assert_eq!(
+        errors.to_string(),
+        "Invalid value 'x' of inner.limits.b, expected u32 (env TEST_ERRORS_LIMITS)"
+    );
+    assert_eq!(config.inner.limits, BTreeMap::from_iter([("a".into(), 1)]));
+
+    let errors = config
+        .try_extend([
+            ("inner.limits.c", Some("-3")),
+            ("inner.limits.d", Some("4.0"))
+        ])
+        .unwrap_err();
+    let mut keys: Vec<_> = errors
+        .iter()
+        .map(|e| (e.key.as_str(), e.value.as_str()))
+        .collect();
+    keys.sort();
+    assert_eq!(keys, [("inner.limits.c", "-3"), ("inner.limits.d", "4.0")]);
+
+    ok()
+}
+
+#[derive(Debug, Default, ExtendFromIter, AppConfig)]
+#[config(
+    prefix = "tool",
+    arg_prefix = "t",
+    command = "tool",
+    after_args = "load_quiet"
+)]
+struct CommandConfig {
+    #[config(short = "-o")]
+    output: Option<String>,
+    #[config(short = "-n", flag)]
+    dry_run: bool,
+    #[config(flag)]
+    quiet: bool,
+    verbose: bool
+}
+
+impl CommandConfig {
+    fn load_quiet(&mut self, _args: &Args) {
+        self.verbose = self.quiet == false;
+    }
+}
+
+#[test]
+fn test_app_config_command() -> Void {
+    let args = |argv: &[&str]| -> Ok<(CommandConfig, Args)> {
+        let mut config = CommandConfig::default();
+        let mut args =
+            Args::new([("exe", "0".into(), None), ("command", "1".into(), None)])?;
+        config.init_args(&mut args);
+        args.parse_args(argv.iter().map(|v| v.to_string()).collect())?;
+        config.load_args(&args)?;
+        (config, args).into_ok()
+    };
+
+    let (config, _) = args(&["app", "tool", "-o", "out", "-n", "--t-quiet"])?;
+    assert_eq!(config.output.as_deref(), Some("out"));
+    assert_eq!(
+        (config.dry_run, config.quiet, config.verbose),
+        (true, true, false)
+    );
+
+    let (config, _) = args(&["app", "tool", "--t-output=out"])?;
+    assert_eq!(config.output.as_deref(), Some("out"));
+    assert_eq!((config.dry_run, config.verbose), (false, true));
+
+    // options of other commands are not added
+    let (config, args) = args(&["app", "serve"])?;
+    assert!(args.get("t-output").is_err());
+    assert_eq!(config.verbose, false);
+
+    ok()
+}
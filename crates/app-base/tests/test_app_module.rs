@@ -0,0 +1,110 @@
+use {
+    app_base::{app::*, prelude::*},
+    std::env::set_current_dir
+};
+
+type MyApp = App<AppSimpleConfig>;
+
+/// Greets users by name.
+///
+/// Prints greeting {times} times.
+#[app_module(command = "greet")]
+#[derive(Default)]
+struct GreetModule {
+    /// Name of user
+    #[arg(position = 2)]
+    name: Option<String>,
+    /// Times of greeting
+    #[arg(short = "-t", default = "1")]
+    times: u32,
+    /// Prints greeting in uppercase
+    #[arg(name = "loud")]
+    is_loud: bool,
+    /// Prints greeting twice
+    #[arg]
+    echo: core::primitive::bool,
+    greetings: Vec<String>
+}
+
+impl AppModuleHooks for GreetModule {
+    fn on_boot(&mut self, _app: &mut MyApp) -> Void {
+        let name = self.name.as_deref().unwrap_or("World");
+        self.greetings = vec![format!("Hello, {name}!"); self.times as usize];
+        ok()
+    }
+}
+
+#[app_module(command = "idle", no_entry)]
+#[derive(Default)]
+struct IdleModule;
+
+impl AppModuleHooks for IdleModule {}
+
+#[test]
+fn test_app_module() -> Void {
+    set_current_dir(env!("PWD"))?;
+
+    assert_eq!(GreetModule::COMMAND, "greet");
+    assert_eq!(GreetModule::DESCRIPTION, "greets users by name");
+    assert_eq!(IdleModule::DESCRIPTION, "");
+
+    // Braces of doc are escaped, defaults are appended to options
+    assert_eq!(
+        GreetModule::help_text("app", "App", "1.0"),
+        r#"
+Usage: app greet [options] <name>
+
+Version: App 1.0
+
+Greets users by name.
+
+Prints greeting {times} times.
+
+Options:
+    <name>            - Name of user
+    -t, --times value - Times of greeting (default: 1)
+    --loud            - Prints greeting in uppercase
+    --echo            - Prints greeting twice
+    -h, --help        - show usage help
+    --env-file file   - loads env vars from file
+    --debug           - enable debuging
+    --version         - show current version
+    --timings         - show durations of boot steps on exit
+"#
+    );
+    assert!(
+        IdleModule::help_text("app", "App", "1.0")
+            .starts_with("\nUsage: app idle [options]\n")
+    );
+
+    let mut app = MyApp::new([GreetModule::handle, IdleModule::handle]);
+    app.with_args([("command", "greet")]).boot()?;
+
+    let options = &app.args().options;
+    assert_eq!(options.get("name"), Some(&Some("2")));
+    assert_eq!(options.get("times"), Some(&Some("-t")));
+    assert_eq!(options.get("loud:b"), Some(&None));
+    assert_eq!(options.get("echo:b"), Some(&None));
+    assert!(options.contains_key("greetings") == false);
+    assert_eq!(app.get_ref::<GreetModule>().unwrap().times, 1);
+
+    // C entry point boots module by loaded arguments
+    app.args_mut().insert("name".into(), Some("Bob".into()));
+    app.args_mut().insert("times".into(), Some("2".into()));
+    app.args_mut().insert("loud".into(), Some("1".into()));
+    assert!(module_greet(&mut app, AppEvent::APP_BOOT).is_null());
+
+    let module = app.get_ref::<GreetModule>().unwrap();
+    assert_eq!(module.greetings, ["Hello, Bob!", "Hello, Bob!"]);
+    assert!(module.is_loud);
+
+    app.args_mut().insert("times".into(), Some("x".into()));
+    assert_eq!(
+        GreetModule::handle(&mut app, AppEvent::APP_BOOT)
+            .unwrap_err()
+            .to_string(),
+        "Invalid value 'x' of times, expected u32 (arg --times)"
+    );
+
+    ok()
+}
@@ -0,0 +1,194 @@
+use {
+    app_base::{app::*, prelude::*},
+    std::{
+        env::set_current_dir,
+        sync::atomic::{AtomicBool, AtomicUsize, Ordering}
+    }
+};
+
+type MyApp = App<AppSimpleConfig>;
+
+static SERVE_RUNS: AtomicUsize = AtomicUsize::new(0);
+static WORKER_RUNS: AtomicUsize = AtomicUsize::new(0);
+
+#[derive(Default)]
+struct ServeModule;
+
+impl AppModuleExt for ServeModule {
+    const COMMAND: &str = "serve";
+
+    type Config = AppSimpleConfig;
+
+    fn run(&mut self, _app: &mut MyApp) -> Void {
+        SERVE_RUNS.fetch_add(1, Ordering::SeqCst);
+        ok()
+    }
+
+    fn run_async(&mut self, _app: &mut MyApp) -> Ok<Option<AppFuture>> {
+        Some(Box::pin(async {
+            SERVE_RUNS.fetch_add(1, Ordering::SeqCst);
+            ok()
+        }) as AppFuture)
+        .into_ok()
+    }
+}
+
+#[derive(Default)]
+struct WorkerModule;
+
+impl AppModuleExt for WorkerModule {
+    const COMMAND: &str = "worker";
+
+    type Config = AppSimpleConfig;
+
+    fn run_async(&mut self, _app: &mut MyApp) -> Ok<Option<AppFuture>> {
+        Some(Box::pin(async {
+            WORKER_RUNS.fetch_add(1, Ordering::SeqCst);
+            ok()
+        }) as AppFuture)
+        .into_ok()
+    }
+}
+
+#[derive(Default)]
+struct SyncModule;
+
+impl AppModuleExt for SyncModule {
+    const COMMAND: &str = "sync";
+
+    type Config = AppSimpleConfig;
+}
+
+#[test]
+fn test_app_run() -> Void {
+    set_current_dir(env!("PWD"))?;
+
+    let modules = [
+        ServeModule::handle,
+        WorkerModule::handle,
+        SyncModule::handle
+    ];
+
+    // one command runs synchronously
+    let mut app = MyApp::new(modules);
+    app.with_args([("command", "serve")]).boot()?;
+    assert_eq!(app.run_commands()?, ["serve"]);
+    assert!(app.is_command("serve"));
+    assert!(app.is_command("worker") == false);
+    app.run()?;
+    assert_eq!(SERVE_RUNS.load(Ordering::SeqCst), 1);
+    drop(app);
+
+    // several commands run together
+    let mut app = MyApp::new(modules);
+    app.with_args([("command", " serve, worker,")]).boot()?;
+    assert_eq!(app.run_commands()?, ["serve", "worker"]);
+    assert!(app.is_command("worker"));
+    app.run()?;
+    assert_eq!(SERVE_RUNS.load(Ordering::SeqCst), 2);
+    assert_eq!(WORKER_RUNS.load(Ordering::SeqCst), 1);
+    assert!(app.timings().iter().any(|t| t.name == "AppRunAll"));
+    drop(app);
+
+    // command without future can run only alone
+    let mut app = MyApp::new(modules);
+    app.with_args([("command", "serve,sync")]).boot()?;
+    let e = app.run().unwrap_err();
+    assert_eq!(
+        e.to_string(),
+        "Command 'sync' can not run with other commands"
+    );
+    drop(app);
+
+    let mut app = MyApp::new(modules);
+    app.with_args([("command", "serve,unknown")]).boot()?;
+    assert_eq!(
+        app.run().unwrap_err().to_string(),
+        "Invalid command: 'unknown'"
+    );
+    drop(app);
+
+    // `app run serve,worker`
+    let mut app = MyApp::new(modules);
+    app.with_args([("command", "run"), ("run-commands", "worker,serve")])
+        .boot()?;
+    assert_eq!(app.run_commands()?, ["worker", "serve"]);
+    app.run()?;
+    assert_eq!(SERVE_RUNS.load(Ordering::SeqCst), 3);
+    assert_eq!(WORKER_RUNS.load(Ordering::SeqCst), 2);
+    drop(app);
+
+    // `app run` with `app.run` of config
+    let mut app = MyApp::new(modules);
+    app.with_args([("command", "run")]).boot()?;
+    app.config_mut()?.run = Some("worker".into());
+    assert_eq!(app.run_commands()?, ["worker"]);
+    drop(app);
+
+    // help of several commands isn't shown and doesn't run them
+    let mut app = MyApp::new(modules);
+    app.with_args([("command", "serve,worker"), ("help", "1")])
+        .boot()?;
+    assert_eq!(
+        app.run().unwrap_err().to_string(),
+        "Help is shown for one command, i.e. `serve --help`"
+    );
+    drop(app);
+
+    let mut app = MyApp::new(modules);
+    app.with_args([("command", "serve,worker"), ("version", "1")])
+        .boot()?;
+    app.run()?;
+    drop(app);
+
+    let mut app = MyApp::new(modules);
+    app.with_args([("command", "run"), ("run-commands", "serve"), ("help", "1")])
+        .boot()?;
+    app.run()?;
+    assert_eq!(SERVE_RUNS.load(Ordering::SeqCst), 3);
+    assert_eq!(WORKER_RUNS.load(Ordering::SeqCst), 2);
+
+    ok()
+}
+
+#[test]
+fn test_app_run_all() -> Void {
+    let futures: [(String, AppFuture); 2] = [
+        ("a".into(), Box::pin(async { ok() })),
+        ("b".into(), Box::pin(async { ok() }))
+    ];
+    app_block_on(AppRunAll::new(futures))?;
+
+    // the first error stops other commands
+    let futures: [(String, AppFuture); 2] = [
+        ("a".into(), Box::pin(async { Void::Err("failed".into()) })),
+        ("b".into(), Box::pin(std::future::pending::<Void>()))
+    ];
+    let e = app_block_on(AppRunAll::new(futures)).unwrap_err();
+    assert_eq!(e.to_string(), "a: failed");
+
+    // the first finished command stops other commands too
+    static DROPPED: AtomicBool = AtomicBool::new(false);
+    struct Guard;
+    impl Drop for Guard {
+        fn drop(&mut self) {
+            DROPPED.store(true, Ordering::SeqCst);
+        }
+    }
+
+    let guard = Guard;
+    let futures: [(String, AppFuture); 2] = [
+        (
+            "a".into(),
+            Box::pin(async move {
+                let _guard = guard;
+                std::future::pending::<Void>().await
+            })
+        ),
+        ("b".into(), Box::pin(async { ok() }))
+    ];
+    app_block_on(AppRunAll::new(futures))?;
+    assert!(DROPPED.load(Ordering::SeqCst));
+
+    ok()
+}
@@ -0,0 +1,67 @@
+use {
+    app_base::{app::*, prelude::*},
+    std::{env::set_current_dir, thread::sleep, time::Duration}
+};
+
+type MyApp = App<AppSimpleConfig>;
+
+#[derive(Default)]
+struct SlowModule;
+
+impl AppModuleExt for SlowModule {
+    const COMMAND: &str = "slow";
+
+    type Config = AppSimpleConfig;
+
+    fn boot(&mut self, _app: &mut MyApp) -> Void {
+        sleep(Duration::from_millis(5));
+        ok()
+    }
+}
+
+#[test]
+fn test_app_timings() -> Void {
+    set_current_dir(env!("PWD"))?;
+
+    let mut app = MyApp::new([SlowModule::handle]);
+    app.with_args([("command", SlowModule::COMMAND)])
+        .boot()?
+        .run()?;
+
+    let timings = app.timings();
+    let boot = timings
+        .iter()
+        .find(|t| t.name == "SlowModule" && t.event == AppEvent::APP_BOOT.name())
+        .unwrap();
+
+    assert!(boot.duration >= Duration::from_millis(5));
+    assert!(
+        timings
+            .iter()
+            .any(|t| t.name == "AppConfig" && t.event == "LOAD")
+    );
+    assert!(
+        timings
+            .iter()
+            .any(|t| t.name == "SlowModule" && t.event == "RUN")
+    );
+    assert!(timings.event("BOOT") >= boot.duration);
+    assert!(timings.total() >= timings.event("BOOT"));
+    assert!(
+        timings
+            .sorted()
+            .windows(2)
+            .all(|w| w[0].duration >= w[1].duration)
+    );
+
+    // the same timings are available for other modules
+    let di_timings = app.get::<AppTimings>().unwrap();
+    assert!(std::ptr::eq(di_timings.as_ref(), timings));
+    assert_eq!(di_timings.iter().count(), timings.iter().count());
+
+    let report = timings.to_string();
+    assert!(report.starts_with("Timings (total "));
+    assert!(report.contains("BOOT"));
+
+    ok()
+}
@@ -68,3 +68,57 @@ fn tests_dirs() -> Void {
 
     ok()
 }
+
+#[test]
+fn tests_dirs_mode() -> Void {
+    setenv("XDG_CONFIG_HOME", "/home/foo/.conf/");
+    setenv("XDG_DATA_HOME", "relative/data");
+    setenv("XDG_STATE_HOME", "/home/foo/.state");
+    setenv("XDG_CACHE_HOME", "/home/foo/.cache");
+    setenv("XDG_RUNTIME_DIR", "/run/user/1000");
+
+    let mut dirs = Dirs::with_mode(DirsMode::User);
+    dirs.home = "/home/foo".into();
+    dirs.suffix = "myapp".into();
+    dirs.init();
+
+    assert_eq!(dirs.mode(), DirsMode::User);
+    assert_eq!(&dirs.prefix, "/home/foo/.local");
+    assert_eq!(&dirs.bin, "/home/foo/.local/bin");
+    assert_eq!(&dirs.config, "/home/foo/.conf/myapp");
+    assert_eq!(&dirs.user_config, "/home/foo/.conf/myapp");
+    assert_eq!(&dirs.data, "/home/foo/.local/share/myapp");
+    assert_eq!(&dirs.state, "/home/foo/.state/myapp");
+    assert_eq!(&dirs.log, "/home/foo/.state/myapp/log");
+    assert_eq!(&dirs.cache, "/home/foo/.cache/myapp");
+    assert_eq!(&dirs.run, "/run/user/1000/myapp");
+    assert_eq!(&dirs.tmp, "/run/user/1000/myapp/tmp");
+
+    setenv("XDG_RUNTIME_DIR", "");
+    let mut dirs = Dirs::with_mode(DirsMode::User);
+    dirs.home = "/home/foo".into();
+    dirs.suffix = "myapp".into();
+    dirs.init();
+    assert_eq!(&dirs.run, "/home/foo/.cache/myapp/run");
+    assert_eq!(&dirs.tmp, "/home/foo/.cache/myapp/run/tmp");
+
+    let mut dirs = Dirs::with_mode(DirsMode::System);
+    dirs.cache = "/opt/cache".into();
+    dirs.set_mode(DirsMode::Portable);
+    dirs.init();
+
+    let exe_dir = Dirs::dirname(dirs.exe()).to_string();
+    assert_eq!(dirs.mode(), DirsMode::Portable);
+    assert_eq!(&dirs.prefix, &exe_dir);
+    assert_eq!(&dirs.config, &format!("{exe_dir}/config"));
+    assert_eq!(&dirs.state, &format!("{exe_dir}/var/lib"));
+    assert_eq!(&dirs.tmp, &format!("{exe_dir}/var/tmp"));
+    assert_eq!(&dirs.cache, "/opt/cache");
+
+    assert_eq!("portable".parse::<DirsMode>()?, DirsMode::Portable);
+    assert!("local".parse::<DirsMode>().is_err());
+    assert!(Dirs::access(&exe_dir, libc::W_OK));
+    assert!(Dirs::access("/not/existing/dir", libc::F_OK) == false);
+
+    ok()
+}
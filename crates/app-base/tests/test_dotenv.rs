@@ -0,0 +1,39 @@
+use {app_base::prelude::*, std::fs::write};
+
+#[test]
+fn test_dotenv_layers() -> Void {
+    let dir = concat!(env!("CARGO_TARGET_TMPDIR"), "/dotenv");
+    std::fs::create_dir_all(dir)?;
+
+    write(
+        format!("{dir}/.env"),
+        "A=first\nA=env\nB=env\nC=env\nD=env\nREAL=env"
+    )?;
+    write(format!("{dir}/.env.local"), "B=local\nC=local\nD=local")?;
+    write(format!("{dir}/.env.test"), "C=test\nD=test")?;
+    write(format!("{dir}/.env.test.local"), "D=test.local")?;
+
+    setenv("APP_ENV", "test");
+    setenv("REAL", "real");
+
+    let files = DotEnv::load(dir, false)?;
+
+    assert_eq!(files.len(), 4);
+    assert!(files[0].ends_with("/.env"));
+    assert!(files[3].ends_with("/.env.test.local"));
+    // the last of repeated keys wins
+    assert_eq!(getenv("A").as_deref(), Some("env"));
+    assert_eq!(getenv("B").as_deref(), Some("local"));
+    assert_eq!(getenv("C").as_deref(), Some("test"));
+    assert_eq!(getenv("D").as_deref(), Some("test.local"));
+    assert_eq!(getenv("REAL").as_deref(), Some("real"));
+
+    // Replaces variables of loaded files only
+    write(format!("{dir}/.env.test.local"), "D=changed\nREAL=changed")?;
+    DotEnv::load(dir, true)?;
+
+    assert_eq!(getenv("D").as_deref(), Some("changed"));
+    assert_eq!(getenv("REAL").as_deref(), Some("real"));
+
+    ok()
+}
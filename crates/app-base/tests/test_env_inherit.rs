@@ -0,0 +1,28 @@
+use app_base::prelude::*;
+
+#[test]
+fn test_env_inherit() {
+    setenv("APP_ENV", "test");
+    setenv("APP_ENVS", "dev,prod,qa:dev,test:qa,staging:prod");
+    unsafe { Env::reset() };
+
+    assert!(Env::is("test"));
+    assert!(Env::is("qa") == false);
+    assert!(Env::inherits("qa"));
+    assert!(Env::inherits("dev"));
+    assert!(Env::inherits("prod") == false);
+    assert!(Env::is_test());
+    assert!(Env::is_dev());
+    assert!(Env::is_prod() == false);
+    assert_eq!(Env::chain().join(","), "test,qa,dev");
+    assert_eq!(
+        Env::envs().collect::<Vec<_>>(),
+        ["dev", "prod", "qa", "test", "staging"]
+    );
+
+    // cyclic inheritance
+    setenv("APP_ENVS", "test:loop,loop:test");
+    unsafe { Env::reset() };
+
+    assert_eq!(Env::chain().join(","), "test,loop");
+}
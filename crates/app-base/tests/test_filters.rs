@@ -1,4 +1,7 @@
-use app_base::filters::{base64_decode, base64_encode};
+use app_base::filters::{
+    HtmlSanitizer, Phone, PhoneCountry, PhoneError, base64_decode, base64_encode,
+    html_escape, html_unescape, normalize_phone, sanitize_html, strip_html
+};
 
 #[test]
 fn test_base64() {
@@ -14,3 +17,284 @@ fn test_base64() {
         assert_eq!(str, &decode_str);
     }
 }
+
+#[test]
+fn test_sanitize_html() {
+    let sanitizer = HtmlSanitizer::default();
+
+    for (html, expected) in [
+        ("a < b && c > d", "a &lt; b &amp;&amp; c &gt; d"),
+        (
+            "Tom &amp; Jerry &copy; &#169; &#x1F600;",
+            "Tom &amp; Jerry &copy; &#169; &#x1F600;"
+        ),
+        ("<b>bold</b> <i>italic", "<b>bold</b> <i>italic</i>"),
+        ("<p>text</b></p>", "<p>text</p>"),
+        (
+            "<ul><li>one<li>two</ul>", "<ul><li>one<li>two</li></li></ul>"
+        ),
+        ("<div class=\"x\">text</div>", "text"),
+        ("<p/>text<br/>", "<p></p>text<br>"),
+        ("<script>alert(1)</script>ok", "ok"),
+        ("<SCRIPT >alert(1)</script >ok", "ok"),
+        ("<style>p {}</style><!-- comment -->ok", "ok"),
+        ("<b onclick=\"alert(1)\" title='x'>b</b>", "<b>b</b>"),
+        (
+            "<a href=\"https://example.com?a=1&amp;b=2\" rel=\"x\">link</a>",
+            "<a href=\"https://example.com?a=1&amp;b=2\" rel=\"nofollow\">link</a>"
+        ),
+        (
+            "<a href=\"/page\">link</a>", "<a href=\"/page\" rel=\"nofollow\">link</a>"
+        ),
+        (
+            "<a href=\"javascript:alert(1)\">x</a>", "<a rel=\"nofollow\">x</a>"
+        ),
+        (
+            "<a href=\"java\tscript:alert(1)\">x</a>", "<a rel=\"nofollow\">x</a>"
+        ),
+        (
+            "<a href=\"&#106;avascript:alert(1)\">x</a>", "<a rel=\"nofollow\">x</a>"
+        ),
+        (
+            "<a href=\"x\" title=\"a&quot;b\">x</a>",
+            "<a href=\"x\" title=\"a&quot;b\" rel=\"nofollow\">x</a>"
+        ),
+        ("<img src=x onerror=alert(1)>", ""),
+        ("<b", "&lt;b"),
+        ("<a href=\"x>y", "&lt;a href=\"x&gt;y")
+    ] {
+        assert_eq!(sanitizer.sanitize(html), expected, "{html}");
+    }
+
+    let mut sanitizer = HtmlSanitizer::new();
+    sanitizer
+        .with_tag_attributes("img", ["src", "alt"])
+        .with_attributes(["class"])
+        .with_url_schemes(["https", "data"]);
+
+    assert_eq!(
+        sanitizer.sanitize(
+            "<p class=\"a\"><img src=\"data:image/png;base64,AA\" class=b alt=\"\"></p>"
+        ),
+        "<img src=\"data:image/png;base64,AA\" class=\"b\" alt=\"\">"
+    );
+    assert_eq!(sanitizer.sanitize("<img src=\"http://x\">"), "<img>");
+    assert_eq!(sanitize_html("<em>x</em><div>y</div>"), "<em>x</em>y");
+}
+
+#[test]
+fn test_strip_html() {
+    assert_eq!(strip_html("a < b > c"), "a < b > c");
+    assert_eq!(strip_html("<p>Hello <b>World</b>!</p>"), "Hello World!");
+    assert_eq!(
+        strip_html("x<script>alert('<b>')</script>y<!-- c -->"),
+        "xy"
+    );
+    assert_eq!(
+        html_unescape("&lt;b&gt; &amp;amp; &unknown; &#65;"),
+        "<b> &amp; &unknown; A"
+    );
+    assert_eq!(
+        html_escape("<a href=\"x\">'</a>"),
+        "&lt;a href=&quot;x&quot;&gt;&#39;&lt;/a&gt;"
+    );
+}
+
+#[test]
+fn test_serde_html() {
+    #[derive(serde::Deserialize)]
+    struct User {
+        #[serde(deserialize_with = "app_base::serde::sanitize_html")]
+        about: String,
+        #[serde(default, deserialize_with = "app_base::serde::sanitize_html_option")]
+        signature: Option<String>,
+        #[serde(deserialize_with = "app_base::serde::strip_html")]
+        name: String
+    }
+
+    let user: User = serde_json::from_str(
+        r#"{"about": "<b>Hi</b><script>x</script>", "signature": "<div> </div>", "name": "<i>Bob</i>"}"#
+    )
+    .unwrap();
+
+    assert_eq!(user.about, "<b>Hi</b>");
+    assert_eq!(user.signature, None);
+    assert_eq!(user.name, "Bob");
+}
+
+#[test]
+fn test_phone() {
+    let ru = PhoneCountry::get("ru");
+
+    for (phone, e164) in [
+        ("8 (916) 123-45-67", "+79161234567"),
+        ("+7 916 123 45 67", "+79161234567"),
+        ("79161234567", "+79161234567"),
+        ("9161234567", "+79161234567"),
+        ("00 44 20 7946 0958", "+442079460958"),
+        ("+1 (555) 123-4567", "+15551234567"),
+        ("+380 44 123 4567", "+380441234567")
+    ] {
+        let parsed = Phone::parse_with(phone, ru).unwrap();
+        assert_eq!(parsed.e164(), e164, "{phone}");
+        assert_eq!(parsed.to_string(), e164);
+    }
+
+    for (phone, error) in [
+        ("", PhoneError::Empty),
+        ("call me", PhoneError::InvalidChars),
+        ("+7 916 123", PhoneError::TooShort),
+        ("8 916 123 45 67 89", PhoneError::TooLong),
+        ("+999 123 456 789", PhoneError::UnknownCountry)
+    ] {
+        assert_eq!(Phone::parse_with(phone, ru), Err(error), "{phone}");
+    }
+    assert_eq!(
+        Phone::parse_with("916 123-45-67", None),
+        Err(PhoneError::NoCountry)
+    );
+
+    let phone = Phone::parse_with("89161234567", ru).unwrap();
+    assert_eq!(phone.country().code, "RU");
+    assert_eq!(phone.number(), "9161234567");
+    assert_eq!(phone.international(), "+7 916 123-45-67");
+    assert_eq!(phone.national(), "8 916 123-45-67");
+
+    let phone = Phone::parse_with("+49 30 1234567", ru).unwrap();
+    assert_eq!(phone.country().code, "DE");
+    assert_eq!(phone.international(), "+49 301 234 567");
+    assert_eq!(phone.national(), "0 301 234 567");
+
+    assert_eq!(PhoneCountry::from_locale("en_US.UTF-8").unwrap().code, "US");
+    assert_eq!(PhoneCountry::from_locale("uk_UA").unwrap().code, "UA");
+    assert_eq!(PhoneCountry::from_locale("uk"), None);
+
+    // assigned country codes without numbering plan are valid by E.164 length
+    assert_eq!(normalize_phone("+359 2 123 4567").unwrap(), "+35921234567");
+    assert_eq!(
+        normalize_phone("00 359 88 123 4567").unwrap(),
+        "+359881234567"
+    );
+    assert_eq!(normalize_phone("+359 123"), Err(PhoneError::TooShort));
+    for phone in ["+999 123 456 789", "+0 123 456 789", "+01 212 555 0100"] {
+        assert_eq!(
+            normalize_phone(phone),
+            Err(PhoneError::UnknownCountry),
+            "{phone}"
+        );
+    }
+
+    let default_country = Phone::default_country();
+    Phone::set_default_country(ru);
+    assert_eq!(Phone::default_country(), ru);
+    assert_eq!(normalize_phone("8-916-123-45-67").unwrap(), "+79161234567");
+
+    #[derive(serde::Deserialize)]
+    struct Form {
+        #[serde(deserialize_with = "app_base::serde::phone")]
+        phone: String,
+        #[serde(default, deserialize_with = "app_base::serde::phone_option")]
+        phone2: Option<String>
+    }
+
+    let form: Form =
+        serde_json::from_str(r#"{"phone": "8 (916) 123-45-67", "phone2": " "}"#).unwrap();
+    assert_eq!(form.phone, "+79161234567");
+    assert_eq!(form.phone2, None);
+
+    let e = serde_json::from_str::<Form>(r#"{"phone": "123"}"#).unwrap_err();
+    assert!(e.to_string().starts_with("Phone number is too short"));
+
+    let form: Form = serde_json::from_str(r#"{"phone": "+359 2 123 4567"}"#).unwrap();
+    assert_eq!(form.phone, "+35921234567");
+
+    Phone::set_default_country(default_country);
+}
+
+#[test]
+fn test_derive_filter() {
+    use app_base::{
+        filters::Filter,
+        prelude::Filter,
+        serde::{Filtered, filtered}
+    };
+
+    fn initials(str: &str) -> String {
+        str.split_whitespace()
+            .filter_map(|w| w.chars().next())
+            .collect()
+    }
+
+    #[derive(Debug, Default, serde::Deserialize, Filter)]
+    #[serde(default)]
+    struct Address {
+        #[filter(collapse_ws, capitalize)]
+        city: String
+    }
+
+    #[derive(Debug, Default, serde::Deserialize, Filter)]
+    #[serde(default)]
+    struct UserForm {
+        #[filter(trim, lowercase)]
+        email: String,
+        #[filter(trim)]
+        login: Option<String>,
+        #[filter(phone)]
+        phone: Option<String>,
+        #[filter(strip_html, collapse_ws)]
+        name: Box<str>,
+        #[filter(slug)]
+        slug: String,
+        #[filter(with = "initials")]
+        initials: String,
+        #[filter(trim)]
+        tags: Vec<String>,
+        #[filter(nested)]
+        address: Option<Address>,
+        about: String
+    }
+
+    let json = r#"{
+        "email": "  Bob@Example.COM ",
+        "login": "   ",
+        "phone": "+7 (916) 123-45-67",
+        "name": "<b>Bob</b>   \n Smith",
+        "slug": "Hello World",
+        "initials": "Bob Smith",
+        "tags": [" a ", "  ", "b"],
+        "address": {"city": "  new   york "},
+        "about": "  <b>as is</b> "
+    }"#;
+
+    let mut form: UserForm = serde_json::from_str(json).unwrap();
+    assert_eq!(form.email, "  Bob@Example.COM ");
+    form.filter();
+    assert_eq!(form.email, "bob@example.com");
+    assert_eq!(form.login, None);
+    assert_eq!(form.phone.as_deref(), Some("+79161234567"));
+    assert_eq!(&*form.name, "Bob Smith");
+    assert_eq!(form.slug, "hello_world");
+    assert_eq!(form.initials, "BS");
+    assert_eq!(form.tags, ["a", "b"]);
+    assert_eq!(form.address.as_ref().unwrap().city, "New york");
+    assert_eq!(form.about, "  <b>as is</b> ");
+
+    // filters run during deserialization
+    let form: Filtered<UserForm> = serde_json::from_str(json).unwrap();
+    assert_eq!(form.email, "bob@example.com");
+
+    #[derive(serde::Deserialize)]
+    struct Request {
+        #[serde(deserialize_with = "filtered")]
+        user: UserForm
+    }
+
+    let request: Request =
+        serde_json::from_str(&format!(r#"{{"user": {json}}}"#)).unwrap();
+    assert_eq!(request.user.tags, ["a", "b"]);
+
+    // invalid phone is kept for validation
+    let mut form = UserForm { phone: Some("+7 916".into()), ..Default::default() };
+    form.filter();
+    assert_eq!(form.phone.as_deref(), Some("+7916"));
+}
@@ -0,0 +1,230 @@
+use {
+    app_base::{prelude::*, t},
+    std::{env::temp_dir, fmt::Display, fs}
+};
+
+const CATALOG_RU: &str = r#"
+# header
+msgid ""
+msgstr ""
+"Language: ru\n"
+"Plural-Forms: nplurals=3; plural=(n%10==1 && n%100!=11 ? 0 : n%10>=2 && "
+"n%10<=4 && (n%100<10 || n%100>=20) ? 1 : 2);\n"
+
+msgid "Hello"
+msgstr "Привет"
+
+msgid "Hello, {name}!"
+msgstr "Привет, {name}!"
+
+msgctxt "menu"
+msgid "Open"
+msgstr "Открыть"
+
+msgid "{n} file"
+msgid_plural "{n} files"
+msgstr[0] "{n} файл"
+msgstr[1] "{n} файла"
+msgstr[2] "{n} файлов"
+
+#, fuzzy
+msgid "Fuzzy"
+msgstr "Неточно"
+
+msgid "Multi"
+"line"
+msgstr "Много\n"
+"строк"
+
+msgid "Untranslated"
+msgstr ""
+"#;
+
+#[test]
+fn test_catalog() -> Void {
+    let catalog = Catalog::parse("ru", CATALOG_RU)?;
+
+    assert_eq!(catalog.len(), 6);
+    assert_eq!(catalog.plural().nplurals(), 3);
+    assert_eq!(catalog.get(None, "Hello"), Some("Привет"));
+    assert_eq!(catalog.get(Some("menu"), "Open"), Some("Открыть"));
+    assert_eq!(catalog.get(None, "Open"), None);
+    assert_eq!(catalog.get(None, "Fuzzy"), None);
+    assert_eq!(catalog.get(None, "Multiline"), Some("Много\nстрок"));
+    assert_eq!(catalog.get(None, "Untranslated"), None);
+
+    for (n, form) in [
+        (1, "{n} файл"),
+        (3, "{n} файла"),
+        (11, "{n} файлов"),
+        (21, "{n} файл")
+    ] {
+        assert_eq!(catalog.get_plural(None, "{n} file", n), Some(form));
+    }
+
+    assert_eq!(
+        Catalog::parse("ru", "msgid \"a\"\nmsgstr \"b").unwrap_err(),
+        I18nError::InvalidCatalog("Invalid string at line 2".into())
+    );
+    assert!(Catalog::parse("ru", "msgid \"a\"\nmsgstr[x] \"b\"").is_err());
+
+    ok()
+}
+
+#[test]
+fn test_plural_rule() -> Void {
+    let cases: [(&str, &[(u64, usize)]); 5] = [
+        ("en", &[(0, 1), (1, 0), (2, 1)]),
+        ("fr", &[(0, 0), (1, 0), (2, 1)]),
+        (
+            "ru_RU",
+            &[(1, 0), (2, 1), (5, 2), (12, 2), (22, 1), (101, 0), (111, 2)]
+        ),
+        ("cs", &[(1, 0), (3, 1), (5, 2)]),
+        ("ja", &[(1, 0), (2, 0)])
+    ];
+    for (language, expected) in cases {
+        let rule = PluralRule::for_language(language);
+        for (n, index) in expected {
+            assert_eq!(rule.index(*n), *index, "{language}: {n}");
+        }
+    }
+
+    let rule = PluralRule::parse("nplurals=2; plural=!(n % 2 == 0) + 5 * 0;")?;
+    assert_eq!((rule.index(2), rule.index(3)), (0, 1));
+    // index is limited by nplurals
+    assert_eq!(PluralRule::parse("nplurals=2; plural=n;")?.index(7), 1);
+
+    for invalid in [
+        "plural=n;", "nplurals=2; plural=(n;", "nplurals=2; plural=n $ 1;"
+    ] {
+        assert!(PluralRule::parse(invalid).is_err(), "{invalid}");
+    }
+
+    ok()
+}
+
+#[test]
+fn test_negotiate() {
+    let available = ["en", "ru", "pt_BR"];
+    let cases = [
+        ("ru-RU,ru;q=0.9,en-US;q=0.8,en;q=0.7", Some("ru")),
+        ("de;q=0.9, en;q=0.5, ru;q=0.8", Some("ru")),
+        ("pt-br", Some("pt_BR")),
+        ("pt", Some("pt_BR")),
+        ("en-GB;q=0.1, fr", Some("en")),
+        ("*;q=0.5, en;q=0", Some("ru")),
+        ("ru;q=0, de", None),
+        ("ru;q=abc, en;q=2", None),
+        ("", None)
+    ];
+
+    for (header, expected) in cases {
+        assert_eq!(I18n::negotiate(header, available), expected, "{header}");
+    }
+}
+
+#[test]
+fn test_i18n() -> Void {
+    let dir = format!("{}/test_i18n", temp_dir().display());
+    fs::create_dir_all(format!("{dir}/{}", I18n::DIR_NAME))?;
+    fs::write(format!("{dir}/{}/ru_RU.po", I18n::DIR_NAME), CATALOG_RU)?;
+
+    let mut dirs = Dirs::default();
+    dirs.data = dir.clone();
+    let mut config = BaseConfig::default();
+    config.locales.insert("ru".into(), Some("ru_RU".into()));
+    config.locales.insert("de".into(), None);
+
+    I18n::init(&config, &dirs)?;
+    assert_eq!(I18n::default_language(), "en");
+    assert!(I18n::catalog("ru").is_some());
+    assert!(I18n::catalog("de").is_none());
+    let hello = I18n::translate("ru", None, "Hello");
+
+    // fallback chains
+    assert_eq!(I18n::fallbacks("ru_RU"), ["ru_RU", "ru", "en"]);
+    let mut i18n = I18n::load(&config, &dirs)?;
+    i18n.set_fallbacks("uk", &["ru"]);
+    i18n.install();
+    // messages of the previous registry stay valid
+    assert_eq!(hello, "Привет");
+    assert_eq!(I18n::translate("uk", None, "Hello"), "Привет");
+    assert_eq!(I18n::translate("ru_RU", None, "Hello"), "Привет");
+    assert_eq!(I18n::translate("de", None, "Hello"), "Hello");
+    assert_eq!(
+        I18n::translate_plural("de", None, "{n} file", "{n} files", 2),
+        "{n} files"
+    );
+
+    // macro uses language of the current scope
+    assert_eq!(t!("Hello"), "Hello");
+    I18n::sync_scope("ru", || {
+        assert_eq!(t!("Hello, {name}!", name = "Иван"), "Привет, Иван!");
+        assert_eq!(t!("{n} file", "{n} files", 5), "5 файлов");
+        assert_eq!(t!(context = "menu"; "Open"), "Открыть");
+        assert_eq!(t!(lang = "en"; "Hello"), "Hello");
+    });
+    assert_eq!(t!(lang = "ru", context = "menu"; "Open"), "Открыть");
+    assert_eq!(t!("{n} file", "{n} files", 1), "1 file");
+
+    let future = I18n::scope("ru", async { t!("Hello") });
+    assert_eq!(poll_ready(future), "Привет");
+    assert_eq!(I18n::language(), "en");
+
+    // invalid catalog fails boot
+    fs::write(format!("{dir}/{}/ru_RU.po", I18n::DIR_NAME), "msgid")?;
+    assert!(I18n::init(&config, &dirs).is_err());
+
+    fs::remove_dir_all(&dir)?;
+
+    ok()
+}
+
+#[test]
+fn test_i18n_scope_panic() {
+    use std::{
+        future::poll_fn,
+        panic::{AssertUnwindSafe, catch_unwind},
+        task::Poll
+    };
+
+    let language = I18n::language();
+    let res = catch_unwind(|| I18n::sync_scope("ru", || panic!("test")));
+    assert!(res.is_err());
+    assert_eq!(I18n::language(), language);
+
+    let future = I18n::scope("ru", poll_fn(|_| -> Poll<()> { panic!("test") }));
+    let res = catch_unwind(AssertUnwindSafe(|| poll_ready(future)));
+    assert!(res.is_err());
+    assert_eq!(I18n::language(), language);
+}
+
+#[test]
+fn test_format() {
+    let n = 5;
+    assert_eq!(
+        I18n::format(
+            "{a} and {b}, {unknown} {",
+            &[("a", &"x" as &dyn Display), ("b", &n)]
+        ),
+        "x and 5, {unknown} {"
+    );
+}
+
+/// Polls future which is ready without waiting
+fn poll_ready<F: Future>(future: F) -> F::Output {
+    use std::{
+        pin::pin,
+        task::{Context, Poll, Waker}
+    };
+
+    let mut future = pin!(future);
+    match future
+        .as_mut()
+        .poll(&mut Context::from_waker(Waker::noop()))
+    {
+        Poll::Ready(res) => res,
+        Poll::Pending => panic!("Future is pending")
+    }
+}
@@ -0,0 +1,32 @@
+use {
+    app_base::{app::*, prelude::*, t},
+    std::env::set_current_dir
+};
+
+#[derive(Default)]
+struct IdleModule;
+
+impl AppModuleExt for IdleModule {
+    const COMMAND: &str = "idle";
+
+    type Config = AppSimpleConfig;
+}
+
+#[test]
+fn test_i18n_boot() -> Void {
+    // shipped config and catalogs of the source tree
+    set_current_dir(concat!(env!("CARGO_MANIFEST_DIR"), "/../.."))?;
+
+    let mut app = App::<AppSimpleConfig>::new([IdleModule::handle]);
+    app.with_args([("command", "idle"), ("dirs-config", "config")])
+        .boot()?;
+
+    assert_eq!(I18n::default_language(), "ru");
+    assert!(I18n::catalog("ru").is_some());
+    assert_eq!(
+        t!("Enter the verification code."),
+        "Введите проверочный код."
+    );
+
+    ok()
+}
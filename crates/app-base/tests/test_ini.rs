@@ -0,0 +1,79 @@
+use app_base::prelude::*;
+
+#[test]
+fn test_ini_values() -> Void {
+    let ini: Ini = r#"
+name = app
+empty =
+quoted = "  say \"hi\"\n"
+single = 'it\'s'
+
+[base]
+description = first line
+  second line
+  third line
+locales = ru_RU
+locales = en_US
+filter[] = app
+filter[] = main
+
+[web.auth]
+skip[] = /logout
+"#
+    .parse()?;
+
+    assert_eq!(ini.get("name"), Some(&Some("app".into())));
+    assert_eq!(ini.get("empty"), Some(&None));
+    assert_eq!(ini.get("quoted"), Some(&Some("  say \"hi\"\n".into())));
+    assert_eq!(ini.get("single"), Some(&Some("it's".into())));
+    assert_eq!(
+        ini.get("base.description"),
+        Some(&Some("first line\nsecond line\nthird line".into()))
+    );
+    assert_eq!(ini.get("base.locales"), Some(&Some("en_US".into())));
+    assert_eq!(ini.get_array("base.locales"), None);
+    assert_eq!(ini.get("base.filter"), Some(&Some("app,main".into())));
+    assert_eq!(
+        ini.get_array("web.auth.skip"),
+        Some(&["/logout".into()][..])
+    );
+    assert_eq!(ini.get_array("name"), None);
+
+    ok()
+}
+
+#[test]
+fn test_ini_errors() -> Void {
+    let cases = [
+        (
+            "[base\nname = app", "test.ini:1:6: expected ']' at the end of section name"
+        ),
+        ("name = \"app", "test.ini:1:8: unterminated quoted value"),
+        (
+            "\nname = \"app\" x",
+            "test.ini:2:14: unexpected characters after quoted value"
+        )
+    ];
+
+    for (content, message) in cases {
+        let err = Ini::parse(content, "test.ini").unwrap_err();
+        assert!(matches!(
+            err.downcast_ref::<IniError>(),
+            Some(IniError::Syntax { .. })
+        ));
+        assert_eq!(err.to_string(), message);
+    }
+
+    ok()
+}
+
+#[test]
+fn test_ini_file_not_found() -> Void {
+    let err = Ini::from_file(&"not_exists.ini").unwrap_err();
+    assert!(matches!(
+        err.downcast_ref::<IniError>(),
+        Some(IniError::FileNotFound(..))
+    ));
+
+    ok()
+}
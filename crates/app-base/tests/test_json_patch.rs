@@ -0,0 +1,224 @@
+use {
+    app_base::{prelude::*, serde::*},
+    serde::{Deserialize, Serialize},
+    serde_json::{Value, json}
+};
+
+#[test]
+fn test_merge_patch() {
+    // RFC 7386 examples
+    let cases = [
+        (json!({"a": "b"}), json!({"a": "c"}), json!({"a": "c"})),
+        (
+            json!({"a": "b"}),
+            json!({"b": "c"}),
+            json!({"a": "b", "b": "c"})
+        ),
+        (json!({"a": "b"}), json!({"a": null}), json!({})),
+        (
+            json!({"a": "b", "b": "c"}),
+            json!({"a": null}),
+            json!({"b": "c"})
+        ),
+        (json!({"a": ["b"]}), json!({"a": "c"}), json!({"a": "c"})),
+        (json!({"a": "c"}), json!({"a": ["b"]}), json!({"a": ["b"]})),
+        (
+            json!({"a": {"b": "c"}}),
+            json!({"a": {"b": "d", "c": null}}),
+            json!({"a": {"b": "d"}})
+        ),
+        (
+            json!({"a": [{"b": "c"}]}),
+            json!({"a": [1]}),
+            json!({"a": [1]})
+        ),
+        (json!(["a", "b"]), json!(["c", "d"]), json!(["c", "d"])),
+        (json!({"a": "b"}), json!(["c"]), json!(["c"])),
+        (json!({"a": "foo"}), json!(null), json!(null)),
+        (
+            json!({"e": null}),
+            json!({"a": 1}),
+            json!({"e": null, "a": 1})
+        ),
+        (
+            json!([1, 2]),
+            json!({"a": "b", "c": null}),
+            json!({"a": "b"})
+        ),
+        (
+            json!({}),
+            json!({"a": {"bb": {"ccc": null}}}),
+            json!({"a": {"bb": {}}})
+        )
+    ];
+
+    for (mut target, patch, expected) in cases {
+        let diff = json_merge_diff(&target, &expected);
+        let mut patched = target.clone();
+        patched.merge_patch(diff);
+        assert_eq!(patched, expected);
+
+        target.merge_patch(patch);
+        assert_eq!(target, expected);
+    }
+}
+
+#[test]
+fn test_merge_patch_arrays() {
+    let target = json!({
+        "tags": ["a"],
+        "items": [{"id": 1, "qty": 1}, {"id": 2, "qty": 2}]
+    });
+    let patch = json!({
+        "tags": ["b"],
+        "items": [{"id": 2, "qty": 5, "note": null}, {"id": 3, "qty": 3}]
+    });
+
+    let mut value = target.clone();
+    value.merge_patch_with(patch.clone(), &MergeOptions::default());
+    assert_eq!(value["tags"], json!(["b"]));
+    assert_eq!(value["items"], patch["items"]);
+
+    let mut options = MergeOptions::default();
+    options
+        .with_arrays(ArrayMerge::Append)
+        .with_path("/items", ArrayMerge::ByKey("id".into()));
+
+    let mut value = target.clone();
+    value.merge_patch_with(patch, &options);
+    assert_eq!(
+        value,
+        json!({
+            "tags": ["a", "b"],
+            "items": [{"id": 1, "qty": 1}, {"id": 2, "qty": 5}, {"id": 3, "qty": 3}]
+        })
+    );
+}
+
+#[test]
+fn test_json_patch() -> Void {
+    let mut value = json!({"foo": ["bar", "baz"], "a/b": 1, "m~n": 2});
+
+    let patch: JsonPatch = serde_json::from_value(json!([
+        {"op": "test", "path": "/a~1b", "value": 1},
+        {"op": "add", "path": "/foo/1", "value": "qux"},
+        {"op": "add", "path": "/foo/-", "value": "end"},
+        {"op": "remove", "path": "/m~0n"},
+        {"op": "replace", "path": "/a~1b", "value": 3},
+        {"op": "copy", "from": "/foo/0", "path": "/first"},
+        {"op": "move", "from": "/foo/3", "path": "/last"}
+    ]))?;
+    value.apply_patch(&patch)?;
+    assert_eq!(
+        value,
+        json!({"foo": ["bar", "qux", "baz"], "a/b": 3, "first": "bar", "last": "end"})
+    );
+    assert_eq!(
+        patch.to_json()?[1],
+        json!({"op": "add", "path": "/foo/1", "value": "qux"})
+    );
+
+    // failed patch changes nothing
+    let original = value.clone();
+    let errors = [
+        (
+            json!({"op": "test", "path": "/last", "value": "baz"}),
+            PatchError::TestFailed("/last".into())
+        ),
+        (
+            json!({"op": "remove", "path": "/none"}),
+            PatchError::PathNotFound("/none".into())
+        ),
+        (
+            json!({"op": "replace", "path": "/foo/3", "value": 1}),
+            PatchError::PathNotFound("/foo/3".into())
+        ),
+        (
+            json!({"op": "add", "path": "/foo/01", "value": 1}),
+            PatchError::InvalidIndex("/foo/01".into())
+        ),
+        (
+            json!({"op": "add", "path": "foo", "value": 1}),
+            PatchError::InvalidPointer("foo".into())
+        ),
+        (
+            json!({"op": "move", "from": "/foo", "path": "/foo/0"}),
+            PatchError::InvalidMove("/foo/0".into())
+        )
+    ];
+    for (operation, error) in errors {
+        let patch: JsonPatch = serde_json::from_value(json!([
+            {"op": "remove", "path": "/first"},
+            operation
+        ]))?;
+        assert_eq!(value.apply_patch(&patch), Err(error));
+        assert_eq!(value, original);
+    }
+
+    ok()
+}
+
+#[test]
+fn test_json_diff() -> Void {
+    let a = json!({"name": "a", "tags": ["x", "y", "z"], "meta": {"k": 1, "old": true}});
+    let b =
+        json!({"name": "b", "tags": ["x"], "meta": {"k": 1, "new/key": null}, "n": 1});
+
+    // keys are sorted by serde_json
+    let patch = a.diff_patch(&b);
+    assert_eq!(
+        patch.to_json()?,
+        json!([
+            {"op": "remove", "path": "/meta/old"},
+            {"op": "add", "path": "/meta/new~1key", "value": null},
+            {"op": "add", "path": "/n", "value": 1},
+            {"op": "replace", "path": "/name", "value": "b"},
+            {"op": "remove", "path": "/tags/2"},
+            {"op": "remove", "path": "/tags/1"}
+        ])
+    );
+
+    let mut value = a.clone();
+    value.apply_patch(&patch)?;
+    assert_eq!(value, b);
+
+    let mut value = b.clone();
+    value.apply_patch(&json_diff(&b, &a))?;
+    assert_eq!(value, a);
+
+    assert!(a.diff_patch(&a).is_empty());
+    assert_eq!(
+        json_diff(&json!(1), &Value::Null).0,
+        [PatchOperation::Replace { path: "".into(), value: Value::Null }]
+    );
+
+    ok()
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct User {
+    name: String,
+    email: Option<String>,
+    roles: Vec<String>
+}
+
+#[test]
+fn test_typed_patch() -> Void {
+    let user = User {
+        name: "John".into(),
+        email: Some("john@example.com".into()),
+        roles: vec!["user".into()]
+    };
+
+    let patched = user.json_merge_patched(json!({"email": null, "roles": ["admin"]}))?;
+    assert_eq!(patched.email, None);
+    assert_eq!(patched.roles, ["admin"]);
+
+    let patch = user.json_diff(&patched)?;
+    assert_eq!(user.json_patched(&patch)?, patched);
+
+    // invalid type is not deserialized
+    assert!(user.json_merge_patched(json!({"name": 1})).is_err());
+
+    ok()
+}
@@ -0,0 +1,108 @@
+use {
+    app_base::prelude::*,
+    log::{Level, LevelFilter}
+};
+
+#[test]
+fn test_log_format() -> Void {
+    let mut config = LogConfig::default();
+    assert_eq!(config.format, LogFormat::Text);
+
+    config.extend([("format", Some("json"))]);
+    assert_eq!(config.format, LogFormat::Json);
+    assert_eq!(config.format.to_string(), "json");
+
+    assert!("xml".parse::<LogFormat>().is_err());
+
+    ok()
+}
+
+#[test]
+fn test_log_directives() -> Void {
+    let mut config = LogConfig::default();
+    config.level = LevelFilter::Info;
+    config.filter = Some(
+        [
+            "app=debug", "app_async::db=trace", "actix_web=warn", "!sqlx", "main"
+        ]
+        .map(String::from)
+        .into()
+    );
+
+    // levels are checked by the global logger
+    let logger = log_init();
+    logger.configure(&config)?;
+    let level = |target: &str| {
+        Level::iter()
+            .rev()
+            .find(|&level| log::log_enabled!(target: target, level))
+            .map(|level| level.to_level_filter())
+            .unwrap_or(LevelFilter::Off)
+    };
+
+    assert_eq!(config.directives()?.0, LevelFilter::Off);
+    assert_eq!(level("app::main_module"), LevelFilter::Debug);
+    assert_eq!(level("app_async::db::db_pool"), LevelFilter::Trace);
+    assert_eq!(level("app_async::queue"), LevelFilter::Debug);
+    assert_eq!(level("actix_web::middleware"), LevelFilter::Warn);
+    assert_eq!(level("sqlx::query"), LevelFilter::Off);
+    assert_eq!(level("main"), LevelFilter::Info);
+    assert_eq!(level("tokio"), LevelFilter::Off);
+
+    // global level changes directives without level only
+    logger.set_level(LevelFilter::Trace);
+    assert_eq!(level("main"), LevelFilter::Trace);
+    assert_eq!(level("actix_web::middleware"), LevelFilter::Warn);
+    assert_eq!(level("tokio"), LevelFilter::Off);
+    logger.configure(&LogConfig::default())?;
+
+    config.filter = Some(["warn", "!sqlx"].map(String::from).into());
+    assert_eq!(
+        config.directives()?,
+        (LevelFilter::Warn, vec![("sqlx".into(), LevelFilter::Off)])
+    );
+
+    config.filter = Some(["!sqlx"].map(String::from).into());
+    assert_eq!(config.directives()?.0, LevelFilter::Info);
+
+    // typo of level fails configuration
+    config.filter = Some(["app=trcae"].map(String::from).into());
+    assert_eq!(
+        config.directives().unwrap_err().to_string(),
+        "Unknown log level 'trcae' of filter: app=trcae"
+    );
+    assert!(logger.configure(&config).is_err());
+
+    ok()
+}
+
+#[test]
+fn test_log_sinks() -> Void {
+    let mut config = LogConfig::default();
+    assert_eq!(config.sinks()?[0].name, "stderr");
+
+    config.file = Some("app.log".into());
+    assert_eq!(config.sinks()?[0].name, "file");
+
+    config.sinks = Some(["stderr:warn".into(), "file:json:debug".into()].into());
+    assert_eq!(
+        config.sinks()?,
+        [
+            LogSinkConfig {
+                name: "stderr".into(),
+                level: Some(LevelFilter::Warn),
+                format: None
+            },
+            LogSinkConfig {
+                name: "file".into(),
+                level: Some(LevelFilter::Debug),
+                format: Some(LogFormat::Json)
+            }
+        ]
+    );
+
+    config.sinks = Some(["stderr:xml".into()].into());
+    assert!(config.sinks().is_err());
+
+    ok()
+}
@@ -0,0 +1,81 @@
+use {
+    app_base::prelude::*,
+    std::{
+        future::poll_fn,
+        panic::{AssertUnwindSafe, catch_unwind},
+        pin::pin,
+        task::{Context, Poll, Waker}
+    }
+};
+
+fn keys() -> Vec<String> {
+    LogContext::with(|fields| fields.iter().map(|(k, v)| format!("{k}={v}")).collect())
+}
+
+#[test]
+fn test_log_context_sync() {
+    LogContext::sync_scope([("request_id", "1".into())], || {
+        assert_eq!(keys(), ["request_id=1"]);
+
+        LogContext::sync_scope([("task_id", "2".into())], || {
+            assert_eq!(keys(), ["request_id=1", "task_id=2"]);
+        });
+
+        assert_eq!(keys(), ["request_id=1"]);
+    });
+
+    assert!(keys().is_empty());
+
+    // Fields aren't kept on the thread outside of scopes
+    LogContext::insert("user_id", "3".into());
+    assert!(keys().is_empty());
+}
+
+#[test]
+fn test_log_context_future() {
+    let mut polls = 0;
+    let future = LogContext::scope(
+        [("request_id", "1".into())],
+        poll_fn(|_| {
+            polls += 1;
+            if polls == 1 {
+                assert_eq!(keys(), ["request_id=1"]);
+                LogContext::insert("user_id", "3".into());
+                Poll::Pending
+            } else {
+                assert_eq!(keys(), ["request_id=1", "user_id=3"]);
+                Poll::Ready(())
+            }
+        })
+    );
+    let mut future = pin!(future);
+    let mut cx = Context::from_waker(Waker::noop());
+
+    assert!(future.as_mut().poll(&mut cx).is_pending());
+    // Fields are not visible outside of poll
+    assert!(keys().is_empty());
+    assert!(future.as_mut().poll(&mut cx).is_ready());
+    assert!(keys().is_empty());
+}
+
+#[test]
+fn test_log_context_panic() {
+    let res = catch_unwind(|| {
+        LogContext::sync_scope([("request_id", "1".into())], || panic!("test"))
+    });
+    assert!(res.is_err());
+    assert!(keys().is_empty());
+
+    let mut future = pin!(LogContext::scope(
+        [("request_id", "2".into())],
+        poll_fn(|_| -> Poll<()> { panic!("test") })
+    ));
+    let mut cx = Context::from_waker(Waker::noop());
+    let res = catch_unwind(AssertUnwindSafe(|| future.as_mut().poll(&mut cx)));
+    assert!(res.is_err());
+    assert!(keys().is_empty());
+
+    // the thread is out of scopes again
+    LogContext::insert("user_id", "3".into());
+    assert!(keys().is_empty());
+}
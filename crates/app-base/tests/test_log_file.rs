@@ -0,0 +1,88 @@
+use {
+    app_base::prelude::*,
+    std::{fs, path::Path, sync::Arc, thread}
+};
+
+#[test]
+fn test_log_file_rotate() -> Void {
+    let dir = concat!(env!("CARGO_TARGET_TMPDIR"), "/log_rotate");
+    let _ = fs::remove_dir_all(dir);
+    let path = format!("{dir}/app.log");
+
+    let mut config = LogConfig::default();
+    config.rotate_size = 20;
+    config.rotate_keep = 2;
+
+    let file = LogFile::open(&path, &config)?;
+    for n in 0..4 {
+        file.write(&format!("line {n} 0123456789\n"));
+    }
+    file.close();
+
+    assert_eq!(fs::read_to_string(&path)?, "line 3 0123456789\n");
+    assert_eq!(
+        fs::read_to_string(format!("{path}.1"))?,
+        "line 2 0123456789\n"
+    );
+    assert_eq!(
+        fs::read_to_string(format!("{path}.2"))?,
+        "line 1 0123456789\n"
+    );
+    assert!(Path::new(&format!("{path}.3")).exists() == false);
+
+    config.rotate_compress = true;
+    let file = LogFile::open(&path, &config)?;
+    file.rotate()?;
+    file.close();
+
+    assert!(Path::new(&format!("{path}.1.gz")).exists());
+    assert!(Path::new(&format!("{path}.1")).exists() == false);
+    assert_eq!(
+        fs::read_to_string(format!("{path}.2"))?,
+        "line 2 0123456789\n"
+    );
+
+    // rotation by write compresses the rotated file too
+    let file = LogFile::open(&path, &config)?;
+    file.write("line 4 0123456789\n");
+    file.write("line 5 0123456789\n");
+    file.close();
+
+    assert_eq!(fs::read_to_string(&path)?, "line 5 0123456789\n");
+    assert!(Path::new(&format!("{path}.1.gz")).exists());
+    assert!(Path::new(&format!("{path}.2.gz")).exists());
+
+    ok()
+}
+
+#[test]
+fn test_log_file_close() -> Void {
+    let dir = concat!(env!("CARGO_TARGET_TMPDIR"), "/log_close");
+    let _ = fs::remove_dir_all(dir);
+    let path = format!("{dir}/app.log");
+
+    // closing waits for writes of other threads
+    let file = Arc::new(LogFile::open(&path, &LogConfig::default())?);
+    let writers = (0..4)
+        .map(|_| {
+            let file = file.clone();
+            thread::spawn(move || (0..1000).for_each(|_| file.write("line\n")))
+        })
+        .collect::<Vec<_>>();
+    file.close();
+    writers.into_iter().for_each(|w| w.join().unwrap());
+    let content = fs::read_to_string(&path)?;
+    assert!(content.lines().all(|line| line == "line"));
+
+    // SIGHUP doesn't open the closed file again
+    unsafe { libc::raise(libc::SIGHUP) };
+    file.write("after close\n");
+    assert_eq!(fs::read_to_string(&path)?, content);
+
+    file.reopen()?;
+    file.write("reopened\n");
+    file.close();
+    assert_eq!(fs::read_to_string(&path)?, content + "reopened\n");
+
+    ok()
+}
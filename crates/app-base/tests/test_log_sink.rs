@@ -0,0 +1,105 @@
+use {
+    app_base::prelude::*,
+    log::LevelFilter,
+    std::sync::{
+        Arc, Mutex,
+        mpsc::{Receiver, SyncSender, sync_channel}
+    }
+};
+
+#[derive(Default, Clone)]
+struct MemorySink(Arc<Mutex<Vec<String>>>);
+
+impl LogSink for MemorySink {
+    fn write(&self, out: &[u8]) {
+        self.0
+            .lock()
+            .unwrap()
+            .push(String::from_utf8_lossy(out).into());
+    }
+}
+
+/// Sink which blocks the writer on the first record until it's released
+struct GateSink {
+    memory: MemorySink,
+    entered: Mutex<SyncSender<()>>,
+    release: Mutex<Receiver<()>>
+}
+
+impl LogSink for GateSink {
+    fn write(&self, out: &[u8]) {
+        if self.memory.0.lock().unwrap().is_empty() {
+            let _ = self.entered.lock().unwrap().send(());
+            let _ = self.release.lock().unwrap().recv();
+        }
+        self.memory.write(out);
+    }
+}
+
+#[test]
+fn test_log_writer() -> Void {
+    let memory = MemorySink::default();
+    let sinks = Arc::new(Vec::from([LogSinkEntry {
+        name: "memory".into(),
+        level: LevelFilter::Trace,
+        format: LogFormat::Text,
+        color: false,
+        sink: Box::new(memory.clone())
+    }]));
+
+    let writer = LogWriter::start(sinks, 16, LogOverflow::Block)?;
+    for n in 0..100 {
+        assert!(
+            writer
+                .send(vec![(0, format!("{n}").into_bytes())])
+                .is_none()
+        );
+    }
+    writer.flush();
+    assert_eq!(memory.0.lock().unwrap().len(), 100);
+
+    writer.stop();
+    assert!(writer.send(vec![(0, b"after stop".to_vec())]).is_some());
+    assert_eq!(writer.dropped(), 0);
+
+    ok()
+}
+
+#[test]
+fn test_log_writer_overflow() -> Void {
+    let memory = MemorySink::default();
+    let (entered, wait_entered) = sync_channel(1);
+    let (release, wait_release) = sync_channel(1);
+    let sinks = Arc::new(Vec::from([LogSinkEntry {
+        name: "gate".into(),
+        level: LevelFilter::Trace,
+        format: LogFormat::Text,
+        color: false,
+        sink: Box::new(GateSink {
+            memory: memory.clone(),
+            entered: entered.into(),
+            release: wait_release.into()
+        })
+    }]));
+
+    let writer = LogWriter::start(sinks, 2, LogOverflow::Drop)?;
+    assert!(writer.send(vec![(0, b"0".to_vec())]).is_none());
+    wait_entered.recv()?;
+
+    // the writer is blocked by the first record, the buffer keeps two more
+    for n in 1..10 {
+        assert!(
+            writer
+                .send(vec![(0, format!("{n}").into_bytes())])
+                .is_none()
+        );
+    }
+    assert_eq!(writer.dropped(), 7);
+
+    release.send(())?;
+    writer.stop();
+    assert_eq!(*memory.0.lock().unwrap(), ["0", "1", "2"]);
+    assert_eq!(writer.dropped(), 7);
+
+    ok()
+}
@@ -0,0 +1,74 @@
+use {
+    app_base::prelude::*,
+    log::{Level, Record},
+    std::{net::UdpSocket, os::unix::net::UnixDatagram}
+};
+
+#[test]
+fn test_journald_sink() -> Void {
+    let path = concat!(env!("CARGO_TARGET_TMPDIR"), "/journald.socket");
+    let _ = std::fs::remove_file(path);
+    let socket = UnixDatagram::bind(path)?;
+
+    let mut config = LogConfig::default();
+    config.journald = path.into();
+    config.ident = "test-app".into();
+
+    let sink = JournaldSink::new(&config)?;
+    let record = Record::builder()
+        .args(format_args!("first\nsecond"))
+        .level(Level::Warn)
+        .target("app::test")
+        .file(Some("src/test.rs"))
+        .line(Some(10))
+        .build();
+    sink.write(&sink.format(&record).unwrap());
+
+    let mut buf = [0u8; 1024];
+    let len = socket.recv(&mut buf)?;
+    let data = &buf[..len];
+
+    let mut message = b"MESSAGE\n".to_vec();
+    message.extend_from_slice(&12u64.to_le_bytes());
+    message.extend_from_slice(b"first\nsecond\n");
+    assert!(data.starts_with(&message));
+
+    let text = String::from_utf8_lossy(&data[message.len()..]);
+    assert!(text.contains("PRIORITY=4\n"));
+    assert!(text.contains("SYSLOG_IDENTIFIER=test-app\n"));
+    assert!(text.contains("TARGET=app::test\n"));
+    assert!(text.contains("CODE_FILE=src/test.rs\n"));
+    assert!(text.contains("CODE_LINE=10\n"));
+
+    ok()
+}
+
+#[test]
+fn test_syslog_sink() -> Void {
+    let socket = UdpSocket::bind("127.0.0.1:0")?;
+
+    let mut config = LogConfig::default();
+    config.syslog = format!("udp:{}", socket.local_addr()?);
+    config.ident = "test app".into();
+    config.syslog_facility = 16;
+
+    let sink = SyslogSink::new(&config)?;
+    let record = Record::builder()
+        .args(format_args!("hello"))
+        .level(Level::Error)
+        .target("app\"test]")
+        .build();
+    sink.write(&sink.format(&record).unwrap());
+
+    let mut buf = [0u8; 1024];
+    let len = socket.recv(&mut buf)?;
+    let text = String::from_utf8_lossy(&buf[..len]);
+
+    assert!(text.starts_with("<131>1 "));
+    assert!(text.contains(&format!(" testapp {} - ", std::process::id())));
+    assert!(text.ends_with(r#"[log@32473 target="app\"test\]"] hello"#));
+
+    assert!(SyslogSink::new(&LogConfig { syslog: "tcp:1".into(), ..config }).is_err());
+
+    ok()
+}
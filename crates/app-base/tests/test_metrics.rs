@@ -0,0 +1,57 @@
+use app_base::prelude::*;
+
+#[test]
+fn test_metrics() {
+    let metrics = Metrics::from_static();
+
+    let counter = metrics.counter("test_total", "Test counter", &[("kind", "a\"b")]);
+    counter.inc();
+    counter.inc_by(2);
+    // the same series is returned
+    metrics
+        .counter("test_total", "Test counter", &[("kind", "a\"b")])
+        .inc();
+    assert_eq!(counter.get(), 4);
+
+    let gauge = metrics.gauge("test_gauge", "Test gauge", &[]);
+    gauge.set(1.5);
+    gauge.dec();
+    assert_eq!(gauge.get(), 0.5);
+
+    let histogram = metrics.histogram(
+        "test_seconds",
+        "Test histogram",
+        &[("op", "x")],
+        &[1.0, 0.1]
+    );
+    for value in [0.0625, 0.5, 0.75, 3.0] {
+        histogram.observe(value);
+    }
+    assert_eq!(histogram.buckets(), [(0.1, 1), (1.0, 3)]);
+    assert_eq!(histogram.count(), 4);
+    assert_eq!(histogram.sum(), 4.3125);
+
+    // registered kind is kept
+    let other = metrics.gauge("test_total", "Test gauge", &[]);
+    other.set(10.0);
+    assert_eq!(counter.get(), 4);
+
+    let text = metrics.render();
+    for line in [
+        "# HELP test_total Test counter\n# TYPE test_total counter\n",
+        "test_total{kind=\"a\\\"b\"} 4\n",
+        "# TYPE test_gauge gauge\ntest_gauge 0.5\n",
+        "# TYPE test_seconds histogram\n",
+        "test_seconds_bucket{op=\"x\",le=\"0.1\"} 1\n",
+        "test_seconds_bucket{op=\"x\",le=\"1\"} 3\n",
+        "test_seconds_bucket{op=\"x\",le=\"+Inf\"} 4\n",
+        "test_seconds_sum{op=\"x\"} 4.3125\n",
+        "test_seconds_count{op=\"x\"} 4\n",
+        "# TYPE process_resident_memory_bytes gauge\n"
+    ] {
+        assert!(text.contains(line), "{line}");
+    }
+
+    metrics.unregister("test_total");
+    assert!(metrics.render().contains("test_total") == false);
+}
@@ -0,0 +1,31 @@
+use {
+    app_base::prelude::*,
+    std::{
+        panic::{AssertUnwindSafe, catch_unwind},
+        sync::Arc,
+        thread
+    }
+};
+
+#[test]
+fn test_spin_lock() {
+    let lock = Arc::new(SpinLock::new(0));
+    let threads = (0..4)
+        .map(|_| {
+            let lock = lock.clone();
+            thread::spawn(move || (0..1000).for_each(|_| *lock.lock() += 1))
+        })
+        .collect::<Vec<_>>();
+    threads.into_iter().for_each(|t| t.join().unwrap());
+    assert_eq!(*lock.lock(), 4000);
+
+    // panic under the lock unlocks it
+    let res = catch_unwind(AssertUnwindSafe(|| {
+        lock.with(|value| {
+            *value += 1;
+            panic!("test")
+        })
+    }));
+    assert!(res.is_err());
+    assert_eq!(lock.with(|value| *value), 4001);
+}
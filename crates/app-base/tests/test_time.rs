@@ -0,0 +1,272 @@
+use {
+    app_base::prelude::*,
+    std::{env::temp_dir, fs, sync::Arc}
+};
+
+/// 2024-01-15T12:00:00Z
+const WINTER: i64 = 1705320000;
+/// 2024-07-01T12:00:00Z
+const SUMMER: i64 = 1719835200;
+/// 2024-02-29T21:30:00Z
+const LEAP_DAY: i64 = 1709242200;
+
+#[test]
+fn test_timezone_fixed() -> Void {
+    for (name, offset) in [
+        ("UTC", 0),
+        ("", 0),
+        ("+03:00", 10800),
+        ("-0530", -19800),
+        ("+7", 25200)
+    ] {
+        let timezone = Timezone::load(name)?;
+        assert_eq!(timezone.offset(SUMMER), offset, "{name}");
+        assert_eq!(timezone.name(), name);
+    }
+
+    assert_eq!(Timezone::load("+03:00")?.local_type(0).abbr, "+03");
+    assert_eq!(Timezone::load("-0930")?.local_type(0).abbr, "-0930");
+
+    for name in ["Nowhere/City", "../etc/passwd", "+25:00"] {
+        assert_eq!(
+            Timezone::load(name).unwrap_err(),
+            TimeError::UnknownTimezone(name.into())
+        );
+    }
+
+    ok()
+}
+
+#[test]
+fn test_timezone_rule() -> Void {
+    let berlin = Timezone::from_rule("Europe/Berlin", "CET-1CEST,M3.5.0,M10.5.0/3")?;
+    assert_eq!(berlin.offset(WINTER), 3600);
+    assert_eq!(berlin.offset(SUMMER), 7200);
+    assert_eq!(berlin.local_type(SUMMER).abbr, "CEST");
+
+    // 2024-03-31T01:00:00Z and 2024-10-27T01:00:00Z
+    assert_eq!(berlin.offset(1711846800 - 1), 3600);
+    assert_eq!(berlin.offset(1711846800), 7200);
+    assert_eq!(berlin.offset(1729990800 - 1), 7200);
+    assert_eq!(berlin.offset(1729990800), 3600);
+
+    // southern hemisphere
+    let sydney = Timezone::from_rule("Australia/Sydney", "AEST-10AEDT,M10.1.0,M4.1.0/3")?;
+    assert_eq!(sydney.offset(WINTER), 39600);
+    assert_eq!(sydney.offset(SUMMER), 36000);
+
+    let india = Timezone::from_rule("Asia/Kolkata", "IST-5:30")?;
+    assert_eq!(india.offset(SUMMER), 19800);
+    let quoted = Timezone::from_rule("America/Sao_Paulo", "<-03>3")?;
+    assert_eq!(quoted.local_type(SUMMER).abbr, "-03");
+
+    assert!(Timezone::from_rule("x", "CET-1CEST,M13.5.0,M10.5.0").is_err());
+    assert!(Timezone::from_rule("x", "C-1").is_err());
+
+    ok()
+}
+
+#[test]
+fn test_timezone_tzif() -> Void {
+    let data = tzif(
+        &[(1277942400, 1), (1301184000, 0)],
+        &[(10800, false, 0), (14400, true, 4)],
+        b"MSK\0MSD\0",
+        "MSK-3"
+    );
+    let timezone = Timezone::parse("Test/Moscow", &data)?;
+
+    assert_eq!(timezone.offset(0), 10800);
+    assert_eq!(timezone.local_type(1277942400).abbr, "MSD");
+    assert_eq!(timezone.offset(1301184000 - 1), 14400);
+    // footer rule after the last transition
+    assert_eq!(timezone.offset(SUMMER), 10800);
+
+    let path = format!("{}/test_time_tzif", temp_dir().display());
+    fs::write(&path, &data)?;
+    assert_eq!(
+        Timezone::load_file("Test/Moscow", &path)?.offset(1277942400),
+        14400
+    );
+    // paths of users and templates are rejected
+    for name in [path.clone(), format!(":{path}"), ":/dev/zero".into()] {
+        assert_eq!(
+            Timezone::load(&name).unwrap_err(),
+            TimeError::UnknownTimezone(name)
+        );
+    }
+    fs::write(
+        &path,
+        [data.as_slice(), &[0; Timezone::MAX_FILE_SIZE]].concat()
+    )?;
+    assert_eq!(
+        Timezone::load_file("x", &path).unwrap_err(),
+        TimeError::InvalidTimezone("File is too large: x".into())
+    );
+    fs::remove_file(&path)?;
+
+    assert_eq!(
+        Timezone::parse("x", &data[..100]).unwrap_err(),
+        TimeError::InvalidTimezone("Unexpected end of data: x".into())
+    );
+    assert!(Timezone::parse("x", b"TZ").is_err());
+
+    ok()
+}
+
+#[test]
+fn test_timezone_tzif_dst() -> Void {
+    // 2010-03-28T01:00:00Z and 2010-10-31T01:00:00Z
+    let data = tzif(
+        &[(1269738000, 1), (1288486800, 0)],
+        &[(3600, false, 0), (7200, true, 4)],
+        b"CET\0CEST\0",
+        "CET-1CEST,M3.5.0,M10.5.0/3"
+    );
+    let berlin = Timezone::parse("Test/Berlin", &data)?;
+
+    // 02:00 CET springs forward to 03:00 CEST
+    let time = berlin.at(1269738000 - 1, 0);
+    assert_eq!((time.time(), time.abbr()), ((1, 59, 59), "CET"));
+    let time = berlin.at(1269738000, 0);
+    assert_eq!((time.time(), time.abbr()), ((3, 0, 0), "CEST"));
+    assert!(time.is_dst());
+
+    // 03:00 CEST falls back to 02:00 CET
+    let time = berlin.at(1288486800 - 1, 0);
+    assert_eq!((time.time(), time.abbr()), ((2, 59, 59), "CEST"));
+    let time = berlin.at(1288486800, 0);
+    assert_eq!((time.time(), time.abbr()), ((2, 0, 0), "CET"));
+    assert_eq!(time.is_dst(), false);
+
+    // footer rule after the last transition, 2030-03-31T01:00:00Z and
+    // 2030-10-27T01:00:00Z
+    assert_eq!(berlin.offset(1901149200 - 1), 3600);
+    assert_eq!(
+        berlin.at(1901149200, 0).to_string(),
+        "2030-03-31T03:00:00+02:00"
+    );
+    assert_eq!(berlin.offset(1919293200 - 1), 7200);
+    assert_eq!(
+        berlin.at(1919293200, 0).to_string(),
+        "2030-10-27T02:00:00+01:00"
+    );
+
+    ok()
+}
+
+#[test]
+fn test_timezone_tzif_malformed() -> Void {
+    let data = tzif(&[(0, 0)], &[(3600, false, 0)], b"CET\0", "CET-1");
+    assert!(Timezone::parse("x", &data).is_ok());
+
+    let invalid = |e: &str| TimeError::InvalidTimezone(format!("{e}: x"));
+    let parse = |data: &[u8]| Timezone::parse("x", data).unwrap_err();
+
+    assert_eq!(parse(b""), invalid("Invalid header"));
+    assert_eq!(
+        parse(&[b"TZfi", &data[4..]].concat()),
+        invalid("Invalid header")
+    );
+    assert_eq!(parse(&data[..40]), invalid("Invalid header"));
+    assert_eq!(
+        parse(&data[..data.len() - 20]),
+        invalid("Unexpected end of data")
+    );
+
+    let data = tzif(&[(0, 1)], &[(3600, false, 0)], b"CET\0", "CET-1");
+    assert_eq!(parse(&data), invalid("Invalid type index"));
+
+    let data = tzif(&[], &[], b"", "");
+    assert_eq!(parse(&data), invalid("Unexpected end of data"));
+
+    for footer in ["CET", "CET-1CEST,M3.5.0", "CET-1CEST,J60,J300"] {
+        let data = tzif(&[(0, 0)], &[(3600, false, 0)], b"CET\0", footer);
+        assert_eq!(parse(&data), invalid("Invalid footer"), "{footer}");
+    }
+
+    ok()
+}
+
+#[test]
+fn test_date_time() -> Void {
+    let time = DateTime::utc(LEAP_DAY, 250_000_000);
+    assert_eq!(time.to_string(), "2024-02-29T21:30:00.250Z");
+    assert_eq!(time.date(), (2024, 2, 29));
+    assert_eq!((time.ordinal(), time.weekday()), (59, 4));
+
+    let time = time.with_timezone(&Timezone::load("+03:00")?);
+    assert_eq!(time.to_string(), "2024-03-01T00:30:00.250+03:00");
+    assert_eq!(time.time(), (0, 30, 0));
+    assert_eq!((time.ordinal(), time.weekday()), (60, 5));
+    assert_eq!(time.format("%F %T %z %Z"), "2024-03-01 00:30:00 +0300 +03");
+    assert_eq!(
+        serde_json::to_value(&time)?,
+        "2024-03-01T00:30:00.250+03:00"
+    );
+
+    let berlin = Timezone::from_rule("Europe/Berlin", "CET-1CEST,M3.5.0,M10.5.0/3")?;
+    let time = berlin.at(SUMMER, 0);
+    assert!(time.is_dst());
+    assert_eq!(time.format("%d.%m.%Y %H:%M %Z"), "01.07.2024 14:00 CEST");
+    assert_eq!(DateTime::utc(-1, 0).to_string(), "1969-12-31T23:59:59Z");
+
+    ok()
+}
+
+#[test]
+fn test_time() -> Void {
+    let mut config = BaseConfig::default();
+    config.timezone = "+05:00".into();
+    Time::init(&config)?;
+
+    let now = Time::now();
+    assert_eq!(now.offset(), 18000);
+    assert!((now.timestamp() - Time::now_utc().timestamp()).abs() <= 1);
+    assert_eq!(Time::now_in("-02:00")?.offset(), -7200);
+    assert!(Arc::ptr_eq(&Time::zone("+04:00")?, &Time::zone(" +04:00")?));
+
+    config.timezone = "Nowhere/City".into();
+    assert!(Time::init(&config).is_err());
+    assert_eq!(Time::timezone().name(), "+05:00");
+
+    ok()
+}
+
+/// TZif v2 file with empty v1 block
+fn tzif(
+    transitions: &[(i64, u8)],
+    types: &[(i32, bool, u8)],
+    chars: &[u8],
+    footer: &str
+) -> Vec<u8> {
+    let header = |counts: [u32; 6]| {
+        let mut out = b"TZif2".to_vec();
+        out.extend([0; 15]);
+        counts.iter().for_each(|n| out.extend(n.to_be_bytes()));
+        out
+    };
+
+    let mut out = header([0, 0, 0, 0, 1, 1]);
+    out.extend([0, 0, 0, 0, 0, 0, 0]);
+    out.extend(header([
+        0,
+        0,
+        0,
+        transitions.len() as u32,
+        types.len() as u32,
+        chars.len() as u32
+    ]));
+    transitions
+        .iter()
+        .for_each(|(time, _)| out.extend(time.to_be_bytes()));
+    transitions.iter().for_each(|(_, index)| out.push(*index));
+    for (offset, dst, abbr) in types {
+        out.extend(offset.to_be_bytes());
+        out.extend([*dst as u8, *abbr]);
+    }
+    out.extend(chars);
+    out.extend(format!("\n{footer}\n").bytes());
+
+    out
+}
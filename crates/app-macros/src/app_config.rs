@@ -0,0 +1,365 @@
+use {
+    alloc::{
+        string::{String, ToString},
+        vec::Vec
+    },
+    proc_macro::TokenStream,
+    proc_macro2::TokenStream as TokenStream2,
+    quote::{format_ident, quote},
+    syn::{
+        Attribute, Data, DataStruct, DeriveInput, Fields, FieldsNamed, GenericArgument,
+        LitStr, PathArguments, Type, parse_macro_input
+    }
+};
+#[cfg(not(feature = "std"))]
+#[allow(unused_imports)]
+use libc_print::std_name::*;
+
+#[derive(Default)]
+pub(crate) struct AppConfig;
+
+/// Options of field from `#[config(...)]`
+#[derive(Default)]
+struct FieldConfig {
+    nested: bool,
+    skip: bool,
+    secret: bool,
+    no_arg: bool,
+    no_env: bool,
+    flag: bool,
+    arg: Option<String>,
+    short: Option<String>,
+    env: Option<String>,
+    dir: Option<String>
+}
+
+impl AppConfig {
+    pub fn derive(self, input: TokenStream) -> TokenStream {
+        let input = parse_macro_input!(input as DeriveInput);
+        let expanded = self
+            .parse(input)
+            .unwrap_or_else(syn::Error::into_compile_error);
+        TokenStream::from(expanded)
+    }
+
+    fn parse(&self, input: DeriveInput) -> syn::Result<TokenStream2> {
+        let struct_name = &input.ident;
+        let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+        let Data::Struct(DataStruct {
+            fields: Fields::Named(FieldsNamed { named, .. }),
+            ..
+        }) = &input.data
+        else {
+            return Err(syn::Error::new_spanned(
+                &input.ident, "Only structs with named fields are supported"
+            ));
+        };
+
+        let (mut prefix, mut env_prefix, mut arg_prefix) = (String::new(), None, None);
+        let (mut command, mut after_args) = (None, None);
+        for attr in input.attrs.iter().filter(|a| a.path().is_ident("config")) {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("prefix") {
+                    prefix = meta.value()?.parse::<LitStr>()?.value();
+                } else if meta.path.is_ident("env_prefix") {
+                    env_prefix = Some(meta.value()?.parse::<LitStr>()?.value());
+                } else if meta.path.is_ident("arg_prefix") {
+                    arg_prefix = Some(meta.value()?.parse::<LitStr>()?.value());
+                } else if meta.path.is_ident("command") {
+                    command = Some(meta.value()?.parse::<LitStr>()?.value());
+                } else if meta.path.is_ident("after_args") {
+                    after_args = Some(meta.value()?.parse::<LitStr>()?.value());
+                } else {
+                    return Err(meta.error(
+                        "Unknown option, expected one of: prefix, env_prefix, \
+                         arg_prefix, command, after_args"
+                    ));
+                }
+                Ok(())
+            })?;
+        }
+
+        let (mut iter, mut init_args, mut load_args, mut load_env, mut load_dirs) =
+            (Vec::new(), Vec::new(), Vec::new(), Vec::new(), Vec::new());
+
+        for field in named {
+            let ident = field.ident.as_ref().unwrap();
+            let name = ident.to_string();
+            let config = Self::field_config(&field.attrs)?;
+            let cfg = field.attrs.iter().filter(|a| a.path().is_ident("cfg"));
+            let cfg = quote! { #(#cfg)* };
+
+            if config.skip {
+                continue;
+            }
+
+            if config.nested {
+                let is_arc = Self::type_name(&field.ty).0 == "Arc";
+                let (field_ref, field_mut) = match is_arc {
+                    true => {
+                        (
+                            quote! { &*self.#ident },
+                            quote! { ::app_base::prelude::TryMut::try_mut(&mut self.#ident).unwrap() }
+                        )
+                    },
+                    false => (quote! { &self.#ident }, quote! { &mut self.#ident })
+                };
+
+                iter.push(quote! {
+                    #cfg
+                    let iter = iter.chain(
+                        ::app_base::prelude::Iter::<'_, ::app_base::convert::ConfigItem>::iter(#field_ref)
+                    );
+                });
+                init_args.push(quote! {
+                    #cfg
+                    ::app_base::prelude::LoadArgs::init_args(#field_mut, args);
+                });
+                load_args.push(quote! {
+                    #cfg
+                    errors.merge(
+                        ::app_base::prelude::LoadArgs::load_args(#field_mut, args)
+                            .map_err(|e| e.key(#name))
+                    );
+                });
+                load_env.push(quote! {
+                    #cfg
+                    errors.merge(
+                        ::app_base::prelude::LoadEnv::load_env(#field_mut)
+                            .map_err(|e| e.key(#name))
+                    );
+                });
+                load_dirs.push(quote! {
+                    #cfg
+                    ::app_base::prelude::LoadDirs::load_dirs(#field_mut, dirs);
+                });
+                continue;
+            }
+
+            let key = match prefix.is_empty() {
+                true => name.clone(),
+                false => [prefix.as_str(), ".", &name].concat()
+            };
+
+            let mut value = Self::value(&field.ty, quote! { &self.#ident });
+            if config.secret {
+                value = quote! { ::app_base::convert::config_secret(#value) };
+            }
+            iter.push(quote! {
+                #cfg
+                let iter = iter.chain(::core::iter::once((#key, #value)));
+            });
+
+            if config.no_arg == false {
+                let arg = config.arg.unwrap_or_else(|| {
+                    match arg_prefix.as_ref() {
+                        Some(p) => [p.as_str(), "-", &name].concat().replace('_', "-"),
+                        None => key.replace(['.', '_'], "-")
+                    }
+                });
+                let option = match config.flag {
+                    true => [arg.as_str(), ":b"].concat(),
+                    false => arg.clone()
+                };
+                let short = match config.short {
+                    Some(short) => quote! { ::core::option::Option::Some(#short) },
+                    None => quote! { ::core::option::Option::None }
+                };
+                init_args.push(quote! {
+                    #cfg
+                    args.add_options([(#option, #short, ::core::option::Option::None)]).unwrap();
+                });
+                load_args.push(quote! {
+                    #cfg
+                    errors.merge(
+                        ::app_base::extend::ExtendErrors::from_args(self, args, [(#name, #arg)])
+                    );
+                });
+            }
+
+            let env = config.env.or_else(|| {
+                env_prefix
+                    .as_ref()
+                    .map(|p| [p.as_str(), "_", &name].concat().to_uppercase())
+            });
+            if let Some(env) = env.filter(|_| config.no_env == false) {
+                load_env.push(quote! {
+                    #cfg
+                    errors.merge(::app_base::extend::ExtendErrors::from_env(self, [(#name, #env)]));
+                });
+            }
+
+            if let Some(dir) = config.dir {
+                let dir = format_ident!("{dir}");
+                load_dirs.push(match Self::type_name(&field.ty).0.as_str() {
+                    // empty path turns the option off
+                    "Option" => quote! {
+                        #cfg
+                        if self.#ident.as_deref() == ::core::option::Option::Some("") {
+                            self.#ident = ::core::option::Option::None;
+                        } else if let ::core::option::Option::Some(path) = self.#ident.as_mut() {
+                            ::app_base::convert::config_path(path, &dirs.#dir);
+                        }
+                    },
+                    _ => quote! {
+                        #cfg
+                        ::app_base::convert::config_path(&mut self.#ident, &dirs.#dir);
+                    }
+                });
+            }
+        }
+
+        // options of command are added and loaded only for the command
+        let (init_gate, load_gate) = match command {
+            Some(command) => {
+                let is_other = quote! {
+                    args.get("command").ok().flatten() != ::core::option::Option::Some(#command)
+                };
+                (
+                    quote! { if #is_other { return; } },
+                    quote! { if #is_other { return ::core::result::Result::Ok(()); } }
+                )
+            },
+            None => (quote! {}, quote! {})
+        };
+        let after_args = after_args.map(|f| {
+            let f = format_ident!("{f}");
+            quote! { self.#f(args); }
+        });
+
+        Ok(quote! {
+            impl #impl_generics ::app_base::prelude::Iter<'_, ::app_base::convert::ConfigItem>
+                for #struct_name #ty_generics #where_clause
+            {
+                fn iter(&self) -> impl Iterator<Item = ::app_base::convert::ConfigItem> {
+                    let iter = ::core::iter::empty::<::app_base::convert::ConfigItem>();
+                    #(#iter)*
+                    iter
+                }
+            }
+
+            impl #impl_generics ::app_base::prelude::LoadArgs for #struct_name #ty_generics #where_clause {
+                fn init_args(&mut self, args: &mut ::app_base::prelude::Args) {
+                    let _ = &args;
+                    #init_gate
+                    #(#init_args)*
+                }
+
+                fn load_args(
+                    &mut self,
+                    args: &::app_base::prelude::Args
+                ) -> ::core::result::Result<(), ::app_base::extend::ExtendErrors> {
+                    let _ = &args;
+                    #load_gate
+                    let mut errors = ::app_base::extend::ExtendErrors::default();
+                    #(#load_args)*
+                    #after_args
+                    errors.into_result()
+                }
+            }
+
+            impl #impl_generics ::app_base::prelude::LoadEnv for #struct_name #ty_generics #where_clause {
+                fn load_env(&mut self) -> ::core::result::Result<(), ::app_base::extend::ExtendErrors> {
+                    let mut errors = ::app_base::extend::ExtendErrors::default();
+                    #(#load_env)*
+                    errors.into_result()
+                }
+            }
+
+            impl #impl_generics ::app_base::prelude::LoadDirs for #struct_name #ty_generics #where_clause {
+                fn load_dirs<'a>(&'a mut self, dirs: &'a ::app_base::prelude::Dirs) {
+                    let _ = &dirs;
+                    #(#load_dirs)*
+                }
+            }
+        })
+    }
+
+    fn field_config(attrs: &[Attribute]) -> syn::Result<FieldConfig> {
+        let mut config = FieldConfig::default();
+
+        for attr in attrs.iter().filter(|a| a.path().is_ident("config")) {
+            attr.parse_nested_meta(|meta| {
+                let path = &meta.path;
+                if path.is_ident("nested") {
+                    config.nested = true;
+                } else if path.is_ident("skip") {
+                    config.skip = true;
+                } else if path.is_ident("secret") {
+                    config.secret = true;
+                } else if path.is_ident("no_arg") {
+                    config.no_arg = true;
+                } else if path.is_ident("no_env") {
+                    config.no_env = true;
+                } else if path.is_ident("flag") {
+                    config.flag = true;
+                } else if path.is_ident("arg") {
+                    config.arg = Some(meta.value()?.parse::<LitStr>()?.value());
+                } else if path.is_ident("short") {
+                    config.short = Some(meta.value()?.parse::<LitStr>()?.value());
+                } else if path.is_ident("env") {
+                    config.env = Some(meta.value()?.parse::<LitStr>()?.value());
+                } else if path.is_ident("dir") {
+                    config.dir = Some(meta.value()?.parse::<LitStr>()?.value());
+                } else {
+                    return Err(meta.error(
+                        "Unknown option, expected one of: nested, skip, secret, no_arg, \
+                         no_env, flag, arg, short, env, dir"
+                    ));
+                }
+                Ok(())
+            })?;
+        }
+
+        Ok(config)
+    }
+
+    /// Expression of shown value of *expr* which is a reference to *ty*
+    fn value(ty: &Type, expr: TokenStream2) -> TokenStream2 {
+        let (name, args) = Self::type_name(ty);
+
+        match (name.as_str(), args.as_slice()) {
+            ("Option", [inner]) => {
+                let inner = Self::value(inner, quote! { v });
+                quote! { ::app_base::convert::config_option((#expr).as_ref(), |v| #inner) }
+            },
+            ("Vec" | "VecDeque" | "HashSet" | "IndexSet" | "BTreeSet", [inner]) => {
+                let inner = Self::value(inner, quote! { v });
+                quote! { ::app_base::convert::config_list(#expr, |v| #inner) }
+            },
+            ("HashMap" | "IndexMap" | "BTreeMap", [_, inner]) => {
+                let inner = Self::value(inner, quote! { v });
+                quote! { ::app_base::convert::config_map(#expr, |v| #inner) }
+            },
+            _ => quote! { ::app_base::convert::config_value(#expr) }
+        }
+    }
+
+    /// Last segment of type path and its type arguments
+    fn type_name(ty: &Type) -> (String, Vec<&Type>) {
+        let Type::Path(path) = ty else {
+            return (String::new(), Vec::new());
+        };
+        let Some(segment) = path.path.segments.last() else {
+            return (String::new(), Vec::new());
+        };
+
+        let args = match &segment.arguments {
+            PathArguments::AngleBracketed(args) => {
+                args.args
+                    .iter()
+                    .filter_map(|arg| {
+                        match arg {
+                            GenericArgument::Type(ty) => Some(ty),
+                            _ => None
+                        }
+                    })
+                    .collect()
+            },
+            _ => Vec::new()
+        };
+
+        (segment.ident.to_string(), args)
+    }
+}
@@ -0,0 +1,400 @@
+use {
+    alloc::{
+        string::{String, ToString},
+        vec::Vec
+    },
+    proc_macro::TokenStream,
+    proc_macro2::{Span, TokenStream as TokenStream2},
+    quote::quote,
+    syn::{
+        Attribute, Expr, ExprLit, Fields, Ident, ItemStruct, Lit, LitInt, LitStr, Meta,
+        Type, meta::ParseNestedMeta, parse_macro_input, parse_quote
+    }
+};
+#[cfg(not(feature = "std"))]
+#[allow(unused_imports)]
+use libc_print::std_name::*;
+
+/// Options of macro from `#[app_module(...)]`
+#[derive(Default)]
+pub(crate) struct AppModule {
+    command: String,
+    config: Option<Type>,
+    entry: Option<String>,
+    no_entry: bool
+}
+
+/// Command line option of field from `#[arg(...)]`
+#[derive(Default)]
+struct FieldArg {
+    name: Option<String>,
+    short: Option<String>,
+    position: Option<String>,
+    default: Option<String>
+}
+
+/// Options shown by default help of `AppModuleExt`
+const HELP_OPTIONS: [(&str, &str); 5] = [
+    ("-h, --help", "show usage help"),
+    ("--env-file file", "loads env vars from file"),
+    ("--debug", "enable debuging"),
+    ("--version", "show current version"),
+    ("--timings", "show durations of boot steps on exit")
+];
+
+impl AppModule {
+    pub fn attribute(mut self, attr: TokenStream, input: TokenStream) -> TokenStream {
+        let parser = syn::meta::parser(|meta| self.parse_option(meta));
+        parse_macro_input!(attr with parser);
+        let input = parse_macro_input!(input as ItemStruct);
+        let expanded = self
+            .parse(input)
+            .unwrap_or_else(syn::Error::into_compile_error);
+        TokenStream::from(expanded)
+    }
+
+    fn parse_option(&mut self, meta: ParseNestedMeta) -> syn::Result<()> {
+        if meta.path.is_ident("command") {
+            self.command = meta.value()?.parse::<LitStr>()?.value();
+        } else if meta.path.is_ident("config") {
+            self.config = Some(meta.value()?.parse::<Type>()?);
+        } else if meta.path.is_ident("entry") {
+            self.entry = Some(meta.value()?.parse::<LitStr>()?.value());
+        } else if meta.path.is_ident("no_entry") {
+            self.no_entry = true;
+        } else {
+            return Err(meta.error(
+                "Unknown option, expected one of: command, config, entry, no_entry"
+            ));
+        }
+        Ok(())
+    }
+
+    fn parse(&self, mut input: ItemStruct) -> syn::Result<TokenStream2> {
+        let struct_name = &input.ident;
+        let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+        let command = &self.command;
+        let config = self
+            .config
+            .clone()
+            .unwrap_or_else(|| parse_quote! { ::app_base::app::AppSimpleConfig });
+
+        let doc = Self::doc(&input.attrs);
+        let description = Self::description(&doc);
+
+        let (mut options, mut load_args, mut help_options) =
+            (Vec::new(), Vec::new(), Vec::new());
+        let (mut positions, mut skip) = (Vec::new(), Vec::new());
+        if let Fields::Named(fields) = &mut input.fields {
+            for field in fields.named.iter_mut() {
+                let Some(arg) = Self::field_arg(&field.attrs)? else {
+                    skip.push(field);
+                    continue;
+                };
+                field.attrs.retain(|a| a.path().is_ident("arg") == false);
+
+                let field_name = field.ident.as_ref().unwrap().to_string();
+                let name = arg.name.unwrap_or_else(|| field_name.replace('_', "-"));
+                let is_flag = Self::is_bool(&field.ty);
+                let option = match is_flag {
+                    true => [name.as_str(), ":b"].concat(),
+                    false => name.clone()
+                };
+                let short = match arg.position.as_ref().or(arg.short.as_ref()) {
+                    Some(short) => quote! { ::core::option::Option::Some(#short) },
+                    None => quote! { ::core::option::Option::None }
+                };
+                let default = match &arg.default {
+                    Some(default) => quote! { ::core::option::Option::Some(#default) },
+                    None => quote! { ::core::option::Option::None }
+                };
+                options.push(quote! { (#option, #short, #default) });
+                load_args.push(quote! { (#field_name, #name) });
+
+                let usage = match (&arg.position, &arg.short, is_flag) {
+                    (Some(_), ..) => {
+                        positions.push(["<", &name, ">"].concat());
+                        ["<", &name, ">"].concat()
+                    },
+                    (None, Some(short), true) => [short, ", --", &name].concat(),
+                    (None, Some(short), false) => {
+                        [short, ", --", &name, " value"].concat()
+                    },
+                    (None, None, true) => ["--", &name].concat(),
+                    (None, None, false) => ["--", &name, " value"].concat()
+                };
+                let mut text = Self::doc(&field.attrs).replace('\n', " ");
+                if let Some(default) = &arg.default {
+                    text = [&text, " (default: ", default, ")"].concat();
+                }
+                help_options.push((usage, text));
+            }
+        }
+
+        // Only options are extended by arguments, other fields are skipped
+        let (init_args, boot_args) = match options.is_empty() {
+            true => (quote! {}, quote! {}),
+            false => {
+                input
+                    .attrs
+                    .push(parse_quote! { #[derive(::app_base::macros::Extend)] });
+                for field in skip {
+                    field.attrs.push(parse_quote! { #[extend_skip] });
+                }
+                (
+                    quote! {
+                        if Self::COMMAND == app.command()? {
+                            app.args_mut().add_options([#(#options),*])?;
+                        }
+                    },
+                    quote! {
+                        if Self::COMMAND == app.command()? {
+                            ::app_base::extend::ExtendErrors::from_args(self, app.args(), [#(#load_args),*])?;
+                        }
+                    }
+                )
+            }
+        };
+
+        let help = self.help(&doc, &positions, &help_options);
+
+        let entry = match self.no_entry {
+            true => quote! {},
+            // C calls entry points with `App<AppSimpleConfig>`
+            false if self.config.is_some() => {
+                return Err(syn::Error::new_spanned(
+                    &self.config,
+                    "Entry point is called with `App<AppSimpleConfig>`, use `no_entry` with \
+                     `config`"
+                ));
+            },
+            false if input.generics.params.is_empty() == false => {
+                return Err(syn::Error::new_spanned(
+                    &input.generics,
+                    "Entry point of generic module isn't known, use `no_entry`"
+                ));
+            },
+            false => {
+                let entry = self.entry.clone().unwrap_or_else(|| {
+                    let name = struct_name.to_string();
+                    let name = name.strip_suffix("Module").unwrap_or(&name);
+                    ["module_", &Self::snake_case(name)].concat()
+                });
+                let entry = Ident::new(&entry, Span::call_site());
+                quote! {
+                    #[unsafe(no_mangle)]
+                    extern "C" fn #entry(
+                        app: *mut ::app_base::prelude::App<#config>,
+                        event: ::app_base::prelude::AppEvent
+                    ) -> *const ::core::ffi::c_void {
+                        match <#struct_name as ::app_base::prelude::AppModuleExt>::handle(
+                            unsafe { &mut *app },
+                            event
+                        ) {
+                            ::core::result::Result::Ok(..) => ::core::ptr::null(),
+                            ::core::result::Result::Err(e) => ::core::panic!("{e}")
+                        }
+                    }
+                }
+            }
+        };
+
+        Ok(quote! {
+            #input
+
+            #entry
+
+            impl #impl_generics ::app_base::prelude::AppModuleExt for #struct_name #ty_generics #where_clause {
+                const COMMAND: &str = #command;
+                const DESCRIPTION: &str = #description;
+
+                type Config = #config;
+
+                fn init(&mut self, app: &mut ::app_base::prelude::App<Self::Config>) -> ::app_base::prelude::Void {
+                    #init_args
+                    ::app_base::prelude::AppModuleHooks::on_init(self, app)
+                }
+
+                fn boot(&mut self, app: &mut ::app_base::prelude::App<Self::Config>) -> ::app_base::prelude::Void {
+                    #boot_args
+                    ::app_base::prelude::AppModuleHooks::on_boot(self, app)
+                }
+
+                fn setup(&mut self, app: &mut ::app_base::prelude::App<Self::Config>) -> ::app_base::prelude::Void {
+                    ::app_base::prelude::AppModuleHooks::on_setup(self, app)
+                }
+
+                fn run(&mut self, app: &mut ::app_base::prelude::App<Self::Config>) -> ::app_base::prelude::Void {
+                    ::app_base::prelude::AppModuleHooks::on_run(self, app)
+                }
+
+                fn run_async(
+                    &mut self,
+                    app: &mut ::app_base::prelude::App<Self::Config>
+                ) -> ::app_base::prelude::Ok<::core::option::Option<::app_base::prelude::AppFuture>> {
+                    ::app_base::prelude::AppModuleHooks::on_run_async(self, app)
+                }
+
+                fn end(&mut self, app: &mut ::app_base::prelude::App<Self::Config>) -> ::app_base::prelude::Void {
+                    ::app_base::prelude::AppModuleHooks::on_end(self, app)
+                }
+
+                fn version(&self, app: &mut ::app_base::prelude::App<Self::Config>) -> ::app_base::prelude::Void {
+                    ::app_base::prelude::AppModuleHooks::on_version(self, app)
+                }
+
+                fn help(&self, app: &mut ::app_base::prelude::App<Self::Config>) -> ::app_base::prelude::Void {
+                    let config = app.config();
+                    ::app_base::println!(
+                        "{}",
+                        Self::help_text(config.dirs.exe_file(), &config.name, &config.version)
+                    );
+                    ::app_base::prelude::ok()
+                }
+            }
+
+            impl #impl_generics #struct_name #ty_generics #where_clause {
+                /// Usage help of command with *bin*, *name* and *version* of app
+                pub fn help_text(bin: &str, name: &str, version: &str) -> ::app_base::alloc::string::String {
+                    ::app_base::alloc::format!(#help, bin = bin, name = name, version = version)
+                }
+            }
+        })
+    }
+
+    /// Format string of help with usage, doc comment and options of module
+    fn help(
+        &self,
+        doc: &str,
+        positions: &[String],
+        options: &[(String, String)]
+    ) -> String {
+        let escape = |s: &str| s.replace('{', "{{").replace('}', "}}");
+        let options = options
+            .iter()
+            .map(|(usage, text)| (usage.as_str(), text.as_str()))
+            .chain(HELP_OPTIONS)
+            .collect::<Vec<_>>();
+        let len = options
+            .iter()
+            .map(|(usage, _)| usage.len())
+            .max()
+            .unwrap_or(0);
+
+        let mut usage = ["{bin} ", &escape(&self.command), " [options]"].concat();
+        for position in positions {
+            usage = [&usage, " ", position].concat();
+        }
+        let options = options
+            .iter()
+            .map(|(usage, text)| {
+                let usage = escape(usage);
+                let text = escape(text);
+                format!("    {usage:<len$} - {text}")
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        format!(
+            "\nUsage: {usage}\n\nVersion: {{name}} {{version}}\n\n{doc}\n\nOptions:\n{options}\n",
+            doc = escape(doc)
+        )
+    }
+
+    /// Command line option of field, `#[arg]` or
+    /// `#[arg(name = "...", short = "-n", position = 2, default = "...")]`
+    fn field_arg(attrs: &[Attribute]) -> syn::Result<Option<FieldArg>> {
+        let mut result = None;
+
+        for attr in attrs.iter().filter(|a| a.path().is_ident("arg")) {
+            let arg = result.get_or_insert_with(FieldArg::default);
+            if let Meta::Path(_) = attr.meta {
+                continue;
+            }
+            attr.parse_nested_meta(|meta| {
+                let path = &meta.path;
+                if path.is_ident("name") {
+                    arg.name = Some(meta.value()?.parse::<LitStr>()?.value());
+                } else if path.is_ident("short") {
+                    arg.short = Some(meta.value()?.parse::<LitStr>()?.value());
+                } else if path.is_ident("position") {
+                    arg.position = Some(meta.value()?.parse::<LitInt>()?.to_string());
+                } else if path.is_ident("default") {
+                    arg.default = Some(meta.value()?.parse::<LitStr>()?.value());
+                } else {
+                    return Err(meta.error(
+                        "Unknown option, expected one of: name, short, position, default"
+                    ));
+                }
+                Ok(())
+            })?;
+        }
+
+        Ok(result)
+    }
+
+    /// Doc comment of attributes with trimmed lines
+    fn doc(attrs: &[Attribute]) -> String {
+        attrs
+            .iter()
+            .filter_map(|a| {
+                match &a.meta {
+                    Meta::NameValue(meta) if meta.path.is_ident("doc") => {
+                        match &meta.value {
+                            Expr::Lit(ExprLit { lit: Lit::Str(s), .. }) => {
+                                Some(s.value().trim().to_string())
+                            },
+                            _ => None
+                        }
+                    },
+                    _ => None
+                }
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+            .trim()
+            .to_string()
+    }
+
+    /// First paragraph of doc comment, which continues "This command ..."
+    /// of help, i.e. `Greets users.` is `greets users`
+    fn description(doc: &str) -> String {
+        let paragraph = doc
+            .split("\n\n")
+            .next()
+            .unwrap_or_default()
+            .replace('\n', " ");
+        let paragraph = paragraph.trim_end_matches('.');
+        let mut chars = paragraph.chars();
+        chars
+            .next()
+            .map(|c| c.to_lowercase().chain(chars).collect())
+            .unwrap_or_default()
+    }
+
+    /// `bool` by any path, i.e. `core::primitive::bool`, is a flag, while
+    /// `Option<bool>` is an option with value like `--name false`
+    fn is_bool(ty: &Type) -> bool {
+        match ty {
+            Type::Path(p) if p.qself.is_none() => {
+                p.path
+                    .segments
+                    .last()
+                    .is_some_and(|s| s.ident == "bool" && s.arguments.is_empty())
+            },
+            _ => false
+        }
+    }
+
+    /// `AppConfig` is `app_config`
+    fn snake_case(name: &str) -> String {
+        let mut result = String::new();
+        for (i, c) in name.char_indices() {
+            if c.is_uppercase() && i > 0 {
+                result.push('_');
+            }
+            result.extend(c.to_lowercase());
+        }
+        result
+    }
+}
@@ -1,20 +1,16 @@
 use {
     alloc::{
-        borrow::Cow,
         string::{String, ToString},
         vec::Vec
     },
-    core::{
-        iter::{Enumerate, Peekable},
-        marker::PhantomData
-    },
     proc_macro::TokenStream,
-    proc_macro2::{Span, TokenStream as TokenStream2, TokenTree},
-    quote::{ToTokens, quote},
+    proc_macro2::{Span, TokenStream as TokenStream2},
+    quote::{format_ident, quote},
     syn::{
-        Attribute, Data, DeriveInput, Field, Fields, GenericParam, Generics, Ident,
-        ImplGenerics, Lifetime, LifetimeParam, Path, TypePath, WhereClause,
-        parse_macro_input, parse_str, punctuated::Punctuated, token::Comma
+        Attribute, Data, DataEnum, DeriveInput, Expr, ExprLit, Field, Fields,
+        GenericArgument, GenericParam, Generics, Ident, Lifetime, LifetimeParam, Lit,
+        PathArguments, Type, TypePath, parse_macro_input, parse_str,
+        punctuated::Punctuated, token::Comma
     }
 };
 #[cfg(not(feature = "std"))]
@@ -51,27 +47,42 @@ impl ExtendMacros {
                     _ => panic!("Only structs with named fields are supported")
                 }
             },
-            _ => panic!("Only structs are supported")
+            Data::Enum(data_enum) => {
+                return self
+                    .parse_enum(&struct_name, &generics, &generics_clone, &data_enum);
+            },
+            _ => panic!("Only structs and enums are supported")
         };
-        let fields_set = self.parse_fields(&fields, &generics_clone);
-
-        #[cfg(feature = "std")]
-        let map_type: TypePath =
-            syn::parse_str("::std::collections::HashMap<&'iter str, Option<&'iter str>>")
-                .unwrap();
+        let fields_set = self.parse_fields(&fields);
 
-        #[cfg(not(feature = "std"))]
+        // Items of `IndexMap` fields keep order of the iterator
         let map_type: TypePath = syn::parse_str(
-            "::indexmap::IndexMap<&'iter str, Option<&'iter str>, ::core::hash::BuildHasherDefault<ahash::AHasher>>",
-        ).unwrap();
+            "::app_base::base::IndexMap<&'iter str, ::core::option::Option<&'iter str>>"
+        )
+        .unwrap();
 
         let mut extend = quote! {
-            impl #impl_generics Extend<(&'iter str, Option<&'iter str>)> for #struct_name #ty_generics #where_clause {
-                fn extend<I: IntoIterator<Item = (&'iter str, Option<&'iter str>)>>(&mut self, iter: I) {
+            impl #impl_generics ::app_base::extend::TryExtend<(&'iter str, ::core::option::Option<&'iter str>)> for #struct_name #ty_generics #where_clause {
+                fn try_extend<I: ::core::iter::IntoIterator<Item = (&'iter str, ::core::option::Option<&'iter str>)>>(
+                    &mut self,
+                    iter: I
+                ) -> ::core::result::Result<(), ::app_base::extend::ExtendErrors> {
                     type MapType<'iter> = #map_type;
-                    let mut map = MapType::from_iter(iter.into_iter());
+                    let map: MapType = ::core::iter::FromIterator::from_iter(iter);
+                    let mut errors = ::app_base::extend::ExtendErrors::default();
 
                     #(#fields_set)*
+
+                    errors.into_result()
+                }
+            }
+
+            impl #impl_generics ::core::iter::Extend<(&'iter str, ::core::option::Option<&'iter str>)> for #struct_name #ty_generics #where_clause {
+                fn extend<I: ::core::iter::IntoIterator<Item = (&'iter str, ::core::option::Option<&'iter str>)>>(&mut self, iter: I) {
+                    // Invalid values are skipped, `TryExtend` returns them
+                    if let ::core::result::Result::Err(e) = ::app_base::extend::TryExtend::try_extend(self, iter) {
+                        e.warn();
+                    }
                 }
             }
         };
@@ -80,36 +91,131 @@ impl ExtendMacros {
             extend = quote! {
                 #extend
 
-                impl #impl_generics FromIterator<(&'iter str, Option<&'iter str>)> for #struct_name #ty_generics #where_clause {
-                    fn from_iter<I: IntoIterator<Item = (&'iter str, Option<&'iter str>)>>(iter: I) -> Self {
-                        let mut this = Self::default();
-                        this.extend(iter);
+                impl #impl_generics ::core::iter::FromIterator<(&'iter str, ::core::option::Option<&'iter str>)> for #struct_name #ty_generics #where_clause {
+                    fn from_iter<I: ::core::iter::IntoIterator<Item = (&'iter str, ::core::option::Option<&'iter str>)>>(iter: I) -> Self {
+                        let mut this: Self = ::core::default::Default::default();
+                        ::core::iter::Extend::extend(&mut this, iter);
                         this
                     }
                 }
             }
-        };
+        }
 
         extend
     }
 
-    fn parse_fields<'a>(
-        &'a self,
-        fields: &'a Punctuated<Field, Comma>,
-        generics: &'a Generics
-    ) -> impl Iterator<Item = TokenStream2> + use<'a> {
-        let generic_types = generics
-            .params
+    /// Unit enum is parsed from name of variant case-insensitively or from
+    /// `#[extend_rename = "name"]`, so it's set by its own key like a value
+    fn parse_enum(
+        &self,
+        enum_name: &Ident,
+        generics: &Generics,
+        generics_iter: &Generics,
+        data_enum: &DataEnum
+    ) -> TokenStream2 {
+        let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+        let (impl_generics_iter, ..) = generics_iter.split_for_impl();
+
+        let variants = data_enum
+            .variants
             .iter()
-            .filter_map(|p| {
-                match p {
-                    GenericParam::Type(t) => t.to_token_stream().to_string().into(),
-                    GenericParam::Lifetime(l) => l.lifetime.to_string().into(),
-                    _ => None
+            .map(|variant| {
+                if matches!(variant.fields, Fields::Unit) == false {
+                    panic!("Only unit variants are supported: {}", variant.ident);
                 }
+                let name = variant
+                    .attrs
+                    .iter()
+                    .find(|a| a.path().is_ident("extend_rename"))
+                    .map(|a| {
+                        match &a.meta.require_name_value().unwrap().value {
+                            Expr::Lit(ExprLit { lit: Lit::Str(s), .. }) => s.value(),
+                            _ => panic!("Expected #[extend_rename = \"name\"]")
+                        }
+                    })
+                    .unwrap_or_else(|| variant.ident.to_string());
+                (&variant.ident, name)
             })
             .collect::<Vec<_>>();
 
+        let idents = variants.iter().map(|(ident, _)| ident);
+        let names = variants.iter().map(|(_, name)| name);
+        let expected = variants
+            .iter()
+            .map(|(_, name)| name.to_lowercase())
+            .collect::<Vec<_>>()
+            .join(", ");
+        let enum_str = enum_name.to_string();
+        let expected_one_of = ["one of: ", &expected].concat();
+
+        let mut extend = quote! {
+            impl #impl_generics ::core::str::FromStr for #enum_name #ty_generics #where_clause {
+                type Err = ::app_base::alloc::string::String;
+
+                fn from_str(s: &str) -> ::core::result::Result<Self, Self::Err> {
+                    let s = s.trim();
+                    #(
+                        if s.eq_ignore_ascii_case(#names) {
+                            return ::core::result::Result::Ok(Self::#idents);
+                        }
+                    )*
+                    ::core::result::Result::Err(::app_base::alloc::format!("Unknown value '{s}' of {}, expected one of: {}", #enum_str, #expected))
+                }
+            }
+
+            impl #impl_generics_iter ::app_base::extend::TryExtend<(&'iter str, ::core::option::Option<&'iter str>)> for #enum_name #ty_generics #where_clause {
+                fn try_extend<I: ::core::iter::IntoIterator<Item = (&'iter str, ::core::option::Option<&'iter str>)>>(
+                    &mut self,
+                    iter: I
+                ) -> ::core::result::Result<(), ::app_base::extend::ExtendErrors> {
+                    let mut errors = ::app_base::extend::ExtendErrors::default();
+                    for item in iter {
+                        if let ("", ::core::option::Option::Some(v)) = item {
+                            match v.parse() {
+                                ::core::result::Result::Ok(v) => *self = v,
+                                ::core::result::Result::Err(_) => {
+                                    errors.merge(::core::result::Result::Err(
+                                        ::app_base::extend::ExtendErrors::expected(#expected_one_of).value(v)
+                                    ));
+                                }
+                            }
+                        }
+                    }
+                    errors.into_result()
+                }
+            }
+
+            impl #impl_generics_iter ::core::iter::Extend<(&'iter str, ::core::option::Option<&'iter str>)> for #enum_name #ty_generics #where_clause {
+                fn extend<I: ::core::iter::IntoIterator<Item = (&'iter str, ::core::option::Option<&'iter str>)>>(&mut self, iter: I) {
+                    // Invalid values are skipped, `TryExtend` returns them
+                    if let ::core::result::Result::Err(e) = ::app_base::extend::TryExtend::try_extend(self, iter) {
+                        e.warn();
+                    }
+                }
+            }
+        };
+
+        if self.from_iter {
+            extend = quote! {
+                #extend
+
+                impl #impl_generics_iter ::core::iter::FromIterator<(&'iter str, ::core::option::Option<&'iter str>)> for #enum_name #ty_generics #where_clause {
+                    fn from_iter<I: ::core::iter::IntoIterator<Item = (&'iter str, ::core::option::Option<&'iter str>)>>(iter: I) -> Self {
+                        let mut this: Self = ::core::default::Default::default();
+                        ::core::iter::Extend::extend(&mut this, iter);
+                        this
+                    }
+                }
+            }
+        }
+
+        extend
+    }
+
+    fn parse_fields<'a>(
+        &'a self,
+        fields: &'a Punctuated<Field, Comma>
+    ) -> impl Iterator<Item = TokenStream2> + use<'a> {
         fields.iter().map(move |field| {
             let field_name = field
                 .ident
@@ -117,227 +223,350 @@ impl ExtendMacros {
                 .expect("Couldn't get ident of field")
                 .to_string();
 
-            if field_name.starts_with("_") {
+            if field_name.starts_with("_")
+                || field.attrs.iter().any(|a| a.path().is_ident("extend_skip"))
+            {
                 return quote! {};
             }
 
-            let mut field_type = field
-                .ty
-                .to_token_stream()
-                .to_string()
-                .replace('\n', "")
-                .replace(" :: ", "::")
-                .replace("'static", "");
-
-            generic_types.iter().for_each(|ty| {
-                field_type = field_type
-                    .replace(&[" ", ty.as_str(), " "].concat(), "")
-                    .replace(&[" ", ty.as_str(), ","].concat(), "");
-            });
-
-            let mut types: Vec<String> = Vec::new();
-            let mut ty_full = String::new();
-            for ty in field_type.split_terminator('<') {
-                let ty = ty.trim_matches(['>', ' ', ',', '&']);
-                ty_full.push_str(ty);
-
-                if ty_full.chars().filter(|ch| *ch == '(').count()
-                    != ty_full.chars().filter(|ch| *ch == ')').count()
-                {
-                    ty_full.push('<');
-                    continue;
-                }
-
-                if ty_full.is_empty() == false {
-                    types.push(ty_full.clone());
-                    ty_full.clear();
-                }
-            }
-
-            let mut ty_iter = types.into_iter().enumerate().peekable();
+            let name_ident = field.ident.as_ref().unwrap();
+            let target = quote! { &mut self.#name_ident };
             let mut iterable = false;
-            let field_token = self
-                .get_value_token(&field_name, &field.attrs, ty_iter, None, &mut iterable);
+            let field_token = self.get_value_token(
+                &field_name,
+                &field.attrs,
+                &field.ty,
+                0,
+                None,
+                false,
+                Some(&target),
+                &mut iterable
+            );
+            let result = quote! {
+                (|| -> ::core::result::Result<(), ::app_base::extend::ExtendErrors> {
+                    #field_token
+                    ::core::result::Result::Ok(())
+                })()
+            };
 
             if iterable {
-                quote! { #field_token }
+                quote! {
+                    if map.iter().any(|(&name, value)| {
+                        value.is_some() && (name.starts_with(::core::concat!(#field_name, ".")) || name == #field_name)
+                    }) {
+                        errors.merge(#result.map_err(|e| e.key(#field_name)));
+                    }
+                }
             } else {
                 quote! {
-                    if let Some(&Some(v)) = map.get(#field_name).take() {
-                        #field_token
+                    if let ::core::option::Option::Some(&::core::option::Option::Some(v)) = map.get(#field_name) {
+                        errors.merge(#result.map_err(|e| e.value(v).key(#field_name)));
                     }
                 }
             }
         })
     }
 
+    /// Token of value of *ty* parsed from `v: &str`, or statement which
+    /// extends *target* by nested keys if it's *iterable*
+    ///
+    /// *n* is a depth of type, the field itself is assigned at zero. *value*
+    /// is set inside lists, maps and tuples, where any type is parsed from
+    /// `v` only. Tokens are evaluated in a closure returning `ExtendErrors`,
+    /// so invalid values are returned by `?`.
+    #[allow(clippy::too_many_arguments)]
     #[allow(clippy::only_used_in_recursion)]
-    fn get_value_token<I: Iterator<Item = (usize, String)>>(
+    fn get_value_token(
         &self,
         name: &str,
         attrs: &[Attribute],
-        mut types: Peekable<I>,
+        ty: &Type,
+        n: usize,
         prev_ty: Option<&str>,
+        value: bool,
+        target: Option<&TokenStream2>,
         iterable: &mut bool
     ) -> TokenStream2 {
-        let Some((n, mut ty)) = types.next() else {
-            return quote! { v };
-        };
         if attrs.iter().any(|a| a.path().is_ident("extend_skip")) {
             return quote! {};
         }
-        if ty.contains('(') || ty.contains(',') || ty.contains(' ') {
-            panic!("This type not supported yet: {ty}");
-        }
-        if let Some(pos) = ty.rfind("::") {
-            ty = ty.get(pos + 2..).unwrap().to_string();
-        }
-        let ty_ident: TypePath = parse_str(&ty)
-            .map_err(|e| format!("{name}: {ty} - {e}"))
-            .unwrap();
-        let name_ident: Ident = parse_str(name)
-            .map_err(|e| format!("{name}: {ty} - {e}"))
-            .unwrap();
-        let next_ty = types.peek().map(|(.., ty)| Cow::from(ty));
-        let is_parse = attrs.iter().any(|a| a.path().is_ident("extend_parse"));
 
-        *iterable = false;
-        match ty.as_str() {
-            "bool" => {
-                let token = quote! {
-                    ["0","off","false","","\0"].contains(&v.to_lowercase().as_str()) == false
-                };
-                if n == 0 {
-                    quote! { self.#name_ident = #token; }
-                } else {
-                    token
-                }
-            },
-            ty @ ("i8" | "i16" | "i32" | "i64" | "i128" | "u8" | "u16" | "u32"
-            | "u64" | "u128" | "f32" | "f64" | "f128" | "isize" | "usize"
-            | "c_char" | "c_short" | "c_ushort" | "c_int" | "c_uint" | "c_long"
-            | "c_ulong" | "c_longlong" | "c_ulonglong" | "c_double" | "c_float") => {
-                let token = quote! {
-                    v.parse::<#ty_ident>().map_err(|_| format!("Failed parse '{v}' to type {}", #ty)).unwrap()
-                };
-                if n == 0 {
-                    quote! { self.#name_ident = #token; }
-                } else {
-                    token
-                }
+        let name_ident: Ident =
+            parse_str(name).map_err(|e| format!("{name}: {e}")).unwrap();
+        let ty = match ty {
+            Type::Reference(r) => {
+                return self.get_value_token(
+                    name, attrs, &r.elem, n, prev_ty, value, target, iterable
+                );
             },
-            "c_void" => {
-                if n == 0 {
-                    quote! { self.#name_ident = ::alloc::ffi::CString::new(v).unwrap().into_raw().cast(); }
-                } else {
-                    quote! { ::alloc::ffi::CString::new(v).unwrap().into_raw().cast() }
-                }
+            Type::Paren(p) => {
+                return self.get_value_token(
+                    name, attrs, &p.elem, n, prev_ty, value, target, iterable
+                );
             },
-            "CString" => {
-                if n == 0 {
-                    quote! { self.#name_ident = ::alloc::ffi::CString::new(v).unwrap(); }
-                } else {
-                    quote! { ::alloc::ffi::CString::new(v).unwrap() }
-                }
+            Type::Group(g) => {
+                return self.get_value_token(
+                    name, attrs, &g.elem, n, prev_ty, value, target, iterable
+                );
             },
-            "char" => {
-                if n == 0 {
-                    quote! { self.#name_ident = v.chars().next().unwrap_or_default(); }
+            Type::Tuple(tuple) => {
+                let len = tuple.elems.len();
+                let items = tuple
+                    .elems
+                    .iter()
+                    .map(|ty| {
+                        self.get_value_token(
+                            name,
+                            attrs,
+                            ty,
+                            n + 1,
+                            Some("()"),
+                            true,
+                            None,
+                            iterable
+                        )
+                    })
+                    .collect::<Vec<_>>();
+                *iterable = false;
+                let token = quote! {{
+                    let mut parts = v.splitn(#len, ',');
+                    (#({ let v = parts.next().unwrap_or_default().trim(); #items },)*)
+                }};
+                return if n == 0 {
+                    quote! { self.#name_ident = #token; }
                 } else {
-                    quote! { v.chars().next().unwrap_or_default() }
-                }
+                    token
+                };
             },
-            "str" => {
-                if n == 0 {
-                    quote! { self.#name_ident = v; }
-                } else {
-                    quote! {v}
-                }
+            Type::Path(ty) => ty,
+            ty => panic!("This type not supported yet: {}", quote! { #ty })
+        };
+        let (ty_name, args) = Self::type_name(ty);
+        let ty_ident: TypePath = parse_str(&ty_name)
+            .map_err(|e| format!("{name}: {ty_name} - {e}"))
+            .unwrap();
+        let next_ty = args.first().map(|ty| Self::type_name_of(ty));
+        let is_parse = attrs.iter().any(|a| a.path().is_ident("extend_parse"));
+        let expected = quote! { #ty }.to_string().replace(' ', "");
+        let parse_token = quote! {
+            v.parse::<#ty>().map_err(|_| ::app_base::extend::ExtendErrors::expected(#expected))?
+        };
+        let cstring_token = quote! {
+            ::app_base::alloc::ffi::CString::new(v)
+                .map_err(|_| ::app_base::extend::ExtendErrors::expected("string without nul"))?
+        };
+        let assign = |token: TokenStream2| {
+            if n == 0 {
+                quote! { self.#name_ident = #token; }
+            } else {
+                token
+            }
+        };
+
+        *iterable = false;
+        match (ty_name.as_str(), args.as_slice()) {
+            ("bool", _) => {
+                assign(quote! {
+                    ["0","off","false","","\0"].contains(&v.to_lowercase().as_str()) == false
+                })
             },
-            "String" => {
-                if n == 0 {
-                    quote! { self.#name_ident = v.to_string(); }
-                } else {
-                    quote! { v.to_string() }
-                }
+            (
+                "i8" | "i16" | "i32" | "i64" | "i128" | "u8" | "u16" | "u32" | "u64"
+                | "u128" | "f32" | "f64" | "f128" | "isize" | "usize" | "c_char"
+                | "c_short" | "c_ushort" | "c_int" | "c_uint" | "c_long" | "c_ulong"
+                | "c_longlong" | "c_ulonglong" | "c_double" | "c_float",
+                _
+            ) => assign(parse_token),
+            ("c_void", _) => assign(quote! { #cstring_token.into_raw().cast() }),
+            ("CString", _) => assign(cstring_token),
+            ("char", _) => assign(quote! { v.chars().next().unwrap_or_default() }),
+            ("str", _) => assign(quote! { v }),
+            ("String", _) => {
+                assign(quote! { ::app_base::alloc::string::ToString::to_string(v) })
             },
-            "Box" | "Arc" | "Rc" | "NonNull" if next_ty == Some("CStr".into()) => {
-                if n == 0 {
-                    quote! { self.#name_ident =  #ty_ident::from(Box::leak(::alloc::ffi::CString::new(v).unwrap().into_boxed_c_str())); }
-                } else {
-                    quote! { #ty_ident::from(Box::leak(::alloc::ffi::CString::new(v).unwrap().into_boxed_c_str())) }
-                }
+            ("PathBuf", _) => assign(quote! { #ty_ident::from(v) }),
+            ("Duration", _) => {
+                assign(quote! {{
+                    let (number, unit) = v.trim().split_at(
+                        v.trim().find(|c: char| c.is_ascii_digit() == false).unwrap_or(v.trim().len())
+                    );
+                    let nanos: ::core::option::Option<u64> = match unit.trim() {
+                        "ns" => ::core::option::Option::Some(1),
+                        "us" => ::core::option::Option::Some(1_000),
+                        "ms" => ::core::option::Option::Some(1_000_000),
+                        "" | "s" => ::core::option::Option::Some(1_000_000_000),
+                        "m" | "min" => ::core::option::Option::Some(60_000_000_000),
+                        "h" => ::core::option::Option::Some(3_600_000_000_000),
+                        "d" => ::core::option::Option::Some(86_400_000_000_000),
+                        _ => ::core::option::Option::None
+                    };
+                    #ty_ident::from_nanos(
+                        number
+                            .parse::<u64>()
+                            .ok()
+                            .zip(nanos)
+                            .and_then(|(number, nanos)| number.checked_mul(nanos))
+                            .ok_or_else(|| {
+                                ::app_base::extend::ExtendErrors::expected(
+                                    "duration with unit: ns, us, ms, s, m, h, d"
+                                )
+                            })?
+                    )
+                }})
             },
-            "Box" | "Arc" | "Rc" | "NonNull" if next_ty == Some("str".into()) => {
-                if n == 0 {
-                    quote! { self.#name_ident = #ty_ident::from(v); }
-                } else {
-                    quote! { #ty_ident::from(v) }
-                }
+            ("Box" | "Arc" | "Rc" | "NonNull", _)
+                if next_ty.as_deref() == Some("CStr") =>
+            {
+                assign(quote! {
+                    #ty_ident::from(::app_base::alloc::boxed::Box::leak(#cstring_token.into_boxed_c_str()))
+                })
             },
-            "Option" => {
-                let token = self.get_value_token(name, attrs, types, Some(&ty), iterable);
-                if n == 0 {
-                    quote! { self.#name_ident = #ty_ident::from(#token); }
-                } else {
-                    quote! { #ty_ident::from(#token) }
-                }
+            ("Box" | "Arc" | "Rc" | "NonNull", _)
+                if next_ty.as_deref() == Some("str") =>
+            {
+                assign(quote! { #ty_ident::from(v) })
             },
-            "Box" => {
-                let token = self.get_value_token(name, attrs, types, Some(&ty), iterable);
-                if n == 0 {
-                    quote! { self.#name_ident.extend(#token); }
-                } else {
-                    quote! { #ty_ident::new(#token) }
-                }
+            ("Option", [inner]) => {
+                let token = self.get_value_token(
+                    name,
+                    attrs,
+                    inner,
+                    n + 1,
+                    Some(&ty_name),
+                    value,
+                    None,
+                    iterable
+                );
+                assign(quote! { #ty_ident::from(#token) })
             },
-            "Arc" | "Rc" => {
-                let token = self.get_value_token(name, attrs, types, Some(&ty), iterable);
-                if n == 0 {
-                    if *iterable {
-                        quote! {
-                            #ty_ident::get_mut(&mut self.#name_ident)
-                                .ok_or_else(||
-                                    format!(
-                                        "Could not get mutable reference of {}.{}",
-                                        ::core::any::type_name::<#ty_ident<Self>>(),
-                                        #name
-                                    )
-                                )
-                                .unwrap()
-                                .extend(#token);
+            ("Box" | "Arc" | "Rc" | "RefCell", [inner]) => {
+                let mut inner_iterable = false;
+                self.get_value_token(
+                    name,
+                    attrs,
+                    inner,
+                    n + 1,
+                    Some(&ty_name),
+                    value,
+                    None,
+                    &mut inner_iterable
+                );
+
+                // Nested keys extend the existing value inside of the field
+                if n == 0 && inner_iterable {
+                    let target = match ty_name.as_str() {
+                        "Box" => quote! { &mut *self.#name_ident },
+                        "RefCell" => quote! { &mut *self.#name_ident.borrow_mut() },
+                        // Shared value can't be extended in place, each nested key
+                        // is reported with its value
+                        _ => {
+                            let expected =
+                                [ty_name.as_str(), " without other references"].concat();
+                            let items = Self::items_token(name);
+                            quote! {
+                                #ty_ident::get_mut(&mut self.#name_ident).ok_or_else(|| {
+                                    let (items, mut errors) = ::app_base::extend::ExtendErrors::partition(#items);
+                                    errors.0.extend(items.into_iter().flat_map(|(key, v)| {
+                                        ::app_base::extend::ExtendErrors::expected(#expected).value(v).key(key).0
+                                    }));
+                                    errors
+                                })?
+                            }
                         }
-                    } else {
-                        quote! { self.#name_ident = #ty_ident::new(#token); }
-                    }
-                } else {
-                    quote! { #ty_ident::new(#token) }
+                    };
+                    return self.get_value_token(
+                        name,
+                        attrs,
+                        inner,
+                        n + 1,
+                        Some(&ty_name),
+                        value,
+                        Some(&target),
+                        iterable
+                    );
                 }
+
+                let token = self.get_value_token(
+                    name,
+                    attrs,
+                    inner,
+                    n + 1,
+                    Some(&ty_name),
+                    value,
+                    None,
+                    iterable
+                );
+                assign(quote! { #ty_ident::new(#token) })
             },
-            "RefCell" => {
-                let token = self.get_value_token(name, attrs, types, Some(&ty), iterable);
-                if n == 0 {
-                    if *iterable {
-                        quote! { self.#name_ident.borrow_mut().extend(#token); }
-                    } else {
-                        quote! { self.#name_ident = #ty_ident::new(#token); }
-                    }
-                } else {
-                    quote! { #ty_ident::new(#token) }
-                }
+            ("NonZero", [inner]) if prev_ty == Some("Option") => {
+                let token = self.get_value_token(
+                    name,
+                    attrs,
+                    inner,
+                    n + 1,
+                    Some(&ty_name),
+                    value,
+                    None,
+                    iterable
+                );
+                assign(quote! { #ty_ident::new(#token) })
             },
-            "Cell" | "NonZero" | "NonNull" => {
-                let token = self.get_value_token(name, attrs, types, Some(&ty), iterable);
-                if n == 0 {
-                    quote! { self.#name_ident = #ty_ident::new(#token); }
-                } else {
-                    quote! { #ty_ident::new(#token) }
-                }
+            (
+                "NonZeroU8" | "NonZeroU16" | "NonZeroU32" | "NonZeroU64" | "NonZeroU128"
+                | "NonZeroUsize" | "NonZeroI8" | "NonZeroI16" | "NonZeroI32"
+                | "NonZeroI64" | "NonZeroI128" | "NonZeroIsize",
+                _
+            ) if prev_ty == Some("Option") => {
+                let primitive = format_ident!("{}", ty_name[7..].to_lowercase());
+                let expected = primitive.to_string();
+                assign(quote! {
+                    #ty_ident::new(
+                        v.parse::<#primitive>().map_err(|_| ::app_base::extend::ExtendErrors::expected(#expected))?
+                    )
+                })
             },
-            "Vec" | "HashSet" | "IndexSet" => {
-                let token = self.get_value_token(name, attrs, types, Some(&ty), iterable);
+            (
+                "NonZero" | "NonZeroU8" | "NonZeroU16" | "NonZeroU32" | "NonZeroU64"
+                | "NonZeroU128" | "NonZeroUsize" | "NonZeroI8" | "NonZeroI16"
+                | "NonZeroI32" | "NonZeroI64" | "NonZeroI128" | "NonZeroIsize",
+                _
+            ) => assign(parse_token),
+            ("Cell" | "NonNull", [inner]) => {
+                let token = self.get_value_token(
+                    name,
+                    attrs,
+                    inner,
+                    n + 1,
+                    Some(&ty_name),
+                    value,
+                    None,
+                    iterable
+                );
+                assign(quote! { #ty_ident::new(#token) })
+            },
+            ("Vec" | "VecDeque" | "HashSet" | "IndexSet" | "BTreeSet", [inner]) => {
+                let token = self.get_value_token(
+                    name,
+                    attrs,
+                    inner,
+                    n + 1,
+                    Some(&ty_name),
+                    true,
+                    None,
+                    iterable
+                );
+                *iterable = false;
                 let token = quote! {
-                    v.split_terminator(',').map(|s| { let v = s.trim(); #token })
+                    ::app_base::extend::ExtendErrors::collect(
+                        v.split_terminator(',').map(|s| {
+                            let v = s.trim();
+                            (|| -> ::core::result::Result<_, ::app_base::extend::ExtendErrors> {
+                                let value = #token;
+                                ::core::result::Result::Ok(value)
+                            })()
+                            .map_err(|e| e.value(v))
+                        })
+                    )?
                 };
                 if n == 0 {
                     quote! { self.#name_ident.extend(#token); }
@@ -345,53 +574,134 @@ impl ExtendMacros {
                     quote! { #ty_ident::from_iter(#token) }
                 }
             },
-            ty if ty.starts_with("(") && ty.ends_with(")") => {
+            ("HashMap" | "IndexMap" | "BTreeMap", [key, val, ..]) if value == false => {
+                let key = self.get_value_token(
+                    name,
+                    attrs,
+                    key,
+                    n + 1,
+                    Some(&ty_name),
+                    true,
+                    None,
+                    iterable
+                );
+                let val = self.get_value_token(
+                    name,
+                    attrs,
+                    val,
+                    n + 1,
+                    Some(&ty_name),
+                    true,
+                    None,
+                    iterable
+                );
+                let items = Self::items_token(name);
+                *iterable = true;
                 let token = quote! {
-                    v.parse::<#ty_ident>().map_err(|_| format!("Failed parse '{v}' to type {}", #ty)).unwrap()
+                    #items.map(|item| item.and_then(|(key, v)| {
+                        (|| -> ::core::result::Result<_, ::app_base::extend::ExtendErrors> {
+                            ::core::result::Result::Ok(({ let v = key; #key }, #val))
+                        })()
+                        .map_err(|e| e.value(v).key(key))
+                    }))
                 };
-                if n == 0 {
-                    quote! { self.#name_ident = #token; }
-                } else {
-                    token
-                }
-            },
-            _ if is_parse => {
-                if n == 0 {
-                    quote! { self.#name_ident = v.parse::<#ty_ident>().unwrap(); }
-                } else {
-                    quote! { v.parse::<#ty_ident>().unwrap() }
+
+                // Valid keys are inserted even if others are invalid
+                match target {
+                    Some(target) => {
+                        quote! {
+                            let (items, errors) = ::app_base::extend::ExtendErrors::partition(#token);
+                            ::core::iter::Extend::extend(#target, items);
+                            errors.into_result()?;
+                        }
+                    },
+                    None => {
+                        quote! {
+                            #ty_ident::from_iter(::app_base::extend::ExtendErrors::collect(#token)?)
+                        }
+                    }
                 }
             },
+            _ if is_parse || value => assign(parse_token),
             _ => {
+                let items = Self::items_token(name);
                 *iterable = true;
                 let token = quote! {
-                     map.iter()
-                        .filter_map(|(&name, &value)| {
-                            (value.is_some() && (name.starts_with(concat!(#name, ".")) || name == #name))
-                                .then(|| {
-                                    let mut name = name.trim_start_matches(#name).trim_start_matches(".");
-                                    let mut value = value;
-
-                                    if name.is_empty() && let Some((n, v)) = value.unwrap().split_once("=") {
-                                        name = String::leak([name,".",n.trim()].concat()).trim_start_matches(".");
-                                        value = Some(v.trim());
-                                    }
-
-                                    (name.into(), value.map(|v| v.into()))
-                                })
-                        })
+                    items.into_iter().map(|(name, value)| (name.into(), ::core::option::Option::Some(value.into())))
                 };
-                if n == 0 {
-                    quote! { self.#name_ident.extend(#token); }
-                } else if n == 1
-                    && prev_ty.map(|p| ["Box", "Arc", "Rc", "RefCell"].contains(&p))
-                        == Some(true)
-                {
-                    quote! { #token }
-                } else {
-                    quote! { #ty_ident::from_iter(#token) }
+                // Valid pairs extend the target even if others are malformed
+                let extend = |target: &TokenStream2| {
+                    quote! {
+                        let (items, mut errors) = ::app_base::extend::ExtendErrors::partition(#items);
+                        errors.merge(::app_base::extend::TryExtend::try_extend(#target, #token));
+                        errors.into_result()?;
+                    }
+                };
+                match target {
+                    Some(target) => extend(target),
+                    None => {
+                        let extend = extend(&quote! { &mut this });
+                        quote! {{
+                            let mut this: #ty_ident = ::core::default::Default::default();
+                            #extend
+                            this
+                        }}
+                    }
                 }
             }
         }
     }
+
+    /// Iterator of `Result<(name, value)>` of keys nested in *name*, malformed
+    /// pairs are errors
+    fn items_token(name: &str) -> TokenStream2 {
+        quote! {
+             map.iter()
+                .filter(|&(&name, &value)| {
+                    value.is_some() && (name.starts_with(::core::concat!(#name, ".")) || name == #name)
+                })
+                .flat_map(|(&name, &value)| {
+                    let name = name.trim_start_matches(#name).trim_start_matches(".");
+                    let value = value.unwrap_or_default();
+
+                    // "a = 1, b = 2" without nested key is a list of pairs
+                    match name.is_empty() && value.contains('=') {
+                        true => ::app_base::extend::ExtendErrors::pairs(value),
+                        false => ::app_base::alloc::vec![::core::result::Result::Ok((name, value))]
+                    }
+                })
+        }
+    }
+
+    /// Last segment of type path without generics and its type arguments
+    fn type_name(ty: &TypePath) -> (String, Vec<&Type>) {
+        let Some(segment) = ty.path.segments.last() else {
+            return (String::new(), Vec::new());
+        };
+
+        let args = match &segment.arguments {
+            PathArguments::AngleBracketed(args) => {
+                args.args
+                    .iter()
+                    .filter_map(|arg| {
+                        match arg {
+                            GenericArgument::Type(ty) => Some(ty),
+                            _ => None
+                        }
+                    })
+                    .collect()
+            },
+            _ => Vec::new()
+        };
+
+        (segment.ident.to_string(), args)
+    }
+
+    fn type_name_of(ty: &Type) -> String {
+        match ty {
+            Type::Path(ty) => Self::type_name(ty).0,
+            Type::Reference(r) => Self::type_name_of(&r.elem),
+            _ => String::new()
+        }
+    }
 }
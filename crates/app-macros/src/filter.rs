@@ -0,0 +1,109 @@
+use {
+    alloc::{string::ToString, vec::Vec},
+    proc_macro::TokenStream,
+    proc_macro2::TokenStream as TokenStream2,
+    quote::quote,
+    syn::{
+        Data, DataStruct, DeriveInput, Expr, Fields, FieldsNamed, LitStr, Path,
+        parse_macro_input
+    }
+};
+#[cfg(not(feature = "std"))]
+#[allow(unused_imports)]
+use libc_print::std_name::*;
+
+#[derive(Default)]
+pub(crate) struct Filter;
+
+impl Filter {
+    /// Names of filters with functions of `app_base::filters`
+    const FILTERS: [(&str, &str); 10] = [
+        ("trim", "trim"),
+        ("lowercase", "lowercase"),
+        ("uppercase", "uppercase"),
+        ("capitalize", "to_capitalize"),
+        ("collapse_ws", "collapse_ws"),
+        ("slug", "to_slug"),
+        ("strip_html", "strip_html"),
+        ("sanitize_html", "sanitize_html"),
+        ("phone", "filter_phone_e164"),
+        ("digits", "filter_digits")
+    ];
+
+    pub fn derive(self, input: TokenStream) -> TokenStream {
+        let input = parse_macro_input!(input as DeriveInput);
+        let expanded = self
+            .parse(input)
+            .unwrap_or_else(syn::Error::into_compile_error);
+        TokenStream::from(expanded)
+    }
+
+    fn parse(&self, input: DeriveInput) -> syn::Result<TokenStream2> {
+        let struct_name = &input.ident;
+        let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+        let Data::Struct(DataStruct {
+            fields: Fields::Named(FieldsNamed { named, .. }),
+            ..
+        }) = &input.data
+        else {
+            return Err(syn::Error::new_spanned(
+                &input.ident, "Only structs with named fields are supported"
+            ));
+        };
+
+        let mut calls = Vec::new();
+
+        for field in named {
+            let ident = field.ident.as_ref().unwrap();
+
+            for attr in field.attrs.iter().filter(|a| a.path().is_ident("filter")) {
+                attr.parse_nested_meta(|meta| {
+                    // custom function: #[filter(with = "path::to::fn")]
+                    if meta.path.is_ident("with") {
+                        let path: Path = meta.value()?.parse::<LitStr>()?.parse()?;
+                        calls.push(quote! {
+                            ::app_base::filters::FilterValue::filter_value(&mut self.#ident, &#path);
+                        });
+                        return Ok(());
+                    }
+
+                    // nested struct: #[filter(nested)]
+                    if meta.path.is_ident("nested") {
+                        calls.push(quote! {
+                            ::app_base::filters::Filter::filter(&mut self.#ident);
+                        });
+                        return Ok(());
+                    }
+
+                    let name = meta
+                        .path
+                        .get_ident()
+                        .map(|i| i.to_string())
+                        .unwrap_or_default();
+                    let Some((_, func)) = Self::FILTERS.iter().find(|(n, _)| *n == name)
+                    else {
+                        return Err(meta.error(format!(
+                            "Unknown filter, expected one of: {}, nested, with",
+                            Self::FILTERS.map(|(n, _)| n).join(", ")
+                        )));
+                    };
+                    let func: Expr = syn::parse_str(&format!("::app_base::filters::{func}"))?;
+                    calls.push(quote! {
+                        ::app_base::filters::FilterValue::filter_value(&mut self.#ident, &#func);
+                    });
+
+                    Ok(())
+                })?;
+            }
+        }
+
+        Ok(quote! {
+            impl #impl_generics ::app_base::filters::Filter for #struct_name #ty_generics #where_clause {
+                fn filter(&mut self) {
+                    #(#calls)*
+                }
+            }
+        })
+    }
+}
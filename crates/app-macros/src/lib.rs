@@ -6,26 +6,66 @@ extern crate alloc;
 extern crate core;
 extern crate proc_macro2;
 
+mod app_config;
+mod app_module;
 mod extend;
 mod struct_fields;
 mod from_static;
+mod filter;
 
 use {
-    crate::{extend::ExtendMacros, from_static::FromStatic, struct_fields::StructFields},
+    crate::{
+        app_config::AppConfig, app_module::AppModule, extend::ExtendMacros,
+        filter::Filter, from_static::FromStatic, struct_fields::StructFields
+    },
     proc_macro::TokenStream
 };
 
-#[proc_macro_derive(Extend, attributes(extend_parse, extend_skip))]
+#[proc_macro_derive(Extend, attributes(extend_parse, extend_skip, extend_rename))]
 pub fn extend(input: TokenStream) -> TokenStream {
     ExtendMacros::default().derive(input)
 }
 
-#[proc_macro_derive(ExtendFromIter, attributes(extend_parse, extend_skip))]
+#[proc_macro_derive(ExtendFromIter, attributes(extend_parse, extend_skip, extend_rename))]
 pub fn extend_from_iter(input: TokenStream) -> TokenStream {
     ExtendMacros { from_iter: true }.derive(input)
 }
 
-#[proc_macro_derive(StructFields)]
+/// `LoadArgs`, `LoadEnv`, `LoadDirs` and `Iter` of config
+///
+/// `#[config(prefix = "web.jwt", env_prefix = "JWT")]` on struct sets names
+/// of options, `web.jwt.secret`, `--web-jwt-secret` and `JWT_SECRET`,
+/// `arg_prefix = "jwt"` sets `--jwt-secret`. `command = "migrator"` adds
+/// options only for the command, `after_args = "method"` calls
+/// `self.method(args)` after loading them. Fields take
+/// `#[config(env = "NAME", arg = "name", short = "-n", no_env, no_arg, secret,
+/// skip)]`, `flag` for options without value, `#[config(dir = "data")]` for
+/// paths relative to `Dirs` or `#[config(nested)]` for configs deriving it
+/// too.
+#[proc_macro_derive(AppConfig, attributes(config))]
+pub fn app_config(input: TokenStream) -> TokenStream {
+    AppConfig.derive(input)
+}
+
+/// `AppModuleExt` of module struct and its `extern "C"` entry point
+///
+/// `#[app_module(command = "greet", config = Config)]` sets `COMMAND` and
+/// `type Config`, `AppSimpleConfig` by default. The first paragraph of doc
+/// comment is `DESCRIPTION`, the whole one is shown by help, which is
+/// rendered by `help_text(bin, name, version)` of module. Fields with
+/// `#[arg(name = "...", short = "-n", position = 2, default = "...")]` or
+/// `#[arg]` are options of command, which are loaded before `on_boot()` of
+/// `AppModuleHooks` implemented by module. `bool` fields are flags, while
+/// `Option<bool>` takes a value. Entry point is named like `module_greet`
+/// for `GreetModule`, `entry = "name"` renames it and `no_entry` skips it.
+/// C calls entry points with `App<AppSimpleConfig>`, so `config` requires
+/// `no_entry`.
+#[proc_macro_attribute]
+pub fn app_module(attr: TokenStream, input: TokenStream) -> TokenStream {
+    AppModule::default().attribute(attr, input)
+}
+
+#[proc_macro_derive(StructFields, attributes(skip_as))]
 pub fn struct_fields(input: TokenStream) -> TokenStream {
     StructFields.derive(input)
 }
@@ -34,3 +74,12 @@ pub fn struct_fields(input: TokenStream) -> TokenStream {
 pub fn from_static(input: TokenStream) -> TokenStream {
     FromStatic.derive(input)
 }
+
+/// Field filters applied by `app_base::filters::Filter::filter()`
+///
+/// `#[filter(trim, lowercase)]`, `#[filter(with = "path::to::fn")]` or
+/// `#[filter(nested)]` for fields implementing `Filter`.
+#[proc_macro_derive(Filter, attributes(filter))]
+pub fn filter(input: TokenStream) -> TokenStream {
+    Filter.derive(input)
+}
@@ -1,21 +1,15 @@
 use {
     alloc::{
-        borrow::Cow,
         string::{String, ToString},
         vec::Vec
     },
-    core::{
-        iter::{Enumerate, Peekable},
-        marker::PhantomData
-    },
     proc_macro::TokenStream,
-    proc_macro2::{Span, TokenStream as TokenStream2, TokenTree},
+    proc_macro2::{TokenStream as TokenStream2, TokenTree},
     quote::{ToTokens, quote},
     syn::{
-        Attribute, Data, DataStruct, DeriveInput, Field, Fields, FieldsNamed,
-        GenericParam, Generics, Ident, ImplGenerics, Lifetime, LifetimeParam, Path,
-        TypePath, WhereClause, parse_macro_input, parse_str, punctuated::Punctuated,
-        token::Comma
+        Attribute, Data, DataStruct, DeriveInput, Expr, ExprLit, Fields, FieldsNamed,
+        Lit, LitStr, Meta, meta::ParseNestedMeta, parse_macro_input, parse_quote,
+        token::Paren
     }
 };
 #[cfg(not(feature = "std"))]
@@ -60,40 +54,68 @@ impl StructFields {
             })
             .collect::<Vec<_>>();
 
-        /*
-        let mut fields_into_bounds = parse_str::<Generics>("<'a, T>").unwrap();
-        let fields_into = named
-            .iter()
-            .filter_map(|f| {
-                if f.attrs.iter().any(|a| a.path().is_ident("skip_as")) {
-                    return None;
-                }
-                f.ident.as_ref().map(|id| {
-                    let ty = f.ty.to_token_stream().to_string();
-                    fields_into_bounds
-                        .type_params_mut()
-                        .last()
-                        .unwrap()
-                        .bounds
-                        .push(parse_str(&format!("TryFrom<&'a {ty}>")).unwrap());
-                    quote! {(&self.#id).try_into().ok()}
-                })
-            })
-            .collect::<Vec<_>>();
+        let rename_all = Self::serde_rename_all(&input.attrs);
+        let (mut fields_info, mut get_fields) = (Vec::new(), Vec::new());
+        for field in named {
+            let ident = field.ident.as_ref().unwrap();
+            let name = ident.to_string();
+            let ty = field.ty.to_token_stream().to_string();
+            let serde_name = Self::serde_rename(&field.attrs).unwrap_or_else(|| {
+                rename_all
+                    .as_deref()
+                    .map(|rule| Self::rename_case(&name, rule))
+                    .unwrap_or_else(|| name.clone())
+            });
+            let doc = Self::doc(&field.attrs);
+            let attrs = field
+                .attrs
+                .iter()
+                .filter(|a| a.path().is_ident("doc") == false)
+                .map(|a| a.meta.to_token_stream().to_string());
 
-            quote! {
-                impl #impl_generics #struct_name #ty_generics #where_clause {
-                    pub fn fields_as #fields_into_bounds (&'a self) -> Vec<T> {
-                        [
-                            #(#fields_into),*
-                        ]
-                        .into_iter()
-                        .filter_map(|r| r)
-                        .collect()
-                    }
+            fields_info.push(quote! {
+                ::app_base::fields::FieldInfo {
+                    name: #name,
+                    ty: #ty,
+                    serde_name: #serde_name,
+                    doc: #doc,
+                    attrs: &[#(#attrs),*]
                 }
+            });
+
+            // Only `'static` types are `Any`
+            if Self::has_lifetime(field.ty.to_token_stream()) {
+                continue;
             }
-        */
+            let names = match serde_name == name {
+                true => quote! { #name },
+                false => quote! { #name | #serde_name }
+            };
+            get_fields.push(quote! { #names => Some(&self.#ident) });
+        }
+
+        // Fields are converted by `TryFrom<&Field>` of value, `#[skip_as]` skips them
+        let (as_types, as_idents): (Vec<_>, Vec<_>) = named
+            .iter()
+            .filter(|f| f.attrs.iter().any(|a| a.path().is_ident("skip_as")) == false)
+            .map(|f| (&f.ty, f.ident.as_ref().unwrap()))
+            .unzip();
+        let as_len = as_idents.len();
+
+        // Values of type parameters are `Any` only if they're `'static`
+        let mut generics_static = input.generics.clone();
+        let params = input
+            .generics
+            .type_params()
+            .map(|p| &p.ident)
+            .collect::<Vec<_>>();
+        generics_static
+            .make_where_clause()
+            .predicates
+            .extend(params.iter().map(|p| -> syn::WherePredicate {
+                parse_quote! { #p: 'static }
+            }));
+        let (.., where_static) = generics_static.split_for_impl();
 
         quote! {
             impl #impl_generics #struct_name #ty_generics #where_clause {
@@ -109,7 +131,147 @@ impl StructFields {
                 pub const fn field_types(&self) -> &'static [(&'static str, &'static str)] {
                     Self::FIELD_TYPES
                 }
+
+                /// Values of fields converted to *FieldAs*, fields which failed
+                /// conversion are skipped
+                pub fn fields_as<'fields, FieldAs>(&'fields self) -> impl Iterator<Item = FieldAs>
+                where
+                    #(FieldAs: TryFrom<&'fields #as_types>,)*
+                {
+                    <[Option<FieldAs>; #as_len]>::into_iter([
+                        #(FieldAs::try_from(&self.#as_idents).ok()),*
+                    ])
+                    .flatten()
+                }
+            }
+
+            impl #impl_generics ::app_base::fields::StructFields for #struct_name #ty_generics #where_static {
+                const FIELDS: &'static [::app_base::fields::FieldInfo] = &[#(#fields_info),*];
+
+                fn get_field(&self, name: &str) -> Option<&dyn ::core::any::Any> {
+                    match name {
+                        #(#get_fields,)*
+                        _ => None
+                    }
+                }
+            }
+        }
+    }
+
+    /// Doc comment of attributes with trimmed lines
+    fn doc(attrs: &[Attribute]) -> String {
+        attrs
+            .iter()
+            .filter_map(|a| {
+                match &a.meta {
+                    Meta::NameValue(meta) if meta.path.is_ident("doc") => {
+                        match &meta.value {
+                            Expr::Lit(ExprLit { lit: Lit::Str(s), .. }) => {
+                                Some(s.value().trim().to_string())
+                            },
+                            _ => None
+                        }
+                    },
+                    _ => None
+                }
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    /// `#[serde(rename = "...")]` or serialized name of
+    /// `#[serde(rename(serialize = "..."))]`
+    fn serde_rename(attrs: &[Attribute]) -> Option<String> {
+        Self::serde_option(attrs, "rename")
+    }
+
+    /// Rule of `#[serde(rename_all = "...")]` of struct
+    fn serde_rename_all(attrs: &[Attribute]) -> Option<String> {
+        Self::serde_option(attrs, "rename_all")
+    }
+
+    fn serde_option(attrs: &[Attribute], name: &str) -> Option<String> {
+        let mut value = None;
+        for attr in attrs.iter().filter(|a| a.path().is_ident("serde")) {
+            // Invalid attributes are reported by serde itself
+            let _ = attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident(name) && meta.input.peek(Paren) {
+                    meta.parse_nested_meta(|meta| {
+                        if meta.path.is_ident("serialize") {
+                            value = Some(meta.value()?.parse::<LitStr>()?.value());
+                            Ok(())
+                        } else {
+                            Self::skip_meta(meta)
+                        }
+                    })
+                } else if meta.path.is_ident(name) {
+                    value = Some(meta.value()?.parse::<LitStr>()?.value());
+                    Ok(())
+                } else {
+                    Self::skip_meta(meta)
+                }
+            });
+        }
+        value
+    }
+
+    /// Skips value of unknown option, i.e. `default = "..."` or `bound(...)`
+    fn skip_meta(meta: ParseNestedMeta) -> syn::Result<()> {
+        if meta.input.peek(syn::Token![=]) {
+            meta.value()?.parse::<Expr>()?;
+        } else if meta.input.peek(Paren) {
+            meta.parse_nested_meta(Self::skip_meta)?;
+        }
+        Ok(())
+    }
+
+    /// Name of field by rule of `rename_all`
+    fn rename_case(name: &str, rule: &str) -> String {
+        let pascal = || {
+            name.split('_')
+                .map(|word| {
+                    let mut chars = word.chars();
+                    chars
+                        .next()
+                        .map(|c| c.to_uppercase().chain(chars).collect::<String>())
+                        .unwrap_or_default()
+                })
+                .collect::<String>()
+        };
+
+        match rule {
+            "lowercase" => name.to_lowercase(),
+            "UPPERCASE" | "SCREAMING_SNAKE_CASE" => name.to_uppercase(),
+            "PascalCase" => pascal(),
+            "camelCase" => {
+                let pascal = pascal();
+                let mut chars = pascal.chars();
+                chars
+                    .next()
+                    .map(|c| c.to_lowercase().chain(chars).collect())
+                    .unwrap_or_default()
+            },
+            "kebab-case" => name.replace('_', "-"),
+            "SCREAMING-KEBAB-CASE" => name.replace('_', "-").to_uppercase(),
+            _ => name.to_string()
+        }
+    }
+
+    /// Whether type has lifetime other than `'static`
+    fn has_lifetime(tokens: TokenStream2) -> bool {
+        let mut tokens = tokens.into_iter().peekable();
+        while let Some(token) = tokens.next() {
+            match token {
+                TokenTree::Punct(p) if p.as_char() == '\'' => {
+                    if matches!(tokens.peek(), Some(TokenTree::Ident(i)) if i != "static")
+                    {
+                        return true;
+                    }
+                },
+                TokenTree::Group(g) if Self::has_lifetime(g.stream()) => return true,
+                _ => ()
             }
         }
+        false
     }
 }
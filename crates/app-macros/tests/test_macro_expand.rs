@@ -5,14 +5,23 @@ extern crate alloc;
 
 use {
     alloc::{rc::Rc, sync::Arc},
+    app_base::extend::{ExtendError, TryExtend},
     app_macros::*,
     core::{
-        cell::RefCell, error::Error, ffi::*, marker::PhantomData, num::NonZero,
-        ptr::NonNull, str::FromStr
+        cell::RefCell,
+        error::Error,
+        ffi::*,
+        marker::PhantomData,
+        num::{NonZero, NonZeroU32},
+        ptr::NonNull,
+        str::FromStr,
+        time::Duration
     },
+    indexmap::IndexMap,
     std::{
-        collections::{HashMap, HashSet},
+        collections::{BTreeMap, HashMap, HashSet},
         ffi::CString,
+        path::PathBuf,
         time::Instant
     }
 };
@@ -145,6 +154,15 @@ fn test_extend() -> Result<(), Box<dyn Error>> {
     assert_eq!(foo.zar.a, Some(-333));
     assert_eq!(foo.zar.b, Some(vec![1, 2, 3].into()));
 
+    // Shared value isn't extended in place
+    let bar = foo.bar.clone();
+    let errors = foo.try_extend([("bar.x", Some("Shared"))]).unwrap_err();
+    assert_eq!(
+        errors.to_string(),
+        "Invalid value 'Shared' of bar.x, expected Arc without other references"
+    );
+    assert_eq!(bar.x, "This is Bar");
+
     Ok(())
 }
 
@@ -179,3 +197,167 @@ fn gen_values2() -> Vec<(&'static str, Option<&'static str>)> {
         ("bar.z.b", "  -123, 0, 123 ".into()),
     ]
 }
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, ExtendFromIter)]
+enum Mode {
+    #[default]
+    Fast,
+    #[extend_rename = "slow-mode"]
+    Slow
+}
+
+#[derive(Debug, Default, ExtendFromIter)]
+struct Typed {
+    mode: Mode,
+    fallback: Option<Mode>,
+    modes: Vec<Mode>,
+    ports: HashMap<String, u16>,
+    timeouts: IndexMap<String, Option<Duration>>,
+    ranks: BTreeMap<u8, Mode>,
+    groups: BTreeMap<String, Vec<u16>>,
+    timeout: Duration,
+    path: PathBuf,
+    retries: Option<NonZero<u16>>,
+    pair: (i32, String),
+    point: Option<(f32, f32, bool)>
+}
+
+#[derive(Debug, Extend)]
+struct Limits {
+    workers: NonZeroU32,
+    memory: NonZero<u64>
+}
+
+#[test]
+fn test_extend_types() {
+    let typed = Typed::from_iter([
+        ("mode", Some("SLOW-MODE")),
+        ("modes", Some("fast, Slow-Mode")),
+        ("ports", Some("http = 80, https = 443")),
+        ("ports.ssh", Some("22")),
+        ("timeouts.read", Some("30s")),
+        ("timeouts.write", Some("5m")),
+        ("ranks.1", Some("fast")),
+        ("groups", Some("web = 80, 443, ssh = 22,")),
+        ("timeout", Some("250ms")),
+        ("path", Some("/var/lib/app")),
+        ("retries", Some("0")),
+        ("pair", Some(" -7, seven, 7 ")),
+        ("point", Some("1.5,-2,on"))
+    ]);
+
+    assert_eq!((typed.mode, typed.fallback), (Mode::Slow, None));
+    assert_eq!(typed.modes, [Mode::Fast, Mode::Slow]);
+    assert_eq!(
+        typed.ports,
+        HashMap::from_iter([
+            ("http".into(), 80),
+            ("https".into(), 443),
+            ("ssh".into(), 22)
+        ])
+    );
+    assert_eq!(
+        typed.timeouts.into_iter().collect::<Vec<_>>(),
+        [
+            ("read".into(), Some(Duration::from_secs(30))),
+            ("write".into(), Some(Duration::from_secs(300)))
+        ]
+    );
+    assert_eq!(typed.ranks, BTreeMap::from_iter([(1, Mode::Fast)]));
+    assert_eq!(
+        typed.groups,
+        BTreeMap::from_iter([("ssh".into(), vec![22]), ("web".into(), vec![80, 443])])
+    );
+    assert_eq!(typed.timeout, Duration::from_millis(250));
+    assert_eq!(typed.path, PathBuf::from("/var/lib/app"));
+    assert_eq!(typed.retries, None);
+    assert_eq!(typed.pair, (-7, "seven, 7".into()));
+    assert_eq!(typed.point, Some((1.5, -2.0, true)));
+
+    let typed = Typed::from_iter([("fallback", Some("fast"))]);
+    assert_eq!(typed.fallback, Some(Mode::Fast));
+
+    let mut limits = Limits { workers: NonZeroU32::MIN, memory: NonZero::<u64>::MAX };
+    limits.extend([("workers", Some("4")), ("memory", Some("1024"))]);
+    assert_eq!((limits.workers.get(), limits.memory.get()), (4, 1024));
+
+    assert_eq!("Fast".parse(), Ok(Mode::Fast));
+    assert_eq!(
+        "medium".parse::<Mode>(),
+        Err("Unknown value 'medium' of Mode, expected one of: fast, slow-mode".into())
+    );
+}
+
+#[derive(Debug, Default, ExtendFromIter)]
+struct Server {
+    port: u16,
+    timeout: Duration,
+    mode: Mode,
+    ports: HashMap<String, u16>,
+    workers: Vec<NonZeroU32>,
+    typed: Typed
+}
+
+#[test]
+fn test_try_extend_errors() {
+    let mut server = Server::default();
+    let errors = server
+        .try_extend([
+            ("port", Some("http")),
+            ("timeout", Some("5 weeks")),
+            ("mode", Some("medium")),
+            ("ports.ssh", Some("22")),
+            ("ports.ftp", Some("-21")),
+            ("workers", Some("1, 0, 2")),
+            ("typed.pair", Some("x, y")),
+            ("typed.mode", Some("slow-mode")),
+            ("typed", Some("bad, timeout = 1s")),
+            ("ports", Some("= 443, http = 80"))
+        ])
+        .unwrap_err();
+
+    let mut errors = errors
+        .iter()
+        .map(|e| (e.key.as_str(), e.value.as_str(), e.expected.as_str()))
+        .collect::<Vec<_>>();
+    errors.sort();
+    assert_eq!(
+        errors,
+        [
+            ("mode", "medium", "one of: fast, slow-mode"),
+            ("port", "http", "u16"),
+            ("ports", "= 443", "key = value"),
+            ("ports.ftp", "-21", "u16"),
+            (
+                "timeout", "5 weeks", "duration with unit: ns, us, ms, s, m, h, d"
+            ),
+            ("typed", "bad", "key = value"),
+            ("typed.pair", "x, y", "i32"),
+            ("workers", "0", "NonZeroU32")
+        ]
+    );
+
+    // Valid values are set anyway
+    assert_eq!(
+        server.ports,
+        HashMap::from_iter([("http".into(), 80), ("ssh".into(), 22)])
+    );
+    assert_eq!(server.typed.mode, Mode::Slow);
+    assert_eq!(server.typed.timeout, Duration::from_secs(1));
+
+    // Extend skips invalid values
+    let mut server = Server::default();
+    server.extend([("port", Some("http")), ("mode", Some("slow-mode"))]);
+    assert_eq!((server.port, server.mode), (0, Mode::Slow));
+
+    let error = ExtendError {
+        key: "actix.port".into(),
+        value: "http".into(),
+        expected: "u16".into(),
+        source: "env ACTIX_PORT".into()
+    };
+    assert_eq!(
+        error.to_string(),
+        "Invalid value 'http' of actix.port, expected u16 (env ACTIX_PORT)"
+    );
+}
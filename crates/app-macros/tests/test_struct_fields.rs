@@ -1,15 +1,18 @@
 #![allow(unused)]
 
 use {
-    app_macros::StructFields,
+    app_base::fields::StructFields as _,
+    app_macros::{ExtendFromIter, StructFields},
     core::{
         any::{Any, TypeId, type_name},
         marker::PhantomData,
         num::NonZero,
-        ops::Deref
+        ops::Deref,
+        str::FromStr
     },
     impls::impls,
-    std::fmt::Debug
+    serde::Serialize,
+    std::{collections::BTreeMap, fmt::Debug}
 };
 
 trait TraitA: Debug {}
@@ -115,3 +118,101 @@ fn test_struct_fields() {
         <Box<[&dyn TraitC]>>::from(&zar_parent as &Zar),
     );
 }
+
+#[derive(Debug, Default, StructFields, ExtendFromIter, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Product<'a, T: Default + FromStr> {
+    /// Name of product
+    ///
+    /// Shown in catalog
+    #[serde(rename = "title")]
+    name: String,
+    unit_price: u32,
+    #[skip_as]
+    tags: Vec<String>,
+    #[skip_as]
+    limits: BTreeMap<String, u32>,
+    #[serde(skip)]
+    #[skip_as]
+    note: &'a str,
+    #[skip_as]
+    #[extend_parse]
+    extra: Option<T>
+}
+
+#[test]
+fn test_struct_fields_reflection() {
+    let mut product = Product::<u8>::default();
+
+    let field = Product::<u8>::FIELDS[0];
+    assert_eq!(
+        (field.name, field.ty, field.serde_name, field.doc),
+        (
+            "name", "String", "title", "Name of product\n\nShown in catalog"
+        )
+    );
+    assert!(field.attrs.len() == 1 && field.has_attr("serde"));
+    let serde_names = Product::<u8>::FIELDS.iter().map(|f| f.serde_name);
+    assert_eq!(
+        serde_names.collect::<Vec<_>>(),
+        ["title", "unitPrice", "tags", "limits", "note", "extra"]
+    );
+    assert!(Product::<u8>::field("note").unwrap().has_attr("serde"));
+    assert_eq!(
+        Product::<u8>::field("unitPrice").unwrap().name,
+        "unit_price"
+    );
+
+    product.set_field_str("title", "Tea").unwrap();
+    product.set_field_str("unit_price", "25").unwrap();
+    product.set_field_str("tags", "green, loose").unwrap();
+    product.set_field_str("limits.max", "3").unwrap();
+    product.set_field_str("extra", "7").unwrap();
+    product.note = "Fresh";
+
+    assert_eq!(
+        product.get_field("name").unwrap().downcast_ref(),
+        Some(&"Tea".to_string())
+    );
+    assert_eq!(
+        product.get_field("unitPrice").unwrap().downcast_ref(),
+        Some(&25u32)
+    );
+    assert_eq!(
+        product.get_field("extra").unwrap().downcast_ref(),
+        Some(&Some(7u8))
+    );
+    assert_eq!(product.limits, BTreeMap::from_iter([("max".into(), 3)]));
+    assert_eq!(product.tags, ["green", "loose"]);
+    assert!(product.get_field("note").is_none());
+    assert!(product.get_field("unknown").is_none());
+
+    let errors = product.set_field_str("unit_price", "cheap").unwrap_err();
+    assert_eq!(
+        errors.to_string(),
+        "Invalid value 'cheap' of unit_price, expected u32"
+    );
+    let errors = product.set_field_str("price", "1").unwrap_err();
+    assert_eq!(errors[0].key, "price");
+
+    let cells: Vec<Cell> = product.fields_as().collect();
+    assert_eq!(cells, [Cell::Text("Tea".into()), Cell::Int(25)]);
+}
+
+#[derive(Debug, PartialEq)]
+enum Cell {
+    Int(i64),
+    Text(String)
+}
+
+impl From<&u32> for Cell {
+    fn from(value: &u32) -> Self {
+        Self::Int(*value as i64)
+    }
+}
+
+impl From<&String> for Cell {
+    fn from(value: &String) -> Self {
+        Self::Text(value.clone())
+    }
+}
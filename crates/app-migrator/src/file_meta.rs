@@ -77,13 +77,12 @@ impl TryFrom<&FileMeta> for (Migration, Migration) {
 
         let file_path_str = file_path.to_string_lossy().to_string();
         let file_name = &file_path_str[..file_path_str.find('.').unwrap()];
-        let app_env = Env::env();
-
         let mut sql_down = String::default();
-        for file in [
-            format!("{file_name}.{app_env}.down.sql"),
-            format!("{file_name}.down.sql")
-        ] {
+        for file in Env::chain()
+            .iter()
+            .map(|env| format!("{file_name}.{env}.down.sql"))
+            .chain([format!("{file_name}.down.sql")])
+        {
             let file_path_down = Path::new(&file);
             if file_path_down.exists() {
                 sql_down = read_to_string(file_path_down)?;
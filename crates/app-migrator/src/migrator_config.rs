@@ -1,7 +1,6 @@
 use {
     app_async::{TokioConfig, db::DbConfig},
     app_base::prelude::*,
-    core::fmt::Display,
     serde::{Deserialize, Serialize},
     std::sync::Arc
 };
@@ -15,15 +14,24 @@ pub trait MigratorConfigExt:
 {
 }
 
-#[derive(Debug, ExtendFromIter, Serialize, Deserialize)]
+#[derive(Debug, ExtendFromIter, AppConfig, Serialize, Deserialize)]
+#[config(prefix = "migrator", command = "migrator", after_args = "load_quiet")]
 pub struct MigratorConfig {
+    #[config(arg = "dir", short = "-d", env = "MIGRATOR_DIR", dir = "data")]
     pub dir: String,
+    #[config(arg = "dry-run", short = "-n", flag)]
     pub dry_run: bool,
+    #[config(arg = "simple", short = "-s", flag)]
     pub simple: bool,
+    #[config(arg = "verbose", short = "-v", flag)]
     pub verbose: bool,
+    #[config(short = "-D", env = "MIGRATOR_DATABASE_URL", secret)]
     pub db_url: String,
+    #[config(short = "-S", env = "MIGRATOR_DATABASE_SCHEMA")]
     pub db_schema: Option<String>,
+    #[config(env = "MIGRATOR_SCHEMA")]
     pub schema: String,
+    #[config(arg = "quiet", short = "-q", flag)]
     quiet: Option<bool>
 }
 
@@ -46,91 +54,15 @@ impl Default for MigratorConfig {
     }
 }
 
-impl Iter<'_, (&'static str, String)> for MigratorConfig {
-    fn iter(&self) -> impl Iterator<Item = (&'static str, String)> {
-        [
-            ("migrator.dir", &self.dir as &dyn Display),
-            ("migrator.dry_run", &self.dry_run),
-            ("migrator.simple", &self.simple),
-            ("migrator.verbose", &self.verbose),
-            ("migrator.db_url", &self.db_url),
-            (
-                "migrator.db_schema",
-                Box::leak(Box::new(self.db_schema.as_deref().unwrap_or_default()))
-            ),
-            ("migrator.schema", &self.schema)
-        ]
-        .into_iter()
-        .map(|(k, v)| (k, v.to_string()))
-    }
-}
-
-impl LoadArgs for MigratorConfig {
-    fn init_args(&mut self, args: &mut Args) {
-        if args.get("command").unwrap() == Some(Self::COMMAND) {
-            args.add_options([
-                ("dir", "-d".into(), None),
-                ("dry-run:b", "-n".into(), None),
-                ("simple:b", "-s".into(), None),
-                ("verbose:b", "-v".into(), None),
-                ("quiet:b", "-q".into(), None),
-                ("migrator-db-url", "-D".into(), None),
-                ("migrator-db-schema", "-S".into(), None),
-                ("migrator-schema", None, None)
-            ])
-            .unwrap();
-        }
-    }
-
-    fn load_args(&mut self, args: &Args) {
-        if args.get("command").unwrap() == Some(Self::COMMAND) {
-            self.extend(
-                [
-                    ("dir", args.get("dir")),
-                    ("dry_run", args.get("dry-run")),
-                    ("simple", args.get("simple")),
-                    ("verbose", args.get("verbose")),
-                    ("quiet", args.get("quiet")),
-                    ("db_url", args.get("migrator-db-url")),
-                    ("db_schema", args.get("migrator-db-schema")),
-                    ("schema", args.get("migrator-schema"))
-                ]
-                .iter()
-                .map(convert::tuple_result_option_str)
-            );
-
-            if let Some(quiet) = self.quiet {
-                self.verbose = !quiet;
-            }
+impl MigratorConfig {
+    /// `--quiet` turns verbose output off
+    fn load_quiet(&mut self, _args: &Args) {
+        if let Some(quiet) = self.quiet {
+            self.verbose = !quiet;
         }
     }
 }
 
-impl LoadDirs for MigratorConfig {
-    fn load_dirs(&mut self, dirs: &Dirs) {
-        if dirs.data.is_empty() == false && self.dir.starts_with("/") == false {
-            self.dir.insert(0, '/');
-            self.dir.insert_str(0, &dirs.data);
-        }
-    }
-}
-
-impl LoadEnv for MigratorConfig {
-    fn load_env(&mut self) {
-        #[rustfmt::skip]
-        self.extend(
-            [
-                ("dir", getenv("MIGRATOR_DIR")),
-                ("db_url", getenv("MIGRATOR_DATABASE_URL")),
-                ("db_schema", getenv("MIGRATOR_DATABASE_SCHEMA")),
-                ("schema", getenv("MIGRATOR_SCHEMA")),
-            ]
-            .iter()
-            .map(convert::tuple_option_str)
-        );
-    }
-}
-
 impl<C> LoadConfig<AppConfig<C>> for MigratorConfig
 where
     C: MigratorConfigExt
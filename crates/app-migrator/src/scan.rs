@@ -24,8 +24,8 @@ impl Scanner {
     #[allow(clippy::only_used_in_recursion)]
     fn find_inner(&self, path: &dyn AsRef<Path>) -> Ok<Vec<String>> {
         let mut files = Vec::new();
-        let mut files_exclude = Vec::new();
-        let app_env = Env::env();
+        let mut files_env: Vec<(String, String, usize)> = Vec::new();
+        let env_chain = Env::chain();
 
         for entry in read_dir(path).map_err(|err| {
             std::io::Error::new(
@@ -51,13 +51,19 @@ impl Scanner {
                 let env_in_file = file_name
                     .split_terminator('.')
                     .skip(1)
-                    .find(|s| ["dev", "prod", "test"].contains(s));
+                    .find(|s| Env::envs().any(|e| &e == s));
 
-                if env_in_file.is_none() {
-                    files.push(file_name.clone());
-                } else if env_in_file == Some(app_env) {
-                    files.push(file_name.clone());
-                    files_exclude.push(file_name.replace(&format!(".{app_env}."), "."));
+                match env_in_file {
+                    None => files.push(file_name.clone()),
+                    Some(env) => {
+                        // The nearest environment in inheritance chain wins
+                        if let Some(rank) =
+                            env_chain.iter().position(|e| e.as_ref() == env)
+                        {
+                            let file_base = file_name.replace(&format!(".{env}."), ".");
+                            files_env.push((file_name.clone(), file_base, rank));
+                        }
+                    }
                 }
             }
 
@@ -70,9 +76,17 @@ impl Scanner {
             }
         }
 
-        files_exclude.iter().for_each(|file| {
-            if let Some(pos) = files.iter().position(|f| f == file) {
-                files.remove(pos);
+        files_env.iter().for_each(|(file, file_base, rank)| {
+            let nearest = files_env
+                .iter()
+                .filter(|(.., base, ..)| base == file_base)
+                .all(|(.., r)| r >= rank);
+
+            if nearest {
+                if let Some(pos) = files.iter().position(|f| f == file_base) {
+                    files.remove(pos);
+                }
+                files.push(file.clone());
             }
         });
 
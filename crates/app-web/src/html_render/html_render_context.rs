@@ -6,7 +6,6 @@ use {
     },
     actix_web::{FromRequest, HttpMessage, HttpRequest, dev::Payload, web},
     app_base::prelude::*,
-    chrono::Local,
     serde::Serialize,
     serde_json::{Value, json},
     std::{borrow::Cow, cell::RefCell, future::Future, ops::Deref, pin::Pin, rc::Rc},
@@ -112,7 +111,7 @@ impl FromRequest for HtmlRenderContext {
             context.add("app", &app);
             //context.add("recaptcha", &req.recaptcha());
 
-            context.add("time", &Local::now());
+            context.add("time", &Time::now());
 
             req.extensions_mut().insert(context);
         }
@@ -125,6 +124,18 @@ impl FromRequest for HtmlRenderContext {
                 let context = extensions.get_mut::<Self>().unwrap();
                 let mut app = context.borrow_mut().remove("app").unwrap();
 
+                // time is shown in timezone of user
+                if let Some(time) = user
+                    .timezone()
+                    .as_deref()
+                    .and_then(|tz| Time::now_in(tz).ok())
+                {
+                    app.as_object_mut()
+                        .unwrap()
+                        .insert("timezone".into(), user.timezone().clone().into());
+                    context.borrow_mut().insert("time", &time);
+                }
+
                 app.as_object_mut()
                     .unwrap()
                     .insert("user".into(), json!(user));
@@ -0,0 +1,51 @@
+use {
+    app_base::prelude::*,
+    chrono::{DateTime as ChronoDateTime, NaiveDate, NaiveDateTime},
+    serde_json::Value,
+    std::collections::HashMap,
+    tera::{Error, Result}
+};
+
+/// Formats date in timezone, `base.timezone` by default:
+/// `{{ post.created_at | date_tz(format="%d.%m.%Y %H:%M", tz=app.timezone) }}`
+///
+/// Value is unix timestamp or RFC 3339 string, date and time without offset
+/// are in UTC.
+pub fn date_tz(value: &Value, args: &HashMap<String, Value>) -> Result<Value> {
+    let timestamp = match value {
+        Value::Number(n) => n.as_i64().or_else(|| n.as_f64().map(|v| v as i64)),
+        Value::String(s) => {
+            ChronoDateTime::parse_from_rfc3339(s)
+                .map(|v| v.timestamp())
+                .or_else(|_| {
+                    NaiveDateTime::parse_from_str(s, "%Y-%m-%dT%H:%M:%S%.f")
+                        .or_else(|_| {
+                            NaiveDateTime::parse_from_str(s, "%Y-%m-%d %H:%M:%S%.f")
+                        })
+                        .map(|v| v.and_utc().timestamp())
+                })
+                .or_else(|_| {
+                    NaiveDate::parse_from_str(s, "%Y-%m-%d")
+                        .map(|v| v.and_time(Default::default()).and_utc().timestamp())
+                })
+                .ok()
+        },
+        _ => None
+    }
+    .ok_or_else(|| {
+        Error::msg(format!("Filter `date_tz` received invalid value: {value}"))
+    })?;
+
+    let timezone = match args.get("tz").and_then(Value::as_str) {
+        Some(name) if name.is_empty() == false => {
+            Time::zone(name).map_err(|e| Error::msg(format!("Filter `date_tz`: {e}")))?
+        },
+        _ => Time::timezone()
+    };
+    let format = args
+        .get("format")
+        .and_then(Value::as_str)
+        .unwrap_or("%Y-%m-%d");
+
+    Ok(Value::String(timezone.at(timestamp, 0).format(format)))
+}
@@ -3,12 +3,15 @@
 //pub mod app_context_script;
 //mod entry;
 //mod is_granted;
-//mod translate;
+mod date;
+mod translate;
 mod utils;
 
 use {
     //self::{app_context_script::*, entry::*, is_granted::*, translate::*, utils::*},
+    date::*,
     tera::Tera,
+    translate::*,
     utils::*
 };
 
@@ -17,9 +20,11 @@ pub(super) fn register_modules(tera: &mut Tera) {
     //     tera.register_function("entry_script_tags", entry_script_tags);
     //     tera.register_function("app_context_script", app_context_script);
     //     tera.register_function("is_granted", is_granted);
-    //    tera.register_filter("t", translate);
     tera.register_function("dbg", dbg);
     tera.register_function("debug", debug);
     tera.register_function("is_debug", is_debug);
     tera.register_tester("is_null", is_null);
+    tera.register_filter("sanitize_html", SanitizeHtml);
+    tera.register_filter("t", translate);
+    tera.register_filter("date_tz", date_tz);
 }
@@ -0,0 +1,49 @@
+use {
+    app_base::prelude::*,
+    serde_json::Value,
+    std::{collections::HashMap, fmt::Display},
+    tera::{Result, try_get_value}
+};
+
+/// Arguments of `t` filter which are not placeholders
+const OPTIONS: [&str; 3] = ["plural", "context", "lang"];
+
+/// Translates message by `I18n` to the language of request:
+/// `{{ "Hello, {name}!" | t(name=user.name) }}`,
+/// `{{ "{n} file" | t(plural="{n} files", n=files | length) }}`,
+/// `{{ "Open" | t(context="menu") }}` or `{{ "Hello" | t(lang="en") }}`
+pub fn translate(value: &Value, args: &HashMap<String, Value>) -> Result<Value> {
+    let id = try_get_value!("t", "value", String, value);
+    let str_arg = |name: &str| args.get(name).and_then(Value::as_str);
+
+    let language = str_arg("lang")
+        .map(Into::into)
+        .unwrap_or_else(I18n::language);
+    let context = str_arg("context");
+
+    let message = match str_arg("plural") {
+        Some(plural) => {
+            let n = args.get("n").and_then(Value::as_u64).unwrap_or_default();
+            I18n::translate_plural(&language, context, &id, plural, n)
+        },
+        None => I18n::translate(&language, context, &id)
+    };
+
+    let values: Vec<(&str, String)> = args
+        .iter()
+        .filter(|(name, _)| OPTIONS.contains(&name.as_str()) == false)
+        .map(|(name, value)| {
+            let value = match value {
+                Value::String(value) => value.clone(),
+                value => value.to_string()
+            };
+            (name.as_str(), value)
+        })
+        .collect();
+    let values: Vec<(&str, &dyn Display)> = values
+        .iter()
+        .map(|(name, value)| (*name, value as &dyn Display))
+        .collect();
+
+    Ok(Value::String(I18n::format(message, &values)))
+}
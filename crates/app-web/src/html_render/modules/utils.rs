@@ -2,7 +2,7 @@ use {
     app_base::prelude::*,
     serde_json::Value,
     std::collections::HashMap,
-    tera::{Result, helpers::tests::number_args_allowed}
+    tera::{Filter, Result, helpers::tests::number_args_allowed, try_get_value}
 };
 
 pub fn dbg(args: &HashMap<String, Value>) -> Result<Value> {
@@ -26,3 +26,28 @@ pub fn is_null(value: Option<&Value>, params: &[Value]) -> Result<bool> {
         _ => Ok(false)
     }
 }
+
+/// Sanitizes HTML by the global `HtmlSanitizer`, output is not escaped:
+/// `{{ user.about | sanitize_html }}` or `{{ text |
+/// sanitize_html(nofollow=false) }}`
+pub struct SanitizeHtml;
+
+impl Filter for SanitizeHtml {
+    fn filter(&self, value: &Value, args: &HashMap<String, Value>) -> Result<Value> {
+        let html = try_get_value!("sanitize_html", "value", String, value);
+        let sanitizer = filters::HtmlSanitizer::from_static();
+
+        let html = match args.get("nofollow").and_then(Value::as_bool) {
+            Some(nofollow) if nofollow != sanitizer.nofollow => {
+                sanitizer.clone().with_nofollow(nofollow).sanitize(&html)
+            },
+            _ => sanitizer.sanitize(&html)
+        };
+
+        Ok(Value::String(html))
+    }
+
+    fn is_safe(&self) -> bool {
+        true
+    }
+}
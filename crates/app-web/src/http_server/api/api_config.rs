@@ -1,10 +1,10 @@
 use {
     app_base::prelude::*,
-    core::fmt::Display,
     serde::{Deserialize, Serialize}
 };
 
-#[derive(Debug, ExtendFromIter, Serialize, Deserialize)]
+#[derive(Debug, ExtendFromIter, AppConfig, Serialize, Deserialize)]
+#[config(prefix = "web.api", env_prefix = "WEB_API")]
 pub struct ApiConfig {
     pub url: String,
     pub path: String,
@@ -20,52 +20,3 @@ impl Default for ApiConfig {
         }
     }
 }
-
-impl Iter<'_, (&'static str, String)> for ApiConfig {
-    fn iter(&self) -> impl Iterator<Item = (&'static str, String)> {
-        [
-            ("web.api.url", &self.url as &dyn Display),
-            ("web.api.path", &self.path),
-            ("web.api.proxy_url", &self.proxy_url)
-        ]
-        .into_iter()
-        .map(|(k, v)| (k, v.to_string()))
-    }
-}
-
-impl LoadArgs for ApiConfig {
-    fn init_args(&mut self, args: &mut Args) {
-        args.add_options([
-            ("web-api-url", None, None),
-            ("web-api-path", None, None),
-            ("web-api-proxy-url", None, None)
-        ])
-        .unwrap();
-    }
-
-    fn load_args(&mut self, args: &app_base::prelude::Args) {
-        self.extend(
-            [
-                ("url", args.get("web-api-url")),
-                ("path", args.get("web-api-path")),
-                ("proxy_url", args.get("web-api-proxy-url"))
-            ]
-            .iter()
-            .map(convert::tuple_result_option_str)
-        );
-    }
-}
-
-impl LoadEnv for ApiConfig {
-    fn load_env(&mut self) {
-        self.extend(
-            [
-                ("url", getenv("WEB_API_URL")),
-                ("path", getenv("WEB_API_PATH")),
-                ("proxy_url", getenv("WEB_API_PROXY_URL"))
-            ]
-            .iter()
-            .map(convert::tuple_option_str)
-        );
-    }
-}
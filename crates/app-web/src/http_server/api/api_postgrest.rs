@@ -12,7 +12,6 @@ use {
     },
     app_base::prelude::*,
     bytes::Bytes,
-    chrono::{DateTime, Local, Utc},
     reqwest::{
         Client,
         header::{HeaderMap, HeaderName, HeaderValue}
@@ -106,7 +105,7 @@ pub async fn api_postgrest(
             .collect::<HashMap<_, _>>();
 
     if url.path() == "/" && (Env::is_prod() || query_params.contains_key("time")) {
-        let mut time: DateTime<Utc> = Utc::now();
+        let mut time = Time::now();
 
         if query_params.get("time").map(|v| v.as_ref()) == Some("sql") {
             let mut conn = req.db_pool().acquire().await?;
@@ -115,13 +114,9 @@ pub async fn api_postgrest(
                 .await?
                 .time
                 .unwrap();
-            time =
-                DateTime::from_timestamp(db_time.unix_timestamp(), db_time.nanosecond())
-                    .unwrap();
+            time = Time::timezone().at(db_time.unix_timestamp(), db_time.nanosecond());
         }
 
-        let time = time.with_timezone(&Local::now().timezone());
-
         return HttpResponse::Ok()
             .json(json!({"time": time.to_string()}))
             .into_ok();
@@ -54,8 +54,11 @@ impl DerefMut for AuthModules {
     }
 }
 
-impl<'a> Extend<(&'a str, Option<&'a str>)> for AuthModules {
-    fn extend<T: IntoIterator<Item = (&'a str, Option<&'a str>)>>(&mut self, iter: T) {
+impl<'a> TryExtend<(&'a str, Option<&'a str>)> for AuthModules {
+    fn try_extend<T: IntoIterator<Item = (&'a str, Option<&'a str>)>>(
+        &mut self,
+        iter: T
+    ) -> Result<(), ExtendErrors> {
         let mut map = IndexMap::<&str, HashMap<&str, Option<&str>>>::default();
 
         for (str, value) in iter.into_iter() {
@@ -67,36 +70,31 @@ impl<'a> Extend<(&'a str, Option<&'a str>)> for AuthModules {
             }
         }
 
+        let mut errors = ExtendErrors::default();
         for (name, params) in map {
-            self.modules
-                .insert(name.to_string(), AuthModuleConfig::from_iter(params));
+            let mut module = AuthModuleConfig::default();
+            errors.merge(module.try_extend(params).map_err(|e| e.key(name)));
+            self.modules.insert(name.to_string(), module);
         }
 
         self.modules
             .sort_by(|_, m1, _, m2| m2.url.len().cmp(&m1.url.len()));
+        errors.into_result()
     }
 }
 
-#[derive(Debug, Default, ExtendFromIter, Serialize, Deserialize)]
-pub struct AuthConfig {
-    #[serde(skip)]
-    pub modules: AuthModules
-}
-
-impl Iter<'_, (&'static str, String)> for AuthConfig {
-    fn iter(&self) -> impl Iterator<Item = (&'static str, String)> {
-        [("web.auth.modules", &self.modules as &dyn Display)]
-            .into_iter()
-            .map(|(k, v)| (k, v.to_string()))
+impl<'a> Extend<(&'a str, Option<&'a str>)> for AuthModules {
+    fn extend<T: IntoIterator<Item = (&'a str, Option<&'a str>)>>(&mut self, iter: T) {
+        if let Err(e) = self.try_extend(iter) {
+            panic!("{e}");
+        }
     }
 }
 
-impl LoadArgs for AuthConfig {
-    fn init_args(&mut self, _args: &mut Args) {}
-
-    fn load_args(&mut self, _args: &Args) {}
-}
-
-impl LoadEnv for AuthConfig {
-    fn load_env(&mut self) {}
+#[derive(Debug, Default, ExtendFromIter, AppConfig, Serialize, Deserialize)]
+#[config(prefix = "web.auth")]
+pub struct AuthConfig {
+    #[serde(skip)]
+    #[config(no_arg)]
+    pub modules: AuthModules
 }
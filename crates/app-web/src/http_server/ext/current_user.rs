@@ -80,6 +80,12 @@ pub struct User {
     short_name: String,
     birthday: Option<NaiveDate>,
     about: Option<String>,
+    /// Timezone of user, `base.timezone` is used if none
+    ///
+    /// Users table is defined by the app, so the column is optional and the
+    /// query without it reads none.
+    #[sqlx(default)]
+    timezone: Option<String>,
     confirmed_at: Option<DateTime<FixedOffset>>,
     blocked_at: Option<DateTime<FixedOffset>>,
     deleted_at: Option<DateTime<FixedOffset>>,
@@ -238,6 +244,7 @@ impl FromRequest for CurrentUser {
             }
             .await
             .inspect(|current_user| {
+                LogContext::insert("user_id", current_user.id().to_string());
                 req.extensions_mut()
                     .borrow_mut()
                     .insert(Ok(current_user.clone()) as Result<Self, String>);
@@ -11,6 +11,7 @@ mod ws;
 mod db_web;
 mod response;
 mod firewall_config;
+mod validate;
 
 pub use {
     auth_config::*,
@@ -25,5 +26,6 @@ pub use {
     //recaptcha::*,
     request::*,
     response::*,
+    validate::*,
     ws::*
 };
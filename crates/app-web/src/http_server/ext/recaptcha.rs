@@ -1,7 +1,7 @@
 use {
     super::RequestHeadExt,
     actix_web::HttpRequest,
-    app_base::prelude::*,
+    app_base::{prelude::*, t},
     reqwest::{Client, Method, Url},
     serde::{Deserialize, Serialize},
     serde_json::Value,
@@ -125,17 +125,12 @@ impl From<String> for CaptchaForm {
 }
 
 impl TryFrom<&Value> for CaptchaForm {
-    type Error = &'static str;
+    type Error = String;
 
     fn try_from(value: &Value) -> std::result::Result<Self, Self::Error> {
-        match value.get("captcha") {
-            Some(captcha) => {
-                match captcha.as_str() {
-                    Some(captcha) => Self::from_str(captcha),
-                    None => Err("Не указан проверочный код.")
-                }
-            },
-            None => Err("Не указан проверочный код.")
+        match value.get("captcha").and_then(Value::as_str) {
+            Some(captcha) => Self::from_str(captcha).map_err(Into::into),
+            None => Err(t!("Verification code is not specified."))
         }
     }
 }
@@ -151,7 +146,7 @@ impl CaptchaForm {
         if self.captcha.is_none() {
             Err(ValidationError::new_with_message(
                 "captcha",
-                "Введите проверочный код.",
+                t!("Enter the verification code."),
                 &[]
             ))?
         }
@@ -105,9 +105,9 @@ impl RequestExt for HttpRequest {
         }
 
         if let Some(header) = self.headers().get(header::ACCEPT_LANGUAGE)
-            && header.as_ref().len() >= 2
-            && let Ok(header_lang) = str::from_utf8(&header.as_ref()[0..2])
-            && config.locales.contains_key(header_lang)
+            && let Ok(header) = header.to_str()
+            && let Some(header_lang) =
+                I18n::negotiate(header, config.locales.keys().map(String::as_str))
         {
             return header_lang.into();
         }
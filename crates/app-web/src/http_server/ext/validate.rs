@@ -0,0 +1,12 @@
+use {app_base::prelude::*, std::borrow::Cow, validator::ValidationError};
+
+/// Custom rule of `validator`: `#[validate(custom(function =
+/// "validate_phone"))]`
+///
+/// Checks country code and length of phone number, numbers without country
+/// code are checked by rules of the default country.
+pub fn validate_phone(value: &str) -> Result<(), ValidationError> {
+    filters::normalize_phone(value).map(|_| ()).map_err(|e| {
+        ValidationError::new("phone").with_message(Cow::Owned(format!("{e}.")))
+    })
+}
@@ -7,7 +7,7 @@ use {
     actix_files::Files,
     actix_multipart::form::tempfile::TempFileConfig,
     actix_web::{
-        HttpRequest,
+        HttpRequest, HttpResponse,
         http::header::ContentType,
         middleware::from_fn,
         web::{self, ServiceConfig}
@@ -71,11 +71,14 @@ where
                 //.wrap(from_fn(app_web::middleware::firewall))
                 .wrap(from_fn(super::middleware::cache_control))
                 .wrap(super::middleware::AuthHeader)
+                .wrap(from_fn(super::middleware::language))
                 //.wrap(super::middleware::errors())
                 .wrap(super::middleware::cors(&web_config))
                 .wrap(actix_web::middleware::NormalizePath::trim())
                 .wrap(actix_web::middleware::DefaultHeaders::new())
-                .wrap(actix_web::middleware::Logger::default())
+                .wrap(from_fn(super::middleware::log_context))
+                .wrap(actix_web::middleware::Logger::new(super::middleware::ACCESS_LOG))
+                .wrap(from_fn(super::middleware::metrics()))
                 .configure({
                     // !this closure executes for each worker!
                     let configure = configure.clone();
@@ -130,6 +133,7 @@ where
             .with_db()
             .with_multipart()
             .with_cache()
+            .with_metrics()
             .with_static_files()
             .with_html_render()
             .with_api()
@@ -181,6 +185,27 @@ where
         })
     }
 
+    /// Serves metrics in Prometheus text format by `web.metrics_path`
+    pub fn with_metrics(&mut self) -> &mut Self {
+        self.add_service(|srv, server| {
+            let web_config = server.config.get::<WebConfig>();
+            if web_config.metrics_path.is_empty() {
+                return;
+            }
+
+            srv.route(
+                &web_config.metrics_path,
+                web::get().to(|| {
+                    async {
+                        HttpResponse::Ok()
+                            .content_type(Metrics::CONTENT_TYPE)
+                            .body(Metrics::from_static().render())
+                    }
+                })
+            );
+        })
+    }
+
     pub fn with_static_files(&mut self) -> &mut Self {
         self.add_service(|srv, server| {
             let web_config = server.config.get::<WebConfig>();
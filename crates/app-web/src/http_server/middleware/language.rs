@@ -0,0 +1,25 @@
+use {
+    crate::ext::RequestExt,
+    actix_web::{
+        Error,
+        body::BoxBody,
+        dev::{ServiceRequest, ServiceResponse},
+        middleware::Next
+    },
+    app_base::prelude::*,
+    std::sync::Arc
+};
+
+/// Sets language of request as the current language of `I18n`,
+/// so `t!()` and the `t` filter of templates translate to it
+pub async fn language(
+    req: ServiceRequest,
+    next: Next<BoxBody>
+) -> Result<ServiceResponse, Error> {
+    if req.app_data::<Arc<BaseConfig>>().is_none() {
+        return next.call(req).await;
+    }
+
+    let language = req.request().language().into_owned();
+    I18n::scope(language, next.call(req)).await
+}
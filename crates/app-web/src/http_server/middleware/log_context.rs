@@ -0,0 +1,53 @@
+use {
+    actix_http::header::{HeaderName, HeaderValue},
+    actix_web::{
+        Error,
+        body::BoxBody,
+        dev::{ServiceRequest, ServiceResponse},
+        middleware::Next
+    },
+    app_base::prelude::*,
+    uuid::Uuid
+};
+
+pub const X_REQUEST_ID: HeaderName = HeaderName::from_static("x-request-id");
+
+/// Format of access log with `request_id` from the response header, the access
+/// log line is written when the body is sent, outside of the log context scope
+pub const ACCESS_LOG: &str =
+    r#"%a "%r" %s %b "%{Referer}i" "%{User-Agent}i" %T request_id=%{x-request-id}o"#;
+
+/// Opens log context scope with `request_id` for each request
+///
+/// Request id is taken from `X-Request-Id` header or generated, it is
+/// returned in the response header. Incoming ids are accepted only of
+/// `[A-Za-z0-9._-]` up to 64 chars, so clients can't forge log fields.
+pub async fn log_context(
+    req: ServiceRequest,
+    next: Next<BoxBody>
+) -> Result<ServiceResponse, Error> {
+    let request_id = req
+        .headers()
+        .get(&X_REQUEST_ID)
+        .and_then(|v| v.to_str().ok())
+        .filter(|v| is_valid_request_id(v))
+        .map(|v| v.to_string())
+        .unwrap_or_else(|| Uuid::new_v4().to_string());
+
+    let mut res =
+        LogContext::scope([("request_id", request_id.clone())], next.call(req)).await?;
+
+    if let Ok(value) = HeaderValue::from_str(&request_id) {
+        res.headers_mut().insert(X_REQUEST_ID, value);
+    }
+
+    Ok(res)
+}
+
+/// Request id of `[A-Za-z0-9._-]`, 1..=64 chars
+fn is_valid_request_id(id: &str) -> bool {
+    (1..=64).contains(&id.len())
+        && id
+            .bytes()
+            .all(|b| b.is_ascii_alphanumeric() || matches!(b, b'.' | b'_' | b'-'))
+}
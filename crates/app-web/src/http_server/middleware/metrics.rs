@@ -0,0 +1,87 @@
+use {
+    actix_http::{Method, StatusCode},
+    actix_web::{
+        Error,
+        body::BoxBody,
+        dev::{ServiceRequest, ServiceResponse},
+        middleware::Next
+    },
+    app_base::prelude::*,
+    futures::future::LocalBoxFuture,
+    std::{cell::RefCell, collections::HashMap, rc::Rc, time::Instant}
+};
+
+/// Handles of request metrics by labels
+///
+/// Each worker builds own middleware, so handles are looked up in the global
+/// `Metrics` only on the first request of route, method and status.
+#[derive(Default)]
+struct HttpMetrics {
+    requests: RefCell<HashMap<(Method, String, StatusCode), Counter>>,
+    durations: RefCell<HashMap<(Method, String), Histogram>>
+}
+
+impl HttpMetrics {
+    fn observe(&self, method: Method, path: String, status: StatusCode, start: Instant) {
+        self.durations
+            .borrow_mut()
+            .entry((method.clone(), path.clone()))
+            .or_insert_with(|| {
+                Metrics::from_static().histogram(
+                    "http_request_duration_seconds",
+                    "Duration of HTTP requests",
+                    &[("method", method.as_str()), ("path", &path)],
+                    &[]
+                )
+            })
+            .observe_since(start);
+
+        self.requests
+            .borrow_mut()
+            .entry((method, path, status))
+            .or_insert_with_key(|(method, path, status)| {
+                Metrics::from_static().counter(
+                    "http_requests_total",
+                    "Number of HTTP requests",
+                    &[
+                        ("method", method.as_str()),
+                        ("path", path),
+                        ("status", status.as_str())
+                    ]
+                )
+            })
+            .inc();
+    }
+}
+
+/// Counts requests and observes their durations
+///
+/// Requests are labeled by route pattern, not by path, so the number of
+/// series does not grow with ids in paths.
+pub fn metrics() -> impl Fn(
+    ServiceRequest,
+    Next<BoxBody>
+) -> LocalBoxFuture<'static, Result<ServiceResponse, Error>> {
+    let metrics = Rc::new(HttpMetrics::default());
+    move |req, next| Box::pin(metrics_middleware(req, next, metrics.clone()))
+}
+
+async fn metrics_middleware(
+    req: ServiceRequest,
+    next: Next<BoxBody>,
+    metrics: Rc<HttpMetrics>
+) -> Result<ServiceResponse, Error> {
+    let start = Instant::now();
+    let method = req.method().clone();
+
+    let res = next.call(req).await;
+    let (status, path) = match &res {
+        Ok(res) => (res.status(), res.request().match_pattern()),
+        Err(e) => (e.as_response_error().status_code(), None)
+    };
+    let path = path.unwrap_or_else(|| "default".into());
+
+    metrics.observe(method, path, status, start);
+
+    res
+}
@@ -4,9 +4,12 @@ mod auth_required;
 mod auth_role;
 mod cache_control;
 mod content_type;
+mod language;
+mod log_context;
+mod metrics;
 //mod firewall;
 
 pub use {
     auth_header::*, auth_required::*, auth_role::*, cache_control::*, content_type::*,
-    cors::*
+    cors::*, language::*, log_context::*, metrics::*
 };
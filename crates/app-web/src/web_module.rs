@@ -1,5 +1,6 @@
 use {
     crate::{HttpServer, WebConfig, WebConfigExt},
+    app_async::{TokioConfig, actix_with_tokio_start},
     app_base::prelude::*,
     core::marker::PhantomData,
     futures::{executor::block_on, future::LocalBoxFuture},
@@ -44,13 +45,21 @@ where
         ok()
     }
 
-    fn run(&mut self, app: &mut App<Self::Config>) -> Void {
-        let mut server = app.take::<HttpServer<Self::Config>>().unwrap();
-
-        if self.enable_defaults {
-            server.with_defaults();
+    fn setup(&mut self, app: &mut App<Self::Config>) -> Void {
+        // Shared runtime of commands running together, i.e. `serve,worker`
+        if self.enable_runtime && app.has_runtime() == false {
+            let tokio_config = app.config().get::<TokioConfig>().clone();
+            app.set_runtime(move |future| {
+                actix_with_tokio_start(Some(&tokio_config), future)?
+            });
         }
 
+        ok()
+    }
+
+    fn run(&mut self, app: &mut App<Self::Config>) -> Void {
+        let server = self.server(app);
+
         if self.enable_runtime {
             let init = self.init_runtime.as_ref().map(|f| f());
             server.run_with_runtime(init)
@@ -58,6 +67,19 @@ where
             block_on(server.run())
         }
     }
+
+    fn run_async(&mut self, app: &mut App<Self::Config>) -> Ok<Option<AppFuture>> {
+        let server = self.server(app);
+        let init = self.init_runtime.as_ref().map(|f| f());
+
+        Some(Box::pin(async move {
+            if let Some(init) = init {
+                init.await?;
+            }
+            server.run().await
+        }) as AppFuture)
+        .into_ok()
+    }
 }
 
 impl<C> WebModule<C>
@@ -71,4 +93,14 @@ where
         self.init_runtime = Some(Arc::new(init));
         self
     }
+
+    fn server(&self, app: &mut App<C>) -> HttpServer<C> {
+        let mut server = app.take::<HttpServer<C>>().unwrap();
+
+        if self.enable_defaults {
+            server.with_defaults();
+        }
+
+        server
+    }
 }
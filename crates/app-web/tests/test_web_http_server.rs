@@ -3,10 +3,12 @@ use {
         HttpRequest, HttpResponse,
         body::MessageBody,
         dev::Service,
-        test::TestRequest,
+        middleware::from_fn,
+        test::{TestRequest, init_service},
         web::{self, ServiceConfig}
     },
     app_base::prelude::*,
+    app_web::middleware,
     common::TEST
 };
 
@@ -36,3 +38,33 @@ async fn test_http_server_success_response() -> Void {
     })
     .await
 }
+
+#[actix_web::test]
+async fn test_http_server_metrics() -> Void {
+    let app = init_service(
+        actix_web::App::new()
+            .wrap(from_fn(middleware::metrics()))
+            .route(
+                "/items/{id}",
+                web::get().to(|| async { HttpResponse::Ok().finish() })
+            )
+    )
+    .await;
+
+    for uri in ["/items/1", "/items/2", "/none"] {
+        app.call(TestRequest::with_uri(uri).to_request()).await?;
+    }
+
+    let metrics = Metrics::from_static().render();
+    assert!(metrics.contains(
+        "http_requests_total{method=\"GET\",path=\"/items/{id}\",status=\"200\"} 2\n"
+    ));
+    assert!(metrics.contains(
+        "http_requests_total{method=\"GET\",path=\"default\",status=\"404\"} 1\n"
+    ));
+    assert!(metrics.contains(
+        "http_request_duration_seconds_count{method=\"GET\",path=\"/items/{id}\"} 2\n"
+    ));
+
+    ok()
+}
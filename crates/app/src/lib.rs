@@ -10,6 +10,8 @@ use app_base::prelude::{
     App as AppBase,
     AppConfig as AppBaseConfig,
     AppConfigModule,
+    CrashesModule,
+    DirsModule,
     AppModule,
     AppModuleExt
 };
@@ -28,6 +30,8 @@ pub type App = AppBase<Config>;
 pub type AppConfig = AppBaseConfig<Config>;
 
 pub static MODULE_CONFIG: AppModule<Config> = AppConfigModule::handle;
+pub static MODULE_DIRS: AppModule<Config> = DirsModule::handle;
+pub static MODULE_CRASHES: AppModule<Config> = CrashesModule::handle;
 
 use app_base::prelude::AppSimpleModule;
 pub static MODULE_SIMPLE: AppModule<Config> = AppSimpleModule::handle;
@@ -36,6 +40,13 @@ mod main_module;
 pub use main_module::*;
 pub static MODULE_MAIN: AppModule<Config> = MainModule::handle;
 
+#[cfg(feature = "std")]
+mod worker_module;
+#[cfg(feature = "std")]
+pub use worker_module::*;
+#[cfg(feature = "std")]
+pub static MODULE_WORKER: AppModule<Config> = WorkerModule::handle;
+
 #[cfg(feature = "web")]
 use app_web::WebModule;
 #[cfg(feature = "web")]
@@ -18,8 +18,11 @@ fn main() -> Void {
     App::new([
             MODULE_MAIN,
             MODULE_CONFIG,
+            MODULE_DIRS,
+            MODULE_CRASHES,
             #[cfg(feature="web")]
             MODULE_WEB,
+            MODULE_WORKER,
             #[cfg(feature="migrator")]
             MODULE_MIGRATOR,
             #[cfg(feature="desktop")]
@@ -40,6 +43,8 @@ fn main(argc: c_int, argv: *const *const c_char) -> c_int {
     let mut app = App::new([
         MODULE_MAIN,
         MODULE_CONFIG,
+        MODULE_DIRS,
+        MODULE_CRASHES,
     ]);
 
     if let Err(e) = app.boot(argc, argv) {
@@ -13,8 +13,13 @@ impl AppModuleExt for MainModule {
     type Config = Config;
 
     fn boot(&mut self, app: &mut App) -> Void {
-        let mut mkdirs = [Self::COMMAND, AppConfigModule::<Self::Config>::COMMAND]
-            .contains(&app.command()?)
+        let mut mkdirs = [
+            Self::COMMAND,
+            AppConfigModule::<Self::Config>::COMMAND,
+            DirsModule::<Self::Config>::COMMAND,
+            CrashesModule::<Self::Config>::COMMAND
+        ]
+        .contains(&app.command()?)
             == false;
 
         mkdirs &= app.args().get_flag("help").unwrap() != true;
@@ -23,13 +28,11 @@ impl AppModuleExt for MainModule {
         if mkdirs {
             let config = app.config();
 
-            Dirs::mkdir(&config.dirs.var)?;
-            Dirs::mkdir(&config.dirs.run)?;
-            Dirs::mkdir(&config.dirs.log)?;
-            Dirs::mkdir(&config.dirs.tmp)?;
-            Dirs::mkdir(&config.dirs.cache)?;
-            Dirs::mkdir(&config.dirs.state)?;
-            Dirs::mkdir(&config.dirs.user_config)?;
+            for (name, path) in config.dirs.list() {
+                if Dirs::WRITABLE.contains(&name) {
+                    Dirs::mkdir(path)?;
+                }
+            }
         }
 
         ok()
@@ -39,10 +42,14 @@ impl AppModuleExt for MainModule {
     fn setup(&mut self, app: &mut App) -> Void {
         #[cfg(feature = "web")]
         {
-            let init_runtime = self.init_runtime(app);
+            // Queue is handled by worker if it runs together with server
+            let init_runtime = (app.is_command(WorkerModule::COMMAND) == false)
+                .then(|| self.init_runtime(app));
             if let Ok(web_module) = app.get_mut::<WebModule<Self::Config>>() {
                 web_module.enable_defaults = true;
-                web_module.with_init_runtime(init_runtime);
+                if let Some(init_runtime) = init_runtime {
+                    web_module.with_init_runtime(init_runtime);
+                }
             }
         }
 
@@ -65,11 +72,17 @@ Version: {name} {version}
 
 Commands:
     {:<LEN$} - {} (default)
+    {:<LEN$} - {}
+    {:<LEN$} - {}
     {:<LEN$} - {}"#,
             Self::COMMAND,
             Self::DESCRIPTION,
             AppConfigModule::<Self::Config>::COMMAND,
             AppConfigModule::<Self::Config>::DESCRIPTION,
+            DirsModule::<Self::Config>::COMMAND,
+            DirsModule::<Self::Config>::DESCRIPTION,
+            CrashesModule::<Self::Config>::COMMAND,
+            CrashesModule::<Self::Config>::DESCRIPTION,
             bin = config.dirs.exe_file(),
             version = config.version,
             name = config.name,
@@ -82,6 +95,18 @@ Commands:
             WebModule::<Self::Config>::DESCRIPTION,
         );
 
+        #[cfg(feature = "std")]
+        println!(
+            "    {:<LEN$} - {}",
+            WorkerModule::COMMAND,
+            WorkerModule::DESCRIPTION,
+        );
+
+        println!(
+            "    {:<LEN$} - {}",
+            "run", "runs several commands together, i.e. `run serve,worker`",
+        );
+
         #[cfg(feature = "migrator")]
         println!(
             "    {:<LEN$} - {}",
@@ -103,6 +128,7 @@ Options:
     --env-file file - loads env vars from file
     --debug         - enable debuging
     --version       - show current version
+    --timings       - show durations of boot steps on exit
 "#
         );
 
@@ -111,13 +137,7 @@ Options:
 }
 
 #[cfg(feature = "web")]
-use {
-    app_async::{
-        db::{DbConfig, DbNotifyListener, db_pool},
-        queue::QueueHandler
-    },
-    futures::future::LocalBoxFuture
-};
+use {app_async::db::DbConfig, futures::future::LocalBoxFuture};
 
 impl MainModule {
     #[cfg(feature = "web")]
@@ -125,23 +145,13 @@ impl MainModule {
         &self,
         app: &mut App
     ) -> impl Fn() -> LocalBoxFuture<'static, Void> + Send + Sync + 'static {
-        const NOTIFY_CHANNELS: [&str; 1] = ["app"];
         let db_config = app.config().get::<DbConfig>().clone();
 
         move || {
             let db_config = db_config.clone();
 
             async move {
-                use app_async::queue::QueueSimpleTaskHandler;
-
-                let db_pool = db_pool(Some(&db_config)).await?;
-                let queue_handler =
-                    QueueHandler::new(&db_pool, [QueueSimpleTaskHandler("test")]);
-                queue_handler.start_resend_periodically().await;
-
-                DbNotifyListener::new(NOTIFY_CHANNELS, &db_pool, queue_handler.handler())
-                    .start()
-                    .await;
+                WorkerModule::start(db_config).await?;
 
                 ok()
             }
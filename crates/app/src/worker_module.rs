@@ -0,0 +1,75 @@
+use {
+    crate::{App, Config},
+    app_async::{
+        TokioConfig,
+        db::{DbConfig, DbNotifyListener, db_pool},
+        queue::{QueueHandler, QueueSimpleTaskHandler},
+        tokio_start
+    },
+    app_base::prelude::*,
+    std::sync::Arc,
+    tokio::task::JoinHandle
+};
+
+/// Queue worker, it can run alone or together with http server:
+/// `app serve,worker`
+#[derive(Default)]
+pub struct WorkerModule;
+
+impl AppModuleExt for WorkerModule {
+    const COMMAND: &str = "worker";
+    const DESCRIPTION: &str = "handles queue tasks";
+
+    type Config = Config;
+
+    fn setup(&mut self, app: &mut App) -> Void {
+        if app.has_runtime() == false {
+            let tokio_config = app.config().get::<TokioConfig>().clone();
+            app.set_runtime(move |future| {
+                tokio_start(Some(&tokio_config))?.block_on(future)
+            });
+        }
+
+        ok()
+    }
+
+    fn run(&mut self, app: &mut App) -> Void {
+        let tokio_config = app.config().get::<TokioConfig>().clone();
+
+        tokio_start(Some(&tokio_config))?.block_on(Self::work(app))
+    }
+
+    fn run_async(&mut self, app: &mut App) -> Ok<Option<AppFuture>> {
+        Some(Box::pin(Self::work(app)) as AppFuture).into_ok()
+    }
+}
+
+impl WorkerModule {
+    pub const NOTIFY_CHANNELS: [&str; 1] = ["app"];
+
+    /// Starts handling of queue tasks, returns handle of DB listener
+    pub async fn start(
+        db_config: Arc<DbConfig>
+    ) -> Ok<JoinHandle<OkAsync<DbNotifyListener>>> {
+        let db_pool = db_pool(Some(&db_config)).await?;
+        let queue_handler = QueueHandler::new(&db_pool, [QueueSimpleTaskHandler("test")]);
+        queue_handler.start_resend_periodically().await;
+
+        DbNotifyListener::new(Self::NOTIFY_CHANNELS, &db_pool, queue_handler.handler())
+            .start()
+            .await
+            .into_ok()
+    }
+
+    /// Handles queue tasks until DB listener is stopped
+    fn work(app: &App) -> impl Future<Output = Void> + 'static {
+        let db_config = app.config().get::<DbConfig>().clone();
+
+        async move {
+            Self::start(db_config).await?.await??;
+            log::info!("Queue worker stopped");
+
+            ok()
+        }
+    }
+}
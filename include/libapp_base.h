@@ -8,6 +8,8 @@
 #include <stdlib.h>
 
 #define MODULE_APP_CONFIG module_app_config
+#define MODULE_DIRS module_dirs
+#define MODULE_CRASHES module_crashes
 
 
 
@@ -63,9 +65,16 @@ const void *app_error(const char *err);
 
 const void *module_app_config(App *app, enum AppEvent event);
 
+const void *module_dirs(App *app, enum AppEvent event);
+
+const void *module_crashes(App *app, enum AppEvent event);
+
 /**
  * Loads .env file variables
  *
+ * Loads `.env`, `.env.local`, `.env.{APP_ENV}` and `.env.{APP_ENV}.local`
+ * from `DOTENV_DIR` or current dir.
+ *
  * Returns zero if initialization is successfull.
  * Otherwise returns int less zero.
  */